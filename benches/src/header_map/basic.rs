@@ -549,6 +549,9 @@ const STD: &'static [HeaderName] = &[
     CONTENT_SECURITY_POLICY_REPORT_ONLY,
     CONTENT_TYPE,
     COOKIE,
+    CROSS_ORIGIN_EMBEDDER_POLICY,
+    CROSS_ORIGIN_OPENER_POLICY,
+    CROSS_ORIGIN_RESOURCE_POLICY,
     DNT,
     DATE,
     ETAG,
@@ -567,6 +570,7 @@ const STD: &'static [HeaderName] = &[
     LOCATION,
     MAX_FORWARDS,
     ORIGIN,
+    PERMISSIONS_POLICY,
     PRAGMA,
     PROXY_AUTHENTICATE,
     PROXY_AUTHORIZATION,
@@ -577,6 +581,10 @@ const STD: &'static [HeaderName] = &[
     REFERRER_POLICY,
     REFRESH,
     RETRY_AFTER,
+    SEC_FETCH_DEST,
+    SEC_FETCH_MODE,
+    SEC_FETCH_SITE,
+    SEC_FETCH_USER,
     SERVER,
     SET_COOKIE,
     STRICT_TRANSPORT_SECURITY,
@@ -592,6 +600,10 @@ const STD: &'static [HeaderName] = &[
     WWW_AUTHENTICATE,
     X_CONTENT_TYPE_OPTIONS,
     X_DNS_PREFETCH_CONTROL,
+    X_FORWARDED_FOR,
+    X_FORWARDED_HOST,
+    X_FORWARDED_PROTO,
     X_FRAME_OPTIONS,
+    X_REAL_IP,
     X_XSS_PROTECTION,
 ];
@@ -0,0 +1,25 @@
+#![no_main]
+
+use http::header::HeaderName;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let name = match HeaderName::from_bytes(data) {
+        Ok(name) => name,
+        Err(_) => {
+            // Only uppercase bytes may differ between the two parsers.
+            if !data.iter().any(u8::is_ascii_uppercase) {
+                assert!(HeaderName::from_lowercase(data).is_err());
+            }
+            return;
+        }
+    };
+
+    // Names are always stored lowercased, and parse back to themselves.
+    let input = std::str::from_utf8(data).expect("valid names are ASCII");
+    let s = name.as_str();
+    assert!(!s.bytes().any(|b| b.is_ascii_uppercase()));
+    assert!(s.eq_ignore_ascii_case(input));
+    assert_eq!(name, *input);
+    assert_eq!(HeaderName::from_lowercase(s.as_bytes()).unwrap(), name);
+});
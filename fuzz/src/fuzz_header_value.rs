@@ -0,0 +1,28 @@
+#![no_main]
+
+use bytes::Bytes;
+use http::fuzzing;
+use http::header::HeaderValue;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let valid = data.iter().all(|&b| b == b'\t' || (b >= 32 && b != 127));
+
+    let copied = HeaderValue::from_bytes(data);
+    let shared = fuzzing::header_value_from_shared(Bytes::copy_from_slice(data));
+    assert_eq!(copied.is_ok(), valid);
+    assert_eq!(shared.is_ok(), valid);
+
+    if let Ok(value) = copied {
+        assert_eq!(value.as_bytes(), data);
+        assert_eq!(shared.unwrap(), value);
+
+        // `to_str` only succeeds for visible ASCII and tabs.
+        let visible = data.iter().all(|&b| b == b'\t' || (b >= 32 && b < 127));
+        assert_eq!(value.to_str().is_ok(), visible);
+    }
+
+    if let Ok(s) = std::str::from_utf8(data) {
+        assert_eq!(HeaderValue::from_str(s).is_ok(), valid);
+    }
+});
@@ -0,0 +1,34 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use bytes::Bytes;
+use http::fuzzing;
+use http::Uri;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let shared = fuzzing::uri_from_shared(Bytes::copy_from_slice(data));
+    let copied = Uri::try_from(data);
+
+    // Both entry points must agree on what is a valid URI.
+    assert_eq!(shared.is_ok(), copied.is_ok());
+
+    if let Ok(uri) = shared {
+        // Anything we accept must round trip through its string form.
+        let reparsed: Uri = uri.to_string().parse().expect("reparse");
+        assert_eq!(uri, reparsed);
+
+        if let Some(authority) = uri.authority() {
+            let bytes = Bytes::copy_from_slice(authority.as_str().as_bytes());
+            fuzzing::authority_from_shared(bytes).expect("authority");
+        }
+        if let Some(path) = uri.path_and_query() {
+            let bytes = Bytes::copy_from_slice(path.as_str().as_bytes());
+            fuzzing::path_and_query_from_shared(bytes).expect("path and query");
+        }
+    }
+
+    let _ = fuzzing::authority_from_shared(Bytes::copy_from_slice(data));
+    let _ = fuzzing::path_and_query_from_shared(Bytes::copy_from_slice(data));
+});
@@ -0,0 +1,406 @@
+//! Evaluation of conditional requests.
+//!
+//! [`Validators`] describes the current state of a resource: whether it
+//! exists, its entity tag and its modification date. [`Validators::evaluate`]
+//! checks a request's `If-Match`, `If-None-Match`, `If-Modified-Since` and
+//! `If-Unmodified-Since` headers against them, in the order given by
+//! [RFC 9110, Section 13.2.2](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2),
+//! and [`Validators::if_range`] decides whether a `Range` header still applies.
+//!
+//! # Examples
+//!
+//! ```
+//! use http::conditional::{Precondition, Validators};
+//! use http::{Method, Request};
+//!
+//! let validators = Validators::new().etag("\"v2\"".parse().unwrap());
+//!
+//! let request = Request::get("/doc")
+//!     .header("if-none-match", "\"v1\", \"v2\"")
+//!     .body(())
+//!     .unwrap();
+//! assert_eq!(
+//!     validators.evaluate(request.method(), request.headers()),
+//!     Precondition::NotModified
+//! );
+//!
+//! let request = Request::put("/doc")
+//!     .header("if-match", "\"v1\"")
+//!     .body(())
+//!     .unwrap();
+//! assert_eq!(
+//!     validators.evaluate(request.method(), request.headers()),
+//!     Precondition::Failed
+//! );
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::header::typed::ETag;
+use crate::header::{self, HeaderMap, HeaderName, HeaderValue};
+use crate::method::Method;
+use crate::status::StatusCode;
+
+/// The validators of the selected representation of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validators {
+    exists: bool,
+    etag: Option<ETag>,
+    last_modified: Option<SystemTime>,
+}
+
+/// The outcome of evaluating the preconditions of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precondition {
+    /// All preconditions passed, and the request should be handled normally.
+    Proceed,
+    /// The client's cached copy is current. Respond with `304 Not Modified`.
+    NotModified,
+    /// A precondition failed. Respond with `412 Precondition Failed`.
+    Failed,
+}
+
+impl Validators {
+    /// Validators for a resource that exists, with no entity tag or
+    /// modification date.
+    pub fn new() -> Validators {
+        Validators {
+            exists: true,
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Validators for a resource that has no current representation.
+    ///
+    /// `If-Match: *` fails and `If-None-Match: *` passes for such a resource.
+    pub fn missing() -> Validators {
+        Validators {
+            exists: false,
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Sets the entity tag of the representation.
+    pub fn etag(mut self, etag: ETag) -> Validators {
+        self.etag = Some(etag);
+        self
+    }
+
+    /// Sets the modification date of the representation.
+    ///
+    /// HTTP dates have a resolution of one second, so any fraction of a
+    /// second is ignored when comparing.
+    pub fn last_modified(mut self, time: SystemTime) -> Validators {
+        self.last_modified = Some(time);
+        self
+    }
+
+    /// Evaluates the preconditions of a request with the given method and
+    /// headers.
+    ///
+    /// Headers that cannot be parsed are ignored, as RFC 9110 requires. The
+    /// `If-Range` header is not considered here; see
+    /// [`if_range`](Validators::if_range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::conditional::{Precondition, Validators};
+    /// use http::header::{HeaderMap, HeaderValue, IF_MODIFIED_SINCE};
+    /// use http::Method;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let modified = UNIX_EPOCH + Duration::from_secs(784111777);
+    /// let validators = Validators::new().last_modified(modified);
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(IF_MODIFIED_SINCE, HeaderValue::from_date(modified));
+    ///
+    /// assert_eq!(validators.evaluate(&Method::GET, &headers), Precondition::NotModified);
+    /// assert_eq!(validators.evaluate(&Method::POST, &headers), Precondition::Proceed);
+    /// ```
+    pub fn evaluate(&self, method: &Method, headers: &HeaderMap<HeaderValue>) -> Precondition {
+        let is_get_or_head = *method == Method::GET || *method == Method::HEAD;
+
+        // Step 1 and 2: the client's state-changing preconditions.
+        if headers.contains_key(header::IF_MATCH) {
+            if !self.matches_any(headers, header::IF_MATCH, ETag::strong_eq) {
+                return Precondition::Failed;
+            }
+        } else if let Some(since) = date(headers, header::IF_UNMODIFIED_SINCE) {
+            if let Some(modified) = self.last_modified_secs() {
+                if modified > since {
+                    return Precondition::Failed;
+                }
+            }
+        }
+
+        // Step 3 and 4: the client's cache validation.
+        if headers.contains_key(header::IF_NONE_MATCH) {
+            if self.matches_any(headers, header::IF_NONE_MATCH, ETag::weak_eq) {
+                return if is_get_or_head {
+                    Precondition::NotModified
+                } else {
+                    Precondition::Failed
+                };
+            }
+        } else if is_get_or_head {
+            if let Some(since) = date(headers, header::IF_MODIFIED_SINCE) {
+                if let Some(modified) = self.last_modified_secs() {
+                    if modified <= since {
+                        return Precondition::NotModified;
+                    }
+                }
+            }
+        }
+
+        Precondition::Proceed
+    }
+
+    /// Returns whether a `Range` header in the request should be honored.
+    ///
+    /// This is `true` when there is no `If-Range` header, or when its entity
+    /// tag or date matches the representation. Otherwise the whole
+    /// representation should be sent, as if there was no `Range`.
+    ///
+    /// An entity tag must match strongly, and a date must equal the
+    /// modification date exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::conditional::Validators;
+    /// use http::header::{HeaderMap, HeaderValue, IF_RANGE};
+    ///
+    /// let validators = Validators::new().etag("\"v2\"".parse().unwrap());
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// assert!(validators.if_range(&headers));
+    ///
+    /// headers.insert(IF_RANGE, HeaderValue::from_static("\"v1\""));
+    /// assert!(!validators.if_range(&headers));
+    /// ```
+    pub fn if_range(&self, headers: &HeaderMap<HeaderValue>) -> bool {
+        let value = match headers.get(header::IF_RANGE) {
+            Some(value) => value,
+            None => return true,
+        };
+
+        if value.as_bytes().starts_with(b"W/") || value.as_bytes().starts_with(b"\"") {
+            let tag = value.to_str().ok().and_then(|s| s.parse::<ETag>().ok());
+            match (tag, &self.etag) {
+                (Some(tag), Some(current)) => tag.strong_eq(current),
+                _ => false,
+            }
+        } else {
+            match (secs(value.to_date().ok()), self.last_modified_secs()) {
+                (Some(date), Some(modified)) => date == modified,
+                _ => false,
+            }
+        }
+    }
+
+    // Whether any entity tag in the `name` headers matches the current one,
+    // with `*` matching any current representation.
+    fn matches_any(
+        &self,
+        headers: &HeaderMap<HeaderValue>,
+        name: HeaderName,
+        eq: fn(&ETag, &ETag) -> bool,
+    ) -> bool {
+        for value in headers.get_all(name) {
+            let elements = match value.split_list() {
+                Ok(elements) => elements,
+                Err(_) => continue,
+            };
+            for element in elements {
+                if element == "*" {
+                    if self.exists {
+                        return true;
+                    }
+                } else if let (Ok(tag), Some(current)) = (element.parse::<ETag>(), &self.etag) {
+                    if eq(&tag, current) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn last_modified_secs(&self) -> Option<u64> {
+        secs(self.last_modified)
+    }
+}
+
+impl Default for Validators {
+    fn default() -> Validators {
+        Validators::new()
+    }
+}
+
+impl Precondition {
+    /// Returns the status code to respond with, if the request should not be
+    /// handled normally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::conditional::Precondition;
+    /// use http::StatusCode;
+    ///
+    /// assert_eq!(Precondition::Proceed.status(), None);
+    /// assert_eq!(Precondition::Failed.status(), Some(StatusCode::PRECONDITION_FAILED));
+    /// ```
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Precondition::Proceed => None,
+            Precondition::NotModified => Some(StatusCode::NOT_MODIFIED),
+            Precondition::Failed => Some(StatusCode::PRECONDITION_FAILED),
+        }
+    }
+}
+
+fn date(headers: &HeaderMap<HeaderValue>, name: HeaderName) -> Option<u64> {
+    // A recipient must ignore the header if it has several values.
+    let mut values = headers.get_all(name).iter();
+    match (values.next(), values.next()) {
+        (Some(value), None) => secs(value.to_date().ok()),
+        _ => None,
+    }
+}
+
+fn secs(time: Option<SystemTime>) -> Option<u64> {
+    time?.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const GET: &Method = &Method::GET;
+    const PUT: &Method = &Method::PUT;
+
+    fn headers(pairs: &[(HeaderName, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(name.clone(), HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn tagged(tag: &str) -> Validators {
+        Validators::new().etag(tag.parse().unwrap())
+    }
+
+    #[test]
+    fn if_match() {
+        let v = tagged("\"a\"");
+        let h = headers(&[(header::IF_MATCH, "\"b\", \"a\"")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+
+        let h = headers(&[(header::IF_MATCH, "W/\"a\"")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Failed);
+        assert_eq!(tagged("W/\"a\"").evaluate(PUT, &h), Precondition::Failed);
+
+        let h = headers(&[(header::IF_MATCH, "*")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+        assert_eq!(
+            Validators::missing().evaluate(PUT, &h),
+            Precondition::Failed
+        );
+
+        // If-Match takes precedence over If-Unmodified-Since.
+        let v = tagged("\"a\"").last_modified(at(2000));
+        let h = headers(&[
+            (header::IF_MATCH, "\"a\""),
+            (header::IF_UNMODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT"),
+        ]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_unmodified_since() {
+        let v = Validators::new().last_modified(at(1) + Duration::from_millis(500));
+        let h = headers(&[(header::IF_UNMODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+
+        let h = headers(&[(header::IF_UNMODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:00 GMT")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Failed);
+
+        let h = headers(&[(header::IF_UNMODIFIED_SINCE, "yesterday")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+        assert_eq!(Validators::new().evaluate(PUT, &h), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_none_match() {
+        let v = tagged("W/\"a\"");
+        let h = headers(&[
+            (header::IF_NONE_MATCH, "\"x\""),
+            (header::IF_NONE_MATCH, "\"a\""),
+        ]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::NotModified);
+        assert_eq!(v.evaluate(&Method::HEAD, &h), Precondition::NotModified);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Failed);
+
+        let h = headers(&[(header::IF_NONE_MATCH, "\"b\"")]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::Proceed);
+
+        let h = headers(&[(header::IF_NONE_MATCH, "*")]);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Failed);
+        assert_eq!(
+            Validators::missing().evaluate(PUT, &h),
+            Precondition::Proceed
+        );
+
+        // If-None-Match takes precedence over If-Modified-Since.
+        let v = tagged("\"a\"").last_modified(at(0));
+        let h = headers(&[
+            (header::IF_NONE_MATCH, "\"b\""),
+            (header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT"),
+        ]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_modified_since() {
+        let v = Validators::new().last_modified(at(1));
+        let h = headers(&[(header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT")]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::NotModified);
+        assert_eq!(v.evaluate(PUT, &h), Precondition::Proceed);
+
+        let h = headers(&[(header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:00 GMT")]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::Proceed);
+
+        let h = headers(&[
+            (header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT"),
+            (header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:01 GMT"),
+        ]);
+        assert_eq!(v.evaluate(GET, &h), Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_range() {
+        let v = tagged("\"a\"").last_modified(at(1));
+        assert!(v.if_range(&headers(&[(header::IF_RANGE, "\"a\"")])));
+        assert!(!v.if_range(&headers(&[(header::IF_RANGE, "W/\"a\"")])));
+        assert!(!v.if_range(&headers(&[(header::IF_RANGE, "\"b\"")])));
+        assert!(v.if_range(&headers(&[(
+            header::IF_RANGE,
+            "Thu, 01 Jan 1970 00:00:01 GMT"
+        )])));
+        assert!(!v.if_range(&headers(&[(
+            header::IF_RANGE,
+            "Thu, 01 Jan 1970 00:00:02 GMT"
+        )])));
+        assert!(!Validators::new().if_range(&headers(&[(header::IF_RANGE, "\"a\"")])));
+    }
+}
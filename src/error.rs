@@ -16,6 +16,9 @@ use crate::uri;
 /// of error for conversions with the `?` operator.
 pub struct Error {
     inner: ErrorKind,
+    // Further errors recorded after this one, such as by a builder that was
+    // given several invalid inputs.
+    next: Option<Box<Error>>,
 }
 
 /// A `Result` typedef to use with the `http::Error` type
@@ -33,16 +36,23 @@ enum ErrorKind {
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("http::Error")
-            // Skip the noise of the ErrorKind enum
-            .field(&self.get_ref())
-            .finish()
+        let mut f = f.debug_tuple("http::Error");
+        // Skip the noise of the ErrorKind enum
+        for err in self.errors() {
+            f.field(&err.get_ref());
+        }
+        f.finish()
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(self.get_ref(), f)
+        fmt::Display::fmt(self.get_ref(), f)?;
+        match self.errors().count() - 1 {
+            0 => Ok(()),
+            1 => f.write_str(" (and 1 more error)"),
+            n => write!(f, " (and {} more errors)", n),
+        }
     }
 }
 
@@ -66,6 +76,49 @@ impl Error {
             MaxSizeReached(ref e) => e,
         }
     }
+
+    /// Returns an iterator over this error and any further errors recorded
+    /// with it.
+    ///
+    /// A request or response builder keeps checking its inputs after the
+    /// first invalid one, so every problem can be reported at once. Other
+    /// errors only yield themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Request;
+    /// let err = Request::builder()
+    ///     .method("BAD METHOD")
+    ///     .header("x-ok", "fine")
+    ///     .header("bad header", "value")
+    ///     .body(())
+    ///     .unwrap_err();
+    ///
+    /// let errors: Vec<String> = err.errors().map(|e| e.get_ref().to_string()).collect();
+    /// assert_eq!(errors, ["invalid HTTP method", "invalid HTTP header name"]);
+    /// ```
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        let mut next = Some(self);
+        std::iter::from_fn(move || {
+            let err = next?;
+            next = err.next.as_deref();
+            Some(err)
+        })
+    }
+
+    /// Records `err` after every error already in this one.
+    pub(crate) fn push(&mut self, err: Error) {
+        let mut last = self;
+        while let Some(ref mut next) = last.next {
+            last = next;
+        }
+        last.next = Some(Box::new(err));
+    }
+
+    fn new(inner: ErrorKind) -> Error {
+        Error { inner, next: None }
+    }
 }
 
 impl error::Error for Error {
@@ -78,57 +131,43 @@ impl error::Error for Error {
 
 impl From<MaxSizeReached> for Error {
     fn from(err: MaxSizeReached) -> Error {
-        Error {
-            inner: ErrorKind::MaxSizeReached(err),
-        }
+        Error::new(ErrorKind::MaxSizeReached(err))
     }
 }
 
 impl From<status::InvalidStatusCode> for Error {
     fn from(err: status::InvalidStatusCode) -> Error {
-        Error {
-            inner: ErrorKind::StatusCode(err),
-        }
+        Error::new(ErrorKind::StatusCode(err))
     }
 }
 
 impl From<method::InvalidMethod> for Error {
     fn from(err: method::InvalidMethod) -> Error {
-        Error {
-            inner: ErrorKind::Method(err),
-        }
+        Error::new(ErrorKind::Method(err))
     }
 }
 
 impl From<uri::InvalidUri> for Error {
     fn from(err: uri::InvalidUri) -> Error {
-        Error {
-            inner: ErrorKind::Uri(err),
-        }
+        Error::new(ErrorKind::Uri(err))
     }
 }
 
 impl From<uri::InvalidUriParts> for Error {
     fn from(err: uri::InvalidUriParts) -> Error {
-        Error {
-            inner: ErrorKind::UriParts(err),
-        }
+        Error::new(ErrorKind::UriParts(err))
     }
 }
 
 impl From<header::InvalidHeaderName> for Error {
     fn from(err: header::InvalidHeaderName) -> Error {
-        Error {
-            inner: ErrorKind::HeaderName(err),
-        }
+        Error::new(ErrorKind::HeaderName(err))
     }
 }
 
 impl From<header::InvalidHeaderValue> for Error {
     fn from(err: header::InvalidHeaderValue) -> Error {
-        Error {
-            inner: ErrorKind::HeaderValue(err),
-        }
+        Error::new(ErrorKind::HeaderValue(err))
     }
 }
 
@@ -157,4 +196,26 @@ mod tests {
             panic!("Bad status allowed!");
         }
     }
+
+    #[test]
+    fn multiple_errors() {
+        let mut err: Error = status::StatusCode::from_u16(0).unwrap_err().into();
+        assert_eq!(err.errors().count(), 1);
+        assert_eq!(err.to_string(), "invalid status code");
+
+        err.push(method::Method::from_bytes(b"").unwrap_err().into());
+        err.push(header::HeaderName::from_bytes(b"").unwrap_err().into());
+        assert_eq!(err.errors().count(), 3);
+        assert!(err.is::<status::InvalidStatusCode>());
+        assert!(err
+            .errors()
+            .nth(2)
+            .unwrap()
+            .is::<header::InvalidHeaderName>());
+        assert_eq!(err.to_string(), "invalid status code (and 2 more errors)");
+        assert_eq!(
+            format!("{:?}", err),
+            "http::Error(InvalidStatusCode, InvalidMethod, InvalidHeaderName)"
+        );
+    }
 }
@@ -1,3 +1,9 @@
+//! The [`Extensions`] type map.
+//!
+//! With the `serde1` feature, this module also has the
+//! `ExtensionRegistry`, which lets selected extensions be serialized along
+//! with a message's parts.
+
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt;
@@ -302,6 +308,338 @@ impl Clone for Box<dyn AnyClone + Send + Sync> {
     }
 }
 
+#[cfg(feature = "serde1")]
+pub use self::serde1::{ExtensionRegistry, ExtensionTypes, PartsSeed, Registered, RegisteredParts};
+
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::Extensions;
+    use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, Visitor};
+    use serde::ser::{self, Serialize, SerializeMap, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    /// A set of extension types that can be serialized, each under a tag.
+    ///
+    /// The serde implementations of `request::Parts` and `response::Parts`
+    /// fail if there are any extensions, since `Extensions` can hold any
+    /// type. A registry names the types that should be kept instead: parts
+    /// serialized through [`wrap`](ExtensionRegistry::wrap) write those
+    /// extensions in an `extensions` map keyed by their tags, and parts
+    /// deserialized through [`seed`](ExtensionRegistry::seed) read them
+    /// back. Extensions of any other type, and unknown tags, are still
+    /// errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::ExtensionRegistry;
+    /// use http::request::Parts;
+    /// use http::Request;
+    /// use serde::de::DeserializeSeed;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TraceId(u64);
+    /// # impl serde::Serialize for TraceId {
+    /// #     fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    /// #         self.0.serialize(s)
+    /// #     }
+    /// # }
+    /// # impl<'de> serde::Deserialize<'de> for TraceId {
+    /// #     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    /// #         u64::deserialize(d).map(TraceId)
+    /// #     }
+    /// # }
+    ///
+    /// let registry = ExtensionRegistry::new().register::<TraceId>("trace-id");
+    ///
+    /// let (mut parts, _) = Request::get("/").body(()).unwrap().into_parts();
+    /// parts.extensions.insert(TraceId(42));
+    ///
+    /// let json = serde_json::to_string(&registry.wrap(&parts)).unwrap();
+    /// assert!(json.ends_with(r#""extensions":{"trace-id":42}}"#));
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(&json);
+    /// let parts = registry.seed::<Parts>().deserialize(&mut de).unwrap();
+    /// assert_eq!(parts.extensions.get(), Some(&TraceId(42)));
+    ///
+    /// let mut parts = parts;
+    /// parts.extensions.insert(7u8);
+    /// assert!(serde_json::to_string(&registry.wrap(&parts)).is_err());
+    /// ```
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ExtensionRegistry<L = ()> {
+        types: L,
+    }
+
+    /// The extension type `T`, registered after the types in `L`.
+    ///
+    /// This type is only used to build up the type of an
+    /// [`ExtensionRegistry`].
+    pub struct Registered<T, L> {
+        tag: &'static str,
+        rest: L,
+        _type: PhantomData<fn() -> T>,
+    }
+
+    /// Parts to be serialized with the extension types in a registry.
+    ///
+    /// This type is returned by [`ExtensionRegistry::wrap`].
+    #[derive(Debug)]
+    pub struct RegisteredParts<'a, P, L> {
+        pub(crate) parts: &'a P,
+        pub(crate) registry: &'a ExtensionRegistry<L>,
+    }
+
+    /// Deserializes parts, along with the extension types in a registry.
+    ///
+    /// This type is returned by [`ExtensionRegistry::seed`].
+    #[derive(Debug)]
+    pub struct PartsSeed<'a, P, L> {
+        pub(crate) registry: &'a ExtensionRegistry<L>,
+        _parts: PhantomData<fn() -> P>,
+    }
+
+    /// A marker trait for the lists of types an [`ExtensionRegistry`] can
+    /// hold.
+    pub trait ExtensionTypes: Sealed {}
+
+    // The methods live on this trait instead of `ExtensionTypes`, so they
+    // are not part of the public API.
+    pub trait Sealed {
+        #[doc(hidden)]
+        fn count(&self, extensions: &Extensions) -> usize;
+
+        #[doc(hidden)]
+        fn serialize_entries<M: SerializeMap>(
+            &self,
+            extensions: &Extensions,
+            map: &mut M,
+        ) -> Result<(), M::Error>;
+
+        #[doc(hidden)]
+        fn deserialize_entry<'de, A: MapAccess<'de>>(
+            &self,
+            tag: &str,
+            map: &mut A,
+            extensions: &mut Extensions,
+        ) -> Result<bool, A::Error>;
+    }
+
+    impl ExtensionRegistry<()> {
+        /// Creates a registry without any extension types.
+        pub fn new() -> ExtensionRegistry<()> {
+            ExtensionRegistry { types: () }
+        }
+    }
+
+    impl<L> ExtensionRegistry<L> {
+        /// Registers the extension type `T` under `tag`.
+        ///
+        /// If the same tag is registered twice, the type registered last is
+        /// used when deserializing.
+        pub fn register<T>(self, tag: &'static str) -> ExtensionRegistry<Registered<T, L>>
+        where
+            T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        {
+            ExtensionRegistry {
+                types: Registered {
+                    tag,
+                    rest: self.types,
+                    _type: PhantomData,
+                },
+            }
+        }
+
+        /// Returns a view of `parts` that serializes its extensions of the
+        /// registered types.
+        ///
+        /// `parts` is either a `request::Parts` or a `response::Parts`.
+        pub fn wrap<'a, P>(&'a self, parts: &'a P) -> RegisteredParts<'a, P, L> {
+            RegisteredParts {
+                parts,
+                registry: self,
+            }
+        }
+
+        /// Returns a seed that deserializes parts, along with their
+        /// extensions of the registered types.
+        ///
+        /// `P` is either `request::Parts` or `response::Parts`.
+        pub fn seed<P>(&self) -> PartsSeed<'_, P, L> {
+            PartsSeed {
+                registry: self,
+                _parts: PhantomData,
+            }
+        }
+    }
+
+    impl<L: ExtensionTypes> ExtensionRegistry<L> {
+        /// Returns a value that serializes `extensions` as a map of tags to
+        /// values, failing if any are of an unregistered type.
+        pub(crate) fn extensions<'a>(
+            &'a self,
+            extensions: &'a Extensions,
+        ) -> SerializeExtensions<'a, L> {
+            SerializeExtensions {
+                types: &self.types,
+                extensions,
+            }
+        }
+
+        /// Returns a seed that deserializes a map written by `extensions`.
+        pub(crate) fn extensions_seed(&self) -> ExtensionsSeed<'_, L> {
+            ExtensionsSeed { types: &self.types }
+        }
+    }
+
+    impl ExtensionTypes for () {}
+
+    impl Sealed for () {
+        fn count(&self, _: &Extensions) -> usize {
+            0
+        }
+
+        fn serialize_entries<M: SerializeMap>(
+            &self,
+            _: &Extensions,
+            _: &mut M,
+        ) -> Result<(), M::Error> {
+            Ok(())
+        }
+
+        fn deserialize_entry<'de, A: MapAccess<'de>>(
+            &self,
+            _: &str,
+            _: &mut A,
+            _: &mut Extensions,
+        ) -> Result<bool, A::Error> {
+            Ok(false)
+        }
+    }
+
+    impl<T, L> ExtensionTypes for Registered<T, L>
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        L: ExtensionTypes,
+    {
+    }
+
+    impl<T, L> Sealed for Registered<T, L>
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        L: ExtensionTypes,
+    {
+        fn count(&self, extensions: &Extensions) -> usize {
+            extensions.get::<T>().is_some() as usize + self.rest.count(extensions)
+        }
+
+        fn serialize_entries<M: SerializeMap>(
+            &self,
+            extensions: &Extensions,
+            map: &mut M,
+        ) -> Result<(), M::Error> {
+            self.rest.serialize_entries(extensions, map)?;
+            match extensions.get::<T>() {
+                Some(value) => map.serialize_entry(self.tag, value),
+                None => Ok(()),
+            }
+        }
+
+        fn deserialize_entry<'de, A: MapAccess<'de>>(
+            &self,
+            tag: &str,
+            map: &mut A,
+            extensions: &mut Extensions,
+        ) -> Result<bool, A::Error> {
+            if tag != self.tag {
+                return self.rest.deserialize_entry(tag, map, extensions);
+            }
+            extensions.insert(map.next_value::<T>()?);
+            Ok(true)
+        }
+    }
+
+    impl<T, L: fmt::Debug> fmt::Debug for Registered<T, L> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Registered")
+                .field("tag", &self.tag)
+                .field("rest", &self.rest)
+                .finish()
+        }
+    }
+
+    impl<T, L: Clone> Clone for Registered<T, L> {
+        fn clone(&self) -> Self {
+            Registered {
+                tag: self.tag,
+                rest: self.rest.clone(),
+                _type: PhantomData,
+            }
+        }
+    }
+
+    impl<T, L: Copy> Copy for Registered<T, L> {}
+
+    pub(crate) struct SerializeExtensions<'a, L> {
+        types: &'a L,
+        extensions: &'a Extensions,
+    }
+
+    impl<'a, L: ExtensionTypes> Serialize for SerializeExtensions<'a, L> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let len = self.types.count(self.extensions);
+            if len != self.extensions.len() {
+                return Err(ser::Error::custom(
+                    "extensions of unregistered types cannot be serialized",
+                ));
+            }
+            let mut map = serializer.serialize_map(Some(len))?;
+            self.types.serialize_entries(self.extensions, &mut map)?;
+            map.end()
+        }
+    }
+
+    pub(crate) struct ExtensionsSeed<'a, L> {
+        types: &'a L,
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> DeserializeSeed<'de> for ExtensionsSeed<'a, L> {
+        type Value = Extensions;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Extensions, D::Error> {
+            deserializer.deserialize_map(self)
+        }
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> Visitor<'de> for ExtensionsSeed<'a, L> {
+        type Value = Extensions;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map of extension tags to values")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Extensions, A::Error> {
+            let mut extensions = Extensions::new();
+            while let Some(tag) = map.next_key::<String>()? {
+                if !self
+                    .types
+                    .deserialize_entry(&tag, &mut map, &mut extensions)?
+                {
+                    return Err(de::Error::custom(format_args!(
+                        "unknown extension tag `{}`",
+                        tag
+                    )));
+                }
+            }
+            Ok(extensions)
+        }
+    }
+}
+
 #[test]
 fn test_extensions() {
     #[derive(Clone, Debug, PartialEq)]
@@ -327,3 +665,68 @@ fn test_extensions() {
     assert_eq!(extensions.get::<bool>(), None);
     assert_eq!(extensions.get(), Some(&MyType(10)));
 }
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_registry() {
+    use crate::{request, response, Request, Response};
+    use serde::de::DeserializeSeed;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Route(String);
+
+    impl serde::Serialize for Route {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Route {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer).map(Route)
+        }
+    }
+
+    let registry = ExtensionRegistry::new()
+        .register::<u64>("trace")
+        .register::<Route>("route");
+
+    let (mut parts, _) = Request::get("/").body(()).unwrap().into_parts();
+    parts.extensions.insert(7u64);
+    parts.extensions.insert(Route("users".into()));
+    let json = serde_json::to_string(&registry.wrap(&parts)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"method":"GET","uri":"/","version":"HTTP/1.1","headers":{},"trailers":null,"extensions":{"trace":7,"route":"users"}}"#
+    );
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let back = registry
+        .seed::<request::Parts>()
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(back.extensions.len(), 2);
+    assert_eq!(back.extensions.get(), Some(&7u64));
+    assert_eq!(back.extensions.get(), Some(&Route("users".into())));
+
+    // Without the registry, the extensions cannot be read back.
+    let err = serde_json::from_str::<request::Parts>(&json).unwrap_err();
+    assert!(err.to_string().contains("unknown extension tag `trace`"));
+
+    let (mut parts, _) = Response::new(()).into_parts();
+    let json = serde_json::to_string(&registry.wrap(&parts)).unwrap();
+    assert!(json.ends_with(r#""extensions":{}}"#));
+    let back: response::Parts = serde_json::from_str(&json).unwrap();
+    assert!(back.extensions.is_empty());
+
+    parts.extensions.insert(1u8);
+    let err = serde_json::to_string(&registry.wrap(&parts)).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("extensions of unregistered types cannot be serialized"));
+
+    let json = r#"{"status":"200","version":"HTTP/1.1","headers":{},"extensions":{"span":1}}"#;
+    let mut de = serde_json::Deserializer::from_str(json);
+    let err =
+        DeserializeSeed::deserialize(registry.seed::<response::Parts>(), &mut de).unwrap_err();
+    assert!(err.to_string().contains("unknown extension tag `span`"));
+}
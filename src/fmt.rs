@@ -0,0 +1,481 @@
+//! Human readable dumps of requests and responses.
+//!
+//! [`pretty`] renders a `Request` or `Response` the way it would look on the
+//! wire in HTTP/1.1, with a few changes that make the output easier to read in
+//! a terminal or in a test failure:
+//!
+//! * headers are sorted by name,
+//! * credentials, such as `Authorization` and `Cookie`, are redacted,
+//! * the body is cut short after a few hundred bytes.
+//!
+//! All of these can be changed through [`PrettyOptions`].
+//!
+//! # Examples
+//!
+//! ```
+//! use http::fmt::pretty;
+//! use http::Request;
+//!
+//! let request = Request::post("https://example.com/login")
+//!     .header("host", "example.com")
+//!     .header("authorization", "Bearer hunter2")
+//!     .body("user=ferris")
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     pretty(&request).to_string(),
+//!     "POST https://example.com/login HTTP/1.1\n\
+//!      authorization: <redacted>\n\
+//!      host: example.com\n\
+//!      \n\
+//!      user=ferris\n",
+//! );
+//! ```
+
+use std::fmt::{self, Write};
+use std::str;
+
+use crate::header::{self, HeaderName, HeaderValue};
+
+pub use self::message::Message;
+
+/// Returns a value that pretty prints `message` when displayed.
+///
+/// The default [`PrettyOptions`] are used, see [`Pretty::options`] to change
+/// them.
+pub fn pretty<M: Message>(message: &M) -> Pretty<'_, M> {
+    Pretty {
+        message,
+        options: PrettyOptions::new(),
+    }
+}
+
+/// A request or response that is pretty printed when displayed.
+///
+/// This struct is returned by [`pretty`].
+#[derive(Debug)]
+pub struct Pretty<'a, M> {
+    message: &'a M,
+    options: PrettyOptions,
+}
+
+/// Options controlling the output of [`pretty`].
+///
+/// # Examples
+///
+/// ```
+/// use http::fmt::{pretty, PrettyOptions};
+/// use http::Response;
+///
+/// let response = Response::builder()
+///     .header("set-cookie", "session=1234")
+///     .body(vec![b'a'; 10])
+///     .unwrap();
+///
+/// let options = PrettyOptions::new().body_limit(4).redact_none();
+/// assert_eq!(
+///     pretty(&response).options(options).to_string(),
+///     "HTTP/1.1 200 OK\n\
+///      set-cookie: session=1234\n\
+///      \n\
+///      aaaa\n\
+///      [... 6 more bytes]\n",
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrettyOptions {
+    color: bool,
+    width: Option<usize>,
+    body_limit: usize,
+    sort_headers: bool,
+    redact: Vec<HeaderName>,
+}
+
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Continuation lines of a wrapped header value are indented this much.
+const INDENT: &str = "    ";
+
+// ===== impl Pretty =====
+
+impl<'a, M> Pretty<'a, M> {
+    /// Sets the options used when displaying the message.
+    pub fn options(self, options: PrettyOptions) -> Pretty<'a, M> {
+        Pretty { options, ..self }
+    }
+}
+
+impl<'a, M: Message> Pretty<'a, M> {
+    fn paint(&self, f: &mut fmt::Formatter<'_>, style: &str, s: &str) -> fmt::Result {
+        if self.options.color {
+            write!(f, "{}{}{}", style, s, RESET)
+        } else {
+            f.write_str(s)
+        }
+    }
+
+    fn write_headers(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut headers: Vec<(&HeaderName, &HeaderValue)> = self.message.headers().iter().collect();
+        if self.options.sort_headers {
+            // The sort is stable, so values of the same header keep their order.
+            headers.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        }
+
+        for (name, value) in headers {
+            self.paint(f, CYAN, name.as_str())?;
+            f.write_str(": ")?;
+
+            if value.is_sensitive() || self.options.redact.contains(name) {
+                self.paint(f, DIM, header::REDACTED)?;
+                f.write_str("\n")?;
+                continue;
+            }
+
+            let value = escape(value.as_bytes());
+            let first = self
+                .options
+                .width
+                .map(|w| w.saturating_sub(name.as_str().len() + 2));
+            let rest = self.options.width.map(|w| w.saturating_sub(INDENT.len()));
+            for (i, line) in wrap(&value, first, rest).enumerate() {
+                if i > 0 {
+                    f.write_str(INDENT)?;
+                }
+                f.write_str(line)?;
+                f.write_str("\n")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_body(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = self.message.body();
+        if body.is_empty() {
+            return Ok(());
+        }
+
+        let mut shown = &body[..body.len().min(self.options.body_limit)];
+        if let Err(e) = str::from_utf8(shown) {
+            // Don't cut a character in half, which would show up as garbage.
+            if e.error_len().is_none() && shown.len() < body.len() {
+                shown = &shown[..e.valid_up_to()];
+            }
+        }
+
+        f.write_str("\n")?;
+        let text = String::from_utf8_lossy(shown);
+        for line in text.split_terminator('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let line = escape_controls(line);
+            for part in wrap(&line, self.options.width, self.options.width) {
+                f.write_str(part)?;
+                f.write_str("\n")?;
+            }
+        }
+
+        if shown.len() < body.len() {
+            let more = format!("[... {} more bytes]", body.len() - shown.len());
+            self.paint(f, DIM, &more)?;
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, M: Message> fmt::Display for Pretty<'a, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.paint(f, BOLD, &self.message.start_line())?;
+        f.write_str("\n")?;
+        self.write_headers(f)?;
+        self.write_body(f)
+    }
+}
+
+// ===== impl PrettyOptions =====
+
+impl PrettyOptions {
+    /// Creates the default options.
+    ///
+    /// These print without color or wrapping, sort headers, show up to 512
+    /// bytes of the body, and redact the `Authorization`,
+    /// `Proxy-Authorization`, `Cookie` and `Set-Cookie` headers.
+    pub fn new() -> PrettyOptions {
+        PrettyOptions {
+            color: false,
+            width: None,
+            body_limit: 512,
+            sort_headers: true,
+            redact: header::CREDENTIALS.to_vec(),
+        }
+    }
+
+    /// Sets whether to highlight the output with ANSI escape codes.
+    pub fn color(self, color: bool) -> PrettyOptions {
+        PrettyOptions { color, ..self }
+    }
+
+    /// Wraps lines longer than `width` characters.
+    ///
+    /// Continuation lines of a header value are indented.
+    pub fn width(self, width: usize) -> PrettyOptions {
+        PrettyOptions {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of body bytes to show.
+    pub fn body_limit(self, body_limit: usize) -> PrettyOptions {
+        PrettyOptions { body_limit, ..self }
+    }
+
+    /// Sets whether headers are sorted by name, or shown in map order.
+    pub fn sort_headers(self, sort_headers: bool) -> PrettyOptions {
+        PrettyOptions {
+            sort_headers,
+            ..self
+        }
+    }
+
+    /// Adds a header whose values are redacted.
+    pub fn redact(mut self, name: HeaderName) -> PrettyOptions {
+        self.redact.push(name);
+        self
+    }
+
+    /// Stops redacting any header by name.
+    ///
+    /// Values marked as [sensitive](HeaderValue::set_sensitive) are redacted
+    /// regardless.
+    pub fn redact_none(self) -> PrettyOptions {
+        PrettyOptions {
+            redact: Vec::new(),
+            ..self
+        }
+    }
+}
+
+impl Default for PrettyOptions {
+    fn default() -> PrettyOptions {
+        PrettyOptions::new()
+    }
+}
+
+/// Escapes bytes that are not visible ASCII as `\xNN`.
+fn escape(bytes: &[u8]) -> String {
+    let mut dst = String::with_capacity(bytes.len());
+    for &b in bytes {
+        if b == b'\t' || (b' '..=b'~').contains(&b) {
+            dst.push(b as char);
+        } else {
+            write!(dst, "\\x{:02x}", b).unwrap();
+        }
+    }
+    dst
+}
+
+/// Escapes control characters, other than tabs, as `\xNN`.
+fn escape_controls(s: &str) -> String {
+    let mut dst = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_control() && c != '\t' && (c as u32) < 0x100 {
+            write!(dst, "\\x{:02x}", c as u32).unwrap();
+        } else {
+            dst.push(c);
+        }
+    }
+    dst
+}
+
+/// Splits `s` into lines of at most `first` characters, then `rest`.
+///
+/// Every line holds at least one character, however small the widths.
+fn wrap(s: &str, first: Option<usize>, rest: Option<usize>) -> impl Iterator<Item = &str> {
+    let mut s = s;
+    let mut width = first;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let end = match width {
+            Some(w) => s.char_indices().nth(w.max(1)).map(|(i, _)| i),
+            None => None,
+        };
+        width = rest;
+        match end {
+            Some(end) => {
+                let (line, tail) = s.split_at(end);
+                s = tail;
+                Some(line)
+            }
+            None => {
+                done = true;
+                Some(s)
+            }
+        }
+    })
+}
+
+mod message {
+    use crate::header::HeaderMap;
+    use crate::{Request, Response};
+
+    /// A request or response that can be pretty printed.
+    ///
+    /// This trait is sealed, and implemented for `Request<B>` and
+    /// `Response<B>` with bodies that are `AsRef<[u8]>`.
+    pub trait Message: Sealed {}
+
+    // All methods are on this pub(super) trait, instead of `Message`, so
+    // that they aren't publicly exposed to the world.
+    pub trait Sealed {
+        #[doc(hidden)]
+        fn start_line(&self) -> String;
+
+        #[doc(hidden)]
+        fn headers(&self) -> &HeaderMap;
+
+        #[doc(hidden)]
+        fn body(&self) -> &[u8];
+    }
+
+    impl<B: AsRef<[u8]>> Sealed for Request<B> {
+        fn start_line(&self) -> String {
+            format!("{} {} {:?}", self.method(), self.uri(), self.version())
+        }
+
+        fn headers(&self) -> &HeaderMap {
+            Request::headers(self)
+        }
+
+        fn body(&self) -> &[u8] {
+            Request::body(self).as_ref()
+        }
+    }
+
+    impl<B: AsRef<[u8]>> Message for Request<B> {}
+
+    impl<B: AsRef<[u8]>> Sealed for Response<B> {
+        fn start_line(&self) -> String {
+            format!("{:?} {}", self.version(), self.status())
+        }
+
+        fn headers(&self) -> &HeaderMap {
+            Response::headers(self)
+        }
+
+        fn body(&self) -> &[u8] {
+            Response::body(self).as_ref()
+        }
+    }
+
+    impl<B: AsRef<[u8]>> Message for Response<B> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Request, Response};
+
+    #[test]
+    fn keeps_map_order_when_not_sorting() {
+        let req = Request::get("/")
+            .header("b", "1")
+            .header("a", "2")
+            .header("b", "3")
+            .body("")
+            .unwrap();
+
+        assert_eq!(
+            pretty(&req).to_string(),
+            "GET / HTTP/1.1\na: 2\nb: 1\nb: 3\n"
+        );
+        assert_eq!(
+            pretty(&req)
+                .options(PrettyOptions::new().sort_headers(false))
+                .to_string(),
+            "GET / HTTP/1.1\nb: 1\nb: 3\na: 2\n"
+        );
+    }
+
+    #[test]
+    fn redacts_sensitive_and_configured_headers() {
+        let mut secret = HeaderValue::from_static("s3cret");
+        secret.set_sensitive(true);
+        let res = Response::builder()
+            .header("x-api-key", secret)
+            .header("x-token", "abc")
+            .header("cookie", "a=1")
+            .body(Vec::new())
+            .unwrap();
+
+        let options = PrettyOptions::new()
+            .redact_none()
+            .redact(HeaderName::from_static("x-token"));
+        assert_eq!(
+            pretty(&res).options(options).to_string(),
+            "HTTP/1.1 200 OK\ncookie: a=1\nx-api-key: <redacted>\nx-token: <redacted>\n"
+        );
+    }
+
+    #[test]
+    fn escapes_and_wraps() {
+        let req = Request::get("/")
+            .header(
+                "x-long",
+                HeaderValue::from_bytes(b"abcdefgh\xffij").unwrap(),
+            )
+            .body("line one\r\nbell\x07")
+            .unwrap();
+
+        assert_eq!(
+            pretty(&req)
+                .options(PrettyOptions::new().width(12))
+                .to_string(),
+            "GET / HTTP/1.1\n\
+             x-long: abcd\n    efgh\\xff\n    ij\n\
+             \n\
+             line one\n\
+             bell\\x07\n"
+        );
+    }
+
+    #[test]
+    fn truncates_body_on_char_boundary() {
+        let res = Response::new("h\u{e9}llo");
+
+        assert_eq!(
+            pretty(&res)
+                .options(PrettyOptions::new().body_limit(2))
+                .to_string(),
+            "HTTP/1.1 200 OK\n\nh\n[... 5 more bytes]\n"
+        );
+    }
+
+    #[test]
+    fn color() {
+        let res = Response::builder()
+            .header("host", "a")
+            .header("cookie", "b")
+            .body(&b"xyz"[..])
+            .unwrap();
+
+        assert_eq!(
+            pretty(&res)
+                .options(PrettyOptions::new().color(true).body_limit(1))
+                .to_string(),
+            "\x1b[1mHTTP/1.1 200 OK\x1b[0m\n\
+             \x1b[36mcookie\x1b[0m: \x1b[2m<redacted>\x1b[0m\n\
+             \x1b[36mhost\x1b[0m: a\n\
+             \n\
+             x\n\
+             \x1b[2m[... 2 more bytes]\x1b[0m\n"
+        );
+    }
+}
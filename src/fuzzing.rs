@@ -0,0 +1,30 @@
+//! Internal entry points for fuzz targets.
+//!
+//! This module is only available with the `fuzzing` feature. It exposes
+//! parsing functions that are otherwise private, so that fuzz targets can
+//! call them directly. Nothing here is covered by semver.
+
+use bytes::Bytes;
+
+use crate::header::{HeaderValue, InvalidHeaderValue};
+use crate::uri::{Authority, InvalidUri, PathAndQuery, Uri};
+
+/// Parses a `Uri` from a shared buffer, without copying it.
+pub fn uri_from_shared(src: Bytes) -> Result<Uri, InvalidUri> {
+    Uri::from_shared(src)
+}
+
+/// Parses an `Authority` from a shared buffer, without copying it.
+pub fn authority_from_shared(src: Bytes) -> Result<Authority, InvalidUri> {
+    Authority::from_shared(src)
+}
+
+/// Parses a `PathAndQuery` from a shared buffer, without copying it.
+pub fn path_and_query_from_shared(src: Bytes) -> Result<PathAndQuery, InvalidUri> {
+    PathAndQuery::from_shared(src)
+}
+
+/// Validates a `HeaderValue` held in a shared buffer, without copying it.
+pub fn header_value_from_shared(src: Bytes) -> Result<HeaderValue, InvalidHeaderValue> {
+    HeaderValue::from_shared(src)
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// A content coding, as listed in the `Content-Encoding` header.
+///
+/// Returned by [`HeaderMap::content_encodings`](super::HeaderMap::content_encodings).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentCoding<'a> {
+    /// `gzip`, or its `x-gzip` alias
+    Gzip,
+    /// `deflate`
+    Deflate,
+    /// `br` (Brotli)
+    Br,
+    /// `zstd` (Zstandard)
+    Zstd,
+    /// `compress`, or its `x-compress` alias
+    Compress,
+    /// `identity`, which leaves the content unchanged
+    Identity,
+    /// A coding this crate does not recognize.
+    ///
+    /// Holds the token as written, or an empty string if the header value was
+    /// not valid text.
+    Unknown(&'a str),
+}
+
+impl<'a> ContentCoding<'a> {
+    pub(super) fn from_token(token: &'a str) -> ContentCoding<'a> {
+        const KNOWN: &[(&str, ContentCoding<'static>)] = &[
+            ("gzip", ContentCoding::Gzip),
+            ("x-gzip", ContentCoding::Gzip),
+            ("deflate", ContentCoding::Deflate),
+            ("br", ContentCoding::Br),
+            ("zstd", ContentCoding::Zstd),
+            ("compress", ContentCoding::Compress),
+            ("x-compress", ContentCoding::Compress),
+            ("identity", ContentCoding::Identity),
+        ];
+
+        KNOWN
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(token))
+            .map(|&(_, coding)| coding)
+            .unwrap_or(ContentCoding::Unknown(token))
+    }
+
+    /// Returns the canonical token for this coding.
+    pub fn as_str(&self) -> &'a str {
+        match *self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Deflate => "deflate",
+            ContentCoding::Br => "br",
+            ContentCoding::Zstd => "zstd",
+            ContentCoding::Compress => "compress",
+            ContentCoding::Identity => "identity",
+            ContentCoding::Unknown(token) => token,
+        }
+    }
+
+    /// Returns `true` if this coding is recognized.
+    pub fn is_known(&self) -> bool {
+        !matches!(*self, ContentCoding::Unknown(_))
+    }
+}
+
+impl<'a> fmt::Display for ContentCoding<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
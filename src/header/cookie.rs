@@ -0,0 +1,240 @@
+use std::hash::BuildHasher;
+use std::iter::FusedIterator;
+use std::str;
+
+use super::map::{HeaderMap, ValueIter};
+use super::name::{COOKIE, SET_COOKIE};
+use super::value::HeaderValue;
+
+/// An iterator over the `name=value` pairs of the `Cookie` headers in a
+/// `HeaderMap`.
+///
+/// Returned by [`HeaderMap::cookies`].
+#[derive(Debug)]
+pub struct Cookies<'a> {
+    values: ValueIter<'a, HeaderValue>,
+    rest: &'a str,
+}
+
+/// An iterator over the `Set-Cookie` headers in a `HeaderMap`.
+///
+/// Returned by [`HeaderMap::set_cookies`].
+#[derive(Debug)]
+pub struct SetCookies<'a> {
+    values: ValueIter<'a, HeaderValue>,
+}
+
+/// A single `Set-Cookie` header, split into the cookie and its attributes.
+///
+/// Returned by [`SetCookies`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCookie<'a> {
+    name: &'a str,
+    value: &'a str,
+    attributes: &'a str,
+    raw: &'a HeaderValue,
+}
+
+impl<S: BuildHasher> HeaderMap<HeaderValue, S> {
+    /// Returns an iterator over the cookies sent in `Cookie` headers.
+    ///
+    /// Every `Cookie` header is read, since HTTP/2 and HTTP/3 allow the
+    /// header to be split across several fields. Pairs are separated by `;`,
+    /// and whitespace around names and values is trimmed. Pairs without a
+    /// `=`, and values that are not UTF-8, are skipped. Values are returned
+    /// as they were sent, including any surrounding double quotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::COOKIE;
+    /// let mut map = HeaderMap::new();
+    /// map.append(COOKIE, "session=abc; theme=dark;".parse().unwrap());
+    /// map.append(COOKIE, "lang = en".parse().unwrap());
+    ///
+    /// let cookies: Vec<_> = map.cookies().collect();
+    /// assert_eq!(cookies, [("session", "abc"), ("theme", "dark"), ("lang", "en")]);
+    /// ```
+    pub fn cookies(&self) -> Cookies<'_> {
+        Cookies {
+            values: self.get_all(COOKIE).iter(),
+            rest: "",
+        }
+    }
+
+    /// Returns an iterator over the cookies set by `Set-Cookie` headers.
+    ///
+    /// Each header is split into the cookie's name and value, and its
+    /// unparsed attributes. Headers without a `=` in the cookie, and values
+    /// that are not UTF-8, are skipped, as RFC 6265 requires of user agents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::SET_COOKIE;
+    /// let mut map = HeaderMap::new();
+    /// map.append(SET_COOKIE, "session=abc; Path=/; HttpOnly".parse().unwrap());
+    /// map.append(SET_COOKIE, "theme=dark".parse().unwrap());
+    ///
+    /// let mut cookies = map.set_cookies();
+    /// let session = cookies.next().unwrap();
+    /// assert_eq!(session.name(), "session");
+    /// assert_eq!(session.value(), "abc");
+    /// assert_eq!(session.attributes(), "Path=/; HttpOnly");
+    /// assert_eq!(cookies.next().unwrap().name(), "theme");
+    /// assert!(cookies.next().is_none());
+    /// ```
+    pub fn set_cookies(&self) -> SetCookies<'_> {
+        SetCookies {
+            values: self.get_all(SET_COOKIE).iter(),
+        }
+    }
+}
+
+impl<'a> SetCookie<'a> {
+    /// Returns the name of the cookie.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the value of the cookie, as it was sent.
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// Returns the attributes after the cookie, such as `Path=/; Secure`,
+    /// without parsing them.
+    pub fn attributes(&self) -> &'a str {
+        self.attributes
+    }
+
+    /// Returns the whole header value.
+    pub fn header_value(&self) -> &'a HeaderValue {
+        self.raw
+    }
+}
+
+/// Splits `name=value`, trimming both, or returns `None` if there is no `=`.
+fn split_pair(pair: &str) -> Option<(&str, &str)> {
+    let eq = pair.find('=')?;
+    Some((trim(&pair[..eq]), trim(&pair[eq + 1..])))
+}
+
+fn trim(s: &str) -> &str {
+    s.trim_matches(|c| c == ' ' || c == '\t')
+}
+
+impl<'a> Iterator for Cookies<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> {
+        loop {
+            if self.rest.is_empty() {
+                let value = self.values.next()?;
+                self.rest = str::from_utf8(value.as_bytes()).unwrap_or("");
+                continue;
+            }
+
+            let (pair, rest) = match self.rest.find(';') {
+                Some(i) => (&self.rest[..i], &self.rest[i + 1..]),
+                None => (self.rest, ""),
+            };
+            self.rest = rest;
+
+            if let Some(pair) = split_pair(pair) {
+                return Some(pair);
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for Cookies<'a> {}
+
+impl<'a> Iterator for SetCookies<'a> {
+    type Item = SetCookie<'a>;
+
+    fn next(&mut self) -> Option<SetCookie<'a>> {
+        for raw in &mut self.values {
+            let s = match str::from_utf8(raw.as_bytes()) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let (pair, attributes) = match s.find(';') {
+                Some(i) => (&s[..i], trim(&s[i + 1..])),
+                None => (s, ""),
+            };
+            if let Some((name, value)) = split_pair(pair) {
+                return Some(SetCookie {
+                    name,
+                    value,
+                    attributes,
+                    raw,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl<'a> FusedIterator for SetCookies<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::HeaderName;
+
+    fn map(name: HeaderName, values: &[&'static [u8]]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for value in values {
+            map.append(name.clone(), HeaderValue::from_bytes(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn cookies() {
+        let map = map(
+            COOKIE,
+            &[
+                b"a=1;b=2 ;  c = \"x y\" ",
+                b"",
+                b";;flag; d==",
+                b"e=\xff; f=6",
+            ],
+        );
+        let cookies: Vec<_> = map.cookies().collect();
+        assert_eq!(
+            cookies,
+            [("a", "1"), ("b", "2"), ("c", "\"x y\""), ("d", "=")]
+        );
+        assert_eq!(HeaderMap::new().cookies().next(), None);
+    }
+
+    #[test]
+    fn set_cookies() {
+        let map = map(
+            SET_COOKIE,
+            &[
+                b"a=1",
+                b"flag; Path=/",
+                b"b = 2 ;Secure ",
+                b"c=\xff",
+                b"=3; Max-Age=0",
+            ],
+        );
+        let cookies: Vec<_> = map
+            .set_cookies()
+            .map(|c| (c.name(), c.value(), c.attributes()))
+            .collect();
+        assert_eq!(
+            cookies,
+            [("a", "1", ""), ("b", "2", "Secure"), ("", "3", "Max-Age=0")]
+        );
+        assert_eq!(
+            map.set_cookies().nth(1).unwrap().header_value(),
+            "b = 2 ;Secure "
+        );
+    }
+}
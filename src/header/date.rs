@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::HeaderValue;
+
+/// A possible error when converting a `HeaderValue` to a `SystemTime`.
+pub struct InvalidHttpDate {
+    _priv: (),
+}
+
+const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const LONG_DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Seconds since the epoch of 10000-01-01T00:00:00Z, the first instant an
+/// HTTP-date cannot represent.
+const MAX_SECS: u64 = 253_402_300_800;
+
+/// A broken-down UTC time with a four digit year.
+#[derive(Debug, PartialEq)]
+struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    // 0 is Monday
+    weekday: u8,
+}
+
+impl DateTime {
+    fn from_secs(secs: u64) -> DateTime {
+        let days = secs / 86400;
+        let rem = secs % 86400;
+
+        // Howard Hinnant's `civil_from_days`, shifted so that the era starts
+        // on 0000-03-01.
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z % 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        DateTime {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (rem / 3600) as u8,
+            minute: (rem / 60 % 60) as u8,
+            second: (rem % 60) as u8,
+            // 1970-01-01 was a Thursday
+            weekday: ((days + 3) % 7) as u8,
+        }
+    }
+
+    fn to_secs(&self) -> Option<u64> {
+        if self.month == 0 || self.month > 12 {
+            return None;
+        }
+        let leap = self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0);
+        let month_len = match self.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        // A leap second is accepted and folded into the following second.
+        if self.year < 1970
+            || self.day == 0
+            || self.day > month_len
+            || self.hour > 23
+            || self.minute > 59
+            || self.second > 60
+        {
+            return None;
+        }
+
+        // `days_from_civil`, the inverse of `from_secs`.
+        let year = u64::from(self.year) - if self.month <= 2 { 1 } else { 0 };
+        let month = u64::from(self.month);
+        let era = year / 400;
+        let yoe = year % 400;
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + u64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+
+        // The day name is redundant, but a mismatch means the value is wrong.
+        if (days + 3) % 7 != u64::from(self.weekday) {
+            return None;
+        }
+
+        Some(
+            days * 86400
+                + u64::from(self.hour) * 3600
+                + u64::from(self.minute) * 60
+                + u64::from(self.second),
+        )
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            DAYS[usize::from(self.weekday)],
+            self.day,
+            MONTHS[usize::from(self.month) - 1],
+            self.year,
+            self.hour,
+            self.minute,
+            self.second,
+        )
+    }
+}
+
+fn digits(s: &str) -> Option<u16> {
+    if s.is_empty() || s.len() > 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn two_digits(s: &str) -> Option<u8> {
+    if s.len() != 2 {
+        return None;
+    }
+    digits(s).map(|n| n as u8)
+}
+
+fn month(s: &str) -> Option<u8> {
+    MONTHS.iter().position(|&m| m == s).map(|i| i as u8 + 1)
+}
+
+fn time(s: &str) -> Option<(u8, u8, u8)> {
+    let mut parts = s.split(':');
+    let hour = two_digits(parts.next()?)?;
+    let minute = two_digits(parts.next()?)?;
+    let second = two_digits(parts.next()?)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((hour, minute, second))
+}
+
+/// `Sun, 06 Nov 1994 08:49:37 GMT`
+fn parse_imf_fixdate(s: &str) -> Option<DateTime> {
+    let (weekday, rest) = split_once(s, ", ")?;
+    let weekday = DAYS.iter().position(|&d| d == weekday)? as u8;
+    let mut parts = rest.split(' ');
+    let day = two_digits(parts.next()?)?;
+    let month = month(parts.next()?)?;
+    let year = parts.next()?;
+    if year.len() != 4 {
+        return None;
+    }
+    let year = digits(year)?;
+    let (hour, minute, second) = time(parts.next()?)?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    Some(DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        weekday,
+    })
+}
+
+/// `Sunday, 06-Nov-94 08:49:37 GMT`
+fn parse_rfc850_date(s: &str) -> Option<DateTime> {
+    let (weekday, rest) = split_once(s, ", ")?;
+    let weekday = LONG_DAYS.iter().position(|&d| d == weekday)? as u8;
+    let mut parts = rest.split(' ');
+    let mut date = parts.next()?.split('-');
+    let day = two_digits(date.next()?)?;
+    let month = month(date.next()?)?;
+    let year = u16::from(two_digits(date.next()?)?);
+    if date.next().is_some() {
+        return None;
+    }
+    // RFC 9110 asks for two digit years to be read as the most recent year
+    // with those digits, which is not worth the complexity for an obsolete
+    // format; this matches what other implementations do.
+    let year = if year < 70 { 2000 + year } else { 1900 + year };
+    let (hour, minute, second) = time(parts.next()?)?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    Some(DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        weekday,
+    })
+}
+
+/// `Sun Nov  6 08:49:37 1994`
+fn parse_asctime(s: &str) -> Option<DateTime> {
+    let (weekday, rest) = split_once(s, " ")?;
+    let weekday = DAYS.iter().position(|&d| d == weekday)? as u8;
+    let (mon, rest) = split_once(rest, " ")?;
+    let month = month(mon)?;
+    // The day is padded to two characters with a space.
+    if rest.len() < 3 {
+        return None;
+    }
+    let (day, rest) = rest.split_at(2);
+    let day = digits(day.trim_start_matches(' '))? as u8;
+    let mut parts = rest.strip_prefix(' ')?.split(' ');
+    let (hour, minute, second) = time(parts.next()?)?;
+    let year = parts.next()?;
+    if year.len() != 4 || parts.next().is_some() {
+        return None;
+    }
+    Some(DateTime {
+        year: digits(year)?,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        weekday,
+    })
+}
+
+// `str::split_once` needs Rust 1.52.
+fn split_once<'a>(s: &'a str, pat: &str) -> Option<(&'a str, &'a str)> {
+    let i = s.find(pat)?;
+    Some((&s[..i], &s[i + pat.len()..]))
+}
+
+impl HeaderValue {
+    /// Formats a `SystemTime` as an HTTP-date.
+    ///
+    /// The value uses the preferred IMF-fixdate format, as required for
+    /// headers like `Date`, `Last-Modified` and `Expires`. Sub-second
+    /// precision is discarded.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `time` is before 1970 or after the year 9999,
+    /// neither of which can be represented as an HTTP-date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let time = UNIX_EPOCH + Duration::from_secs(784_111_777);
+    /// let val = HeaderValue::from_date(time);
+    /// assert_eq!(val, "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// ```
+    pub fn from_date(time: SystemTime) -> HeaderValue {
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .expect("HeaderValue::from_date with a time before 1970")
+            .as_secs();
+        assert!(
+            secs < MAX_SECS,
+            "HeaderValue::from_date with a time after the year 9999"
+        );
+        let date = DateTime::from_secs(secs).to_string();
+        HeaderValue::from_maybe_shared(date).expect("HTTP-date is a valid header value")
+    }
+
+    /// Parses the value as an HTTP-date.
+    ///
+    /// All three formats allowed by RFC 9110 are accepted: IMF-fixdate,
+    /// and the obsolete RFC 850 and asctime formats. Two digit years in the
+    /// RFC 850 format are read as 1970 through 2069.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let time = UNIX_EPOCH + Duration::from_secs(784_111_777);
+    ///
+    /// for date in &[
+    ///     "Sun, 06 Nov 1994 08:49:37 GMT",
+    ///     "Sunday, 06-Nov-94 08:49:37 GMT",
+    ///     "Sun Nov  6 08:49:37 1994",
+    /// ] {
+    ///     let val = HeaderValue::from_static(date);
+    ///     assert_eq!(val.to_date().unwrap(), time);
+    /// }
+    ///
+    /// assert!(HeaderValue::from_static("tomorrow").to_date().is_err());
+    /// ```
+    pub fn to_date(&self) -> Result<SystemTime, InvalidHttpDate> {
+        let s = self.to_str().map_err(|_| InvalidHttpDate { _priv: () })?;
+        parse_imf_fixdate(s)
+            .or_else(|| parse_rfc850_date(s))
+            .or_else(|| parse_asctime(s))
+            .and_then(|date| date.to_secs())
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+            .ok_or(InvalidHttpDate { _priv: () })
+    }
+}
+
+impl fmt::Debug for InvalidHttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidHttpDate")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidHttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid HTTP-date")
+    }
+}
+
+impl Error for InvalidHttpDate {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(val: &str) -> Option<u64> {
+        HeaderValue::from_str(val)
+            .unwrap()
+            .to_date()
+            .ok()
+            .map(|t| t.duration_since(UNIX_EPOCH).unwrap().as_secs())
+    }
+
+    #[test]
+    fn round_trips() {
+        for &s in &[0, 784_111_777, 951_782_400, 1_709_164_800, MAX_SECS - 1] {
+            let val = HeaderValue::from_date(UNIX_EPOCH + Duration::from_secs(s));
+            assert_eq!(secs(val.to_str().unwrap()), Some(s), "{:?}", val);
+        }
+        assert_eq!(
+            HeaderValue::from_date(UNIX_EPOCH),
+            "Thu, 01 Jan 1970 00:00:00 GMT"
+        );
+        assert_eq!(
+            HeaderValue::from_date(UNIX_EPOCH + Duration::from_secs(MAX_SECS - 1)),
+            "Fri, 31 Dec 9999 23:59:59 GMT"
+        );
+    }
+
+    #[test]
+    fn parses_obsolete_formats() {
+        assert_eq!(secs("Sunday, 06-Nov-94 08:49:37 GMT"), Some(784_111_777));
+        assert_eq!(secs("Sun Nov  6 08:49:37 1994"), Some(784_111_777));
+        assert_eq!(secs("Thu Feb 29 00:00:00 2024"), Some(1_709_164_800));
+        assert_eq!(secs("Thursday, 01-Jan-70 00:00:00 GMT"), Some(0));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for s in &[
+            "",
+            "Sun, 06 Nov 1994 08:49:37",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Mon, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 94 08:49:37 GMT",
+            "Sun, 31 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Thu, 01 Jan 1969 00:00:00 GMT",
+            "sun, 06 nov 1994 08:49:37 gmt",
+            "Sunday, 06-Nov-1994 08:49:37 GMT",
+            "Sun Nov 6 08:49:37 1994",
+        ] {
+            assert_eq!(secs(s), None, "{:?}", s);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_date_before_epoch() {
+        HeaderValue::from_date(UNIX_EPOCH - Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,223 @@
+use std::iter::{FromIterator, FusedIterator};
+
+use bytes::BytesMut;
+
+use super::value::{is_valid_value, HeaderValue, InvalidHeaderValue, ToStrError};
+
+/// An iterator over the elements of a comma-separated header value.
+///
+/// Returned by [`HeaderValue::split_list`].
+#[derive(Debug, Clone)]
+pub struct SplitList<'a> {
+    rest: &'a str,
+}
+
+impl HeaderValue {
+    /// Splits the value into the elements of a comma-separated list.
+    ///
+    /// Commas inside quoted strings, including escaped quotes, do not split
+    /// an element. Each element is trimmed of surrounding whitespace, and
+    /// empty elements are skipped, as RFC 9110 requires of recipients.
+    ///
+    /// An error is returned if the value contains characters other than
+    /// visible ASCII, as with [`to_str`](HeaderValue::to_str).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static(r#"no-cache="set-cookie, via", max-age=60,,"#);
+    /// let elements: Vec<&str> = val.split_list().unwrap().collect();
+    ///
+    /// assert_eq!(elements, [r#"no-cache="set-cookie, via""#, "max-age=60"]);
+    /// ```
+    pub fn split_list(&self) -> Result<SplitList<'_>, ToStrError> {
+        Ok(SplitList {
+            rest: self.to_str()?,
+        })
+    }
+
+    /// Joins several values into one, with `separator` between each.
+    ///
+    /// This folds repeated field lines into the single line that RFC 9110
+    /// allows for list-based headers; it must not be used for `Set-Cookie`,
+    /// whose values can contain commas. Every value, and the separator, are
+    /// checked as with [`from_bytes`](HeaderValue::from_bytes).
+    ///
+    /// Collecting an iterator of `HeaderValue`s is equivalent to joining them
+    /// with `", "`, except that the result is marked as sensitive if any of
+    /// the values is. `join` only sees bytes, so its result never is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::join(&["gzip", "br"], ", ").unwrap();
+    /// assert_eq!(val, "gzip, br");
+    ///
+    /// assert!(HeaderValue::join(&["a", "b\n"], ", ").is_err());
+    ///
+    /// let val: HeaderValue = vec![
+    ///     HeaderValue::from_static("text/html"),
+    ///     HeaderValue::from_static("*/*;q=0.8"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// assert_eq!(val, "text/html, */*;q=0.8");
+    /// ```
+    pub fn join<I>(values: I, separator: &str) -> Result<HeaderValue, InvalidHeaderValue>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        join_with(values, separator, |_| false)
+    }
+}
+
+fn join_with<I, F>(
+    values: I,
+    separator: &str,
+    is_sensitive: F,
+) -> Result<HeaderValue, InvalidHeaderValue>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+    F: Fn(&I::Item) -> bool,
+{
+    if !is_valid_value(separator.as_bytes()) {
+        return Err(InvalidHeaderValue::new());
+    }
+
+    let mut buf = BytesMut::new();
+    let mut sensitive = false;
+    for (i, value) in values.into_iter().enumerate() {
+        if !is_valid_value(value.as_ref()) {
+            return Err(InvalidHeaderValue::new());
+        }
+        if i > 0 {
+            buf.extend_from_slice(separator.as_bytes());
+        }
+        buf.extend_from_slice(value.as_ref());
+        sensitive |= is_sensitive(&value);
+    }
+    Ok(HeaderValue::from_valid_shared(buf.freeze(), sensitive))
+}
+
+impl FromIterator<HeaderValue> for HeaderValue {
+    fn from_iter<I: IntoIterator<Item = HeaderValue>>(iter: I) -> HeaderValue {
+        join_with(iter, ", ", HeaderValue::is_sensitive).expect("joined header values are valid")
+    }
+}
+
+impl<'a> FromIterator<&'a HeaderValue> for HeaderValue {
+    fn from_iter<I: IntoIterator<Item = &'a HeaderValue>>(iter: I) -> HeaderValue {
+        join_with(iter, ", ", |v| v.is_sensitive()).expect("joined header values are valid")
+    }
+}
+
+impl<'a> Iterator for SplitList<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while !self.rest.is_empty() {
+            let bytes = self.rest.as_bytes();
+            let mut in_quotes = false;
+            let mut escaped = false;
+            let mut end = bytes.len();
+
+            for (i, &b) in bytes.iter().enumerate() {
+                if escaped {
+                    escaped = false;
+                } else if in_quotes {
+                    match b {
+                        b'\\' => escaped = true,
+                        b'"' => in_quotes = false,
+                        _ => {}
+                    }
+                } else if b == b'"' {
+                    in_quotes = true;
+                } else if b == b',' {
+                    end = i;
+                    break;
+                }
+            }
+
+            let element = self.rest[..end].trim_matches(|c| c == ' ' || c == '\t');
+            self.rest = if end < bytes.len() {
+                &self.rest[end + 1..]
+            } else {
+                ""
+            };
+
+            if !element.is_empty() {
+                return Some(element);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> FusedIterator for SplitList<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(s: &str) -> Vec<String> {
+        let val = HeaderValue::from_str(s).unwrap();
+        val.split_list().unwrap().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn splits_plain_lists() {
+        assert_eq!(split("gzip, br"), ["gzip", "br"]);
+        assert_eq!(split("gzip,br"), ["gzip", "br"]);
+        assert_eq!(split(" gzip\t"), ["gzip"]);
+        assert!(split("").is_empty());
+        assert!(split(" , ,, ").is_empty());
+    }
+
+    #[test]
+    fn respects_quoted_strings() {
+        assert_eq!(split(r#"a="x, y", b"#), [r#"a="x, y""#, "b"]);
+        assert_eq!(split(r#"a="x \", y", b"#), [r#"a="x \", y""#, "b"]);
+        assert_eq!(split(r#"a="\\", b"#), [r#"a="\\""#, "b"]);
+        // An unterminated quote runs to the end of the value.
+        assert_eq!(split(r#"a="x, y"#), [r#"a="x, y"#]);
+    }
+
+    #[test]
+    fn joins_values() {
+        let empty: [&str; 0] = [];
+        assert_eq!(HeaderValue::join(&empty, ", ").unwrap(), "");
+        assert_eq!(HeaderValue::join(&["a"], ", ").unwrap(), "a");
+        assert_eq!(
+            HeaderValue::join(vec!["a", "b", "c"], ",").unwrap(),
+            "a,b,c"
+        );
+        assert!(HeaderValue::join(&["a", "b\r\n"], ", ").is_err());
+
+        let values = [HeaderValue::from_static("a"), HeaderValue::from_static("b")];
+        let joined: HeaderValue = values.iter().collect();
+        assert_eq!(joined, "a, b");
+        assert_eq!(joined.split_list().unwrap().collect::<Vec<_>>(), ["a", "b"]);
+        assert!(!joined.is_sensitive());
+
+        let mut secret = HeaderValue::from_static("c");
+        secret.set_sensitive(true);
+        let joined: HeaderValue = vec![values[0].clone(), secret.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(joined, "a, c");
+        assert!(joined.is_sensitive());
+        let joined: HeaderValue = [&secret, &values[1]].iter().copied().collect();
+        assert!(joined.is_sensitive());
+        assert!(!HeaderValue::join(&[&secret], ", ").unwrap().is_sensitive());
+    }
+
+    #[test]
+    fn rejects_opaque_bytes() {
+        let val = HeaderValue::from_bytes(b"a, \xff").unwrap();
+        assert!(val.split_list().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -6,10 +7,12 @@ use std::iter::{FromIterator, FusedIterator};
 use std::marker::PhantomData;
 use std::{fmt, mem, ops, ptr, vec};
 
-use crate::Error;
+use crate::{Error, Version};
 
 use super::name::{HdrName, HeaderName, InvalidHeaderName};
-use super::HeaderValue;
+use super::{
+    ContentCoding, HeaderValue, InvalidHeaderBlock, InvalidServerTiming, Profile, ServerTiming,
+};
 
 pub use self::as_header_name::AsHeaderName;
 pub use self::into_header_name::IntoHeaderName;
@@ -18,6 +21,19 @@ pub use self::into_header_name::IntoHeaderName;
 ///
 /// `HeaderMap` is a multimap of [`HeaderName`] to values.
 ///
+/// Header names are hashed with `S`, which defaults to a fast hash function
+/// well suited to short keys. See [`HeaderMap::with_hasher`] to use another.
+///
+/// A `HeaderMap` always allocates from the global allocator. To avoid
+/// allocating for every message on a connection, keep one map per connection
+/// and [`clear`](HeaderMap::clear) it between messages, which keeps its
+/// storage for reuse.
+///
+/// The `Debug` output shows the values of `Authorization`,
+/// `Proxy-Authorization`, `Cookie` and `Set-Cookie` as `<redacted>`, so
+/// that printing a map does not leak credentials into logs. Use
+/// [`unredacted`](HeaderMap::unredacted) to print them anyway.
+///
 /// [`HeaderName`]: struct.HeaderName.html
 ///
 /// # Examples
@@ -41,14 +57,36 @@ pub use self::into_header_name::IntoHeaderName;
 ///
 /// assert!(!headers.contains_key(HOST));
 /// ```
+///
+/// Reusing a map for several messages
+///
+/// ```
+/// # use http::HeaderMap;
+/// # use http::header::{ACCEPT, HOST};
+/// let mut headers: HeaderMap = HeaderMap::with_capacity(16);
+/// let allocated = headers.allocated_bytes();
+///
+/// for path in &["/a", "/b", "/c"] {
+///     headers.insert(HOST, "example.com".parse().unwrap());
+///     headers.insert(ACCEPT, "*/*".parse().unwrap());
+///     // ... handle the request for `path` ...
+///     # let _ = path;
+///     headers.clear();
+/// }
+///
+/// assert_eq!(headers.allocated_bytes(), allocated);
+/// ```
 #[derive(Clone)]
-pub struct HeaderMap<T = HeaderValue> {
+pub struct HeaderMap<T = HeaderValue, S = DefaultHashBuilder> {
     // Used to mask values to get an index
     mask: Size,
-    indices: Box<[Pos]>,
+    indices: Indices,
     entries: Vec<Bucket<T>>,
     extra_values: Vec<ExtraValue<T>>,
     danger: Danger,
+    // The maximum number of values the map may hold
+    limit: usize,
+    hash_builder: S,
 }
 
 // # Implementation notes
@@ -82,7 +120,8 @@ pub struct HeaderMap<T = HeaderValue> {
 /// more than once if it has more than one associated value.
 #[derive(Debug)]
 pub struct Iter<'a, T> {
-    map: &'a HeaderMap<T>,
+    entries: &'a [Bucket<T>],
+    extra_values: &'a [ExtraValue<T>],
     entry: usize,
     cursor: Option<Cursor>,
 }
@@ -93,7 +132,8 @@ pub struct Iter<'a, T> {
 /// yielded more than once if it has more than one associated value.
 #[derive(Debug)]
 pub struct IterMut<'a, T> {
-    map: *mut HeaderMap<T>,
+    entries: *mut [Bucket<T>],
+    extra_values: *mut [ExtraValue<T>],
     entry: usize,
     cursor: Option<Cursor>,
     lt: PhantomData<&'a mut HeaderMap<T>>,
@@ -110,6 +150,15 @@ pub struct IntoIter<T> {
     extra_values: Vec<ExtraValue<T>>,
 }
 
+/// An iterator over the `HeaderMap` entries marked as trailers.
+///
+/// Yields `(&HeaderName, &value)` tuples, like `Iter`. Returned by
+/// [`HeaderMap::trailers`].
+#[derive(Debug)]
+pub struct Trailers<'a, T> {
+    inner: Iter<'a, T>,
+}
+
 /// An iterator over `HeaderMap` keys.
 ///
 /// Each header name is yielded only once, even if it has more than one
@@ -150,26 +199,27 @@ pub struct Drain<'a, T> {
 /// This struct is returned by `HeaderMap::get_all`.
 #[derive(Debug)]
 pub struct GetAll<'a, T> {
-    map: &'a HeaderMap<T>,
+    entries: &'a [Bucket<T>],
+    extra_values: &'a [ExtraValue<T>],
     index: Option<usize>,
 }
 
 /// A view into a single location in a `HeaderMap`, which may be vacant or occupied.
 #[derive(Debug)]
-pub enum Entry<'a, T: 'a> {
+pub enum Entry<'a, T: 'a, S = DefaultHashBuilder> {
     /// An occupied entry
-    Occupied(OccupiedEntry<'a, T>),
+    Occupied(OccupiedEntry<'a, T, S>),
 
     /// A vacant entry
-    Vacant(VacantEntry<'a, T>),
+    Vacant(VacantEntry<'a, T, S>),
 }
 
 /// A view into a single empty location in a `HeaderMap`.
 ///
 /// This struct is returned as part of the `Entry` enum.
 #[derive(Debug)]
-pub struct VacantEntry<'a, T> {
-    map: &'a mut HeaderMap<T>,
+pub struct VacantEntry<'a, T, S = DefaultHashBuilder> {
+    map: &'a mut HeaderMap<T, S>,
     key: HeaderName,
     hash: HashValue,
     probe: usize,
@@ -180,8 +230,8 @@ pub struct VacantEntry<'a, T> {
 ///
 /// This struct is returned as part of the `Entry` enum.
 #[derive(Debug)]
-pub struct OccupiedEntry<'a, T> {
-    map: &'a mut HeaderMap<T>,
+pub struct OccupiedEntry<'a, T, S = DefaultHashBuilder> {
+    map: &'a mut HeaderMap<T, S>,
     probe: usize,
     index: usize,
 }
@@ -189,7 +239,8 @@ pub struct OccupiedEntry<'a, T> {
 /// An iterator of all values associated with a single header name.
 #[derive(Debug)]
 pub struct ValueIter<'a, T> {
-    map: &'a HeaderMap<T>,
+    entries: &'a [Bucket<T>],
+    extra_values: &'a [ExtraValue<T>],
     index: usize,
     front: Option<Cursor>,
     back: Option<Cursor>,
@@ -198,7 +249,8 @@ pub struct ValueIter<'a, T> {
 /// A mutable iterator of all values associated with a single header name.
 #[derive(Debug)]
 pub struct ValueIterMut<'a, T> {
-    map: *mut HeaderMap<T>,
+    entries: *mut [Bucket<T>],
+    extra_values: *mut [ExtraValue<T>],
     index: usize,
     front: Option<Cursor>,
     back: Option<Cursor>,
@@ -218,6 +270,20 @@ pub struct MaxSizeReached {
     _priv: (),
 }
 
+/// The default hash builder used by `HeaderMap`.
+///
+/// Header names are short and mostly come from a small, well known set, so
+/// this uses a fast, unkeyed hash function. `HeaderMap` detects collision
+/// attacks on its own and switches to a keyed hash when one is seen.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultHashBuilder {
+    _priv: (),
+}
+
+/// The hasher built by `DefaultHashBuilder`.
+#[derive(Default)]
+pub struct DefaultHasher(fnv::FnvHasher);
+
 /// Tracks the value iterator state
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Cursor {
@@ -252,6 +318,24 @@ struct Pos {
     hash: HashValue,
 }
 
+/// The hash table itself.
+///
+/// Most header maps only ever hold a handful of headers, so tables with up to
+/// `INLINE_INDICES` slots are stored inline. This saves an allocation for
+/// those maps, leaving `entries` as the only one.
+#[derive(Clone)]
+enum Indices {
+    Inline {
+        slots: [Pos; INLINE_INDICES],
+        len: u8,
+    },
+    Heap(Box<[Pos]>),
+}
+
+/// The largest table that is stored inline. This is the capacity that an
+/// empty map grows to on its first insert.
+const INLINE_INDICES: usize = 8;
+
 /// Hash values are limited to u16 as well. While `fast_hash` and `Hasher`
 /// return `usize` hash codes, limiting the effective hash code to the lower 16
 /// bits is fine since we know that the `indices` vector will never grow beyond
@@ -271,6 +355,7 @@ struct Bucket<T> {
     key: HeaderName,
     value: T,
     links: Option<Links>,
+    trailer: bool,
 }
 
 /// The head and tail of the value linked list.
@@ -312,11 +397,15 @@ enum Link {
 ///
 /// When in the red state, a safe hashing algorithm is used and all values in
 /// the header map have to be rehashed.
+///
+/// A map created by `new_secure` starts in the secure state, which hashes
+/// like the red state but is kept even when the map is cleared.
 #[derive(Clone)]
 enum Danger {
     Green,
     Yellow,
     Red(RandomState),
+    Secure(RandomState),
 }
 
 // Constants related to detecting DOS attacks.
@@ -385,7 +474,7 @@ macro_rules! insert_phase_one {
      $occupied:expr,
      $robinhood:expr) =>
     {{
-        let $hash = hash_elem_using(&$map.danger, &$key);
+        let $hash = hash_elem_using(&$map.danger, &$map.hash_builder, &$key);
         let mut $probe = desired_pos($map.mask, $hash);
         let mut dist = 0;
         let ret;
@@ -448,6 +537,338 @@ impl HeaderMap {
     pub fn new() -> Self {
         HeaderMap::try_with_capacity(0).unwrap()
     }
+
+    /// Create an empty `HeaderMap` that hashes with a randomly keyed hash
+    /// function from the start.
+    ///
+    /// By default, a `HeaderMap` uses a fast hash function and only switches
+    /// to a keyed one once it sees enough collisions to suspect a hash
+    /// flooding attack. Maps holding headers from untrusted peers can use
+    /// this constructor to pay the (small) cost of the keyed hash up front
+    /// instead of relying on that detection. Unlike a map that switched
+    /// after detecting an attack, this one keeps the keyed hash when it is
+    /// cleared.
+    ///
+    /// This function will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new_secure();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert_eq!(map[HOST], "example.com");
+    /// ```
+    pub fn new_secure() -> Self {
+        HeaderMap {
+            danger: Danger::Secure(RandomState::new()),
+            ..HeaderMap::new()
+        }
+    }
+
+    /// Parses an HTTP/1.1 header block.
+    ///
+    /// The block is a sequence of `name: value` lines, each ended by CRLF or
+    /// a bare LF, and optionally followed by the empty line that ends the
+    /// block. Any data after that empty line is an error, so the start line
+    /// and body must be split off by the caller.
+    ///
+    /// Obsolete line folding, whitespace before the colon, and invalid bytes
+    /// in names or values are rejected. The returned error reports the byte
+    /// offset at which parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let map = HeaderMap::parse(b"Host: example.com\r\nAccept: */*\r\n\r\n").unwrap();
+    /// assert_eq!(map["host"], "example.com");
+    ///
+    /// let err = HeaderMap::parse(b"Host: example.com\r\n folded\r\n").unwrap_err();
+    /// assert_eq!(err.position(), 19);
+    /// ```
+    pub fn parse(src: &[u8]) -> Result<HeaderMap, InvalidHeaderBlock> {
+        super::parse::parse(src)
+    }
+}
+
+impl<S: BuildHasher> HeaderMap<HeaderValue, S> {
+    /// Appends a `Server-Timing` metric to the map.
+    ///
+    /// The metric is encoded as its own `Server-Timing` field value, which is
+    /// equivalent to joining all metrics into a single list.
+    ///
+    /// Returns `false` if no `Server-Timing` values were previously present.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if capacity exceeds max `HeaderMap` capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ServerTiming, SERVER_TIMING};
+    /// let mut map = HeaderMap::new();
+    /// map.append_server_timing(ServerTiming::new("db").duration(53.2)).unwrap();
+    /// map.append_server_timing(ServerTiming::new("cache").description("hit")).unwrap();
+    ///
+    /// let values: Vec<_> = map.get_all(SERVER_TIMING).iter().collect();
+    /// assert_eq!(values, ["db;dur=53.2", "cache;desc=hit"]);
+    /// ```
+    pub fn append_server_timing(
+        &mut self,
+        metric: ServerTiming,
+    ) -> Result<bool, InvalidServerTiming> {
+        let value = HeaderValue::try_from(&metric)?;
+        Ok(self.append(super::SERVER_TIMING, value))
+    }
+
+    /// Returns `true` if the connection should be closed after a message with
+    /// these headers, sent using `version`.
+    ///
+    /// HTTP/1.1 connections are persistent unless `Connection: close` is
+    /// present, while HTTP/1.0 connections are closed unless
+    /// `Connection: keep-alive` is present. HTTP/0.9 connections are always
+    /// closed, and HTTP/2 and later manage connections outside of headers.
+    /// Extension versions are treated like HTTP/1.1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, Version};
+    /// # use http::header::CONNECTION;
+    /// let mut map = HeaderMap::new();
+    /// assert!(!map.connection_close(Version::HTTP_11));
+    /// assert!(map.connection_close(Version::HTTP_10));
+    ///
+    /// map.insert(CONNECTION, "Upgrade, Close".parse().unwrap());
+    /// assert!(map.connection_close(Version::HTTP_11));
+    /// ```
+    pub fn connection_close(&self, version: Version) -> bool {
+        match version {
+            Version::HTTP_09 => true,
+            Version::HTTP_10 => !self.has_connection_token("keep-alive"),
+            Version::HTTP_11 => self.has_connection_token("close"),
+            _ if version.is_extension() => self.has_connection_token("close"),
+            _ => false,
+        }
+    }
+
+    /// Sets the headers so the connection is closed (or kept alive) after the
+    /// message, using the semantics of `version`.
+    ///
+    /// Other `Connection` options are preserved. When closing, any
+    /// `Keep-Alive` header is removed. For HTTP/2 and later, where
+    /// connection-specific headers are not allowed, `Connection` and
+    /// `Keep-Alive` are removed entirely. Extension versions, such as
+    /// `RTSP/1.0`, share the HTTP/1.1 message format and are treated like
+    /// HTTP/1.1.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if capacity exceeds max `HeaderMap` capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, Version};
+    /// # use http::header::CONNECTION;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONNECTION, "upgrade".parse().unwrap());
+    ///
+    /// map.set_connection_close(true, Version::HTTP_11);
+    /// assert_eq!(map[CONNECTION], "upgrade, close");
+    ///
+    /// map.set_connection_close(false, Version::HTTP_11);
+    /// assert_eq!(map[CONNECTION], "upgrade");
+    ///
+    /// map.set_connection_close(false, Version::HTTP_10);
+    /// assert_eq!(map[CONNECTION], "upgrade, keep-alive");
+    ///
+    /// map.set_connection_close(true, Version::from_static(&"RTSP/1.0"));
+    /// assert_eq!(map[CONNECTION], "upgrade, close");
+    /// ```
+    pub fn set_connection_close(&mut self, close: bool, version: Version) {
+        let keep_alive = HeaderName::from_static("keep-alive");
+
+        if !version.is_extension() && version >= Version::HTTP_2 {
+            self.remove(super::CONNECTION);
+            self.remove(keep_alive);
+            return;
+        }
+
+        let mut tokens: Vec<String> = self
+            .get_all(super::CONNECTION)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(|t| t.trim())
+            .filter(|t| {
+                !t.is_empty()
+                    && !t.eq_ignore_ascii_case("close")
+                    && !t.eq_ignore_ascii_case("keep-alive")
+            })
+            .map(str::to_owned)
+            .collect();
+
+        if close {
+            self.remove(keep_alive);
+            if version == Version::HTTP_11 || version.is_extension() {
+                tokens.push("close".to_owned());
+            }
+        } else if version == Version::HTTP_10 {
+            tokens.push("keep-alive".to_owned());
+        }
+
+        if tokens.is_empty() {
+            self.remove(super::CONNECTION);
+        } else {
+            let value = HeaderValue::from_str(&tokens.join(", "))
+                .expect("connection tokens are valid header values");
+            self.insert(super::CONNECTION, value);
+        }
+    }
+
+    /// Returns the content codings of the body, in the order they must be
+    /// decoded.
+    ///
+    /// `Content-Encoding` lists codings in the order they were applied, so
+    /// the last listed coding is returned first. Callers should refuse to
+    /// decode the body if any coding is not [known](ContentCoding::is_known).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ContentCoding, CONTENT_ENCODING};
+    /// let mut map = HeaderMap::new();
+    /// map.append(CONTENT_ENCODING, "gzip".parse().unwrap());
+    /// map.append(CONTENT_ENCODING, "BR, x-custom".parse().unwrap());
+    ///
+    /// assert_eq!(
+    ///     map.content_encodings(),
+    ///     [
+    ///         ContentCoding::Unknown("x-custom"),
+    ///         ContentCoding::Br,
+    ///         ContentCoding::Gzip,
+    ///     ]
+    /// );
+    /// ```
+    pub fn content_encodings(&self) -> Vec<ContentCoding<'_>> {
+        let mut codings = Vec::new();
+        for value in self.get_all(super::CONTENT_ENCODING) {
+            match value.to_str() {
+                Ok(value) => codings.extend(
+                    value
+                        .split(',')
+                        .map(|t| t.trim())
+                        .filter(|t| !t.is_empty())
+                        .map(ContentCoding::from_token),
+                ),
+                Err(_) => codings.push(ContentCoding::Unknown("")),
+            }
+        }
+        codings.reverse();
+        codings
+    }
+
+    /// Returns the number of bytes `encode` will write for these headers.
+    ///
+    /// This can be used to reserve space in a buffer up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert_eq!(map.encoded_len(), "host: example.com\r\n".len());
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        self.iter()
+            .map(|(name, value)| name.as_str().len() + 2 + value.len() + 2)
+            .sum()
+    }
+
+    /// Appends these headers to `dst` in HTTP/1.1 wire format.
+    ///
+    /// Each value is written as its own `name: value\r\n` line, in iteration
+    /// order. Names are written in lowercase; see `encode_title_case` for
+    /// peers that expect `Title-Case`. The blank line that ends a header
+    /// block is not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.append(ACCEPT, "text/html".parse().unwrap());
+    /// map.append(ACCEPT, "*/*".parse().unwrap());
+    ///
+    /// let mut buf = Vec::with_capacity(map.encoded_len());
+    /// map.encode(&mut buf);
+    ///
+    /// assert_eq!(buf, &b"host: example.com\r\naccept: text/html\r\naccept: */*\r\n"[..]);
+    /// ```
+    pub fn encode(&self, dst: &mut Vec<u8>) {
+        self.encode_lines(dst, false)
+    }
+
+    /// Writes these headers in HTTP/1.1 wire format, with `Title-Case` names.
+    ///
+    /// This is the same as `encode`, except that the first letter of each
+    /// name, and every letter following a `-`, is written in uppercase.
+    /// Header names are case-insensitive, but some older peers only
+    /// recognize the conventional casing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_TYPE;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    ///
+    /// let mut buf = Vec::new();
+    /// map.encode_title_case(&mut buf);
+    ///
+    /// assert_eq!(buf, &b"Content-Type: text/plain\r\n"[..]);
+    /// ```
+    pub fn encode_title_case(&self, dst: &mut Vec<u8>) {
+        self.encode_lines(dst, true)
+    }
+
+    fn encode_lines(&self, dst: &mut Vec<u8>, title_case: bool) {
+        for (name, value) in self.iter() {
+            if title_case {
+                let mut upper = true;
+                for &b in name.as_str().as_bytes() {
+                    dst.push(if upper { b.to_ascii_uppercase() } else { b });
+                    upper = b == b'-';
+                }
+            } else {
+                dst.extend_from_slice(name.as_str().as_bytes());
+            }
+            dst.extend_from_slice(b": ");
+            dst.extend_from_slice(value.as_bytes());
+            dst.extend_from_slice(b"\r\n");
+        }
+    }
+
+    fn has_connection_token(&self, token: &str) -> bool {
+        self.get_all(super::CONNECTION)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|t| t.trim().eq_ignore_ascii_case(token))
+    }
 }
 
 impl<T> HeaderMap<T> {
@@ -500,13 +921,119 @@ impl<T> HeaderMap<T> {
     /// assert_eq!(12, map.capacity());
     /// ```
     pub fn try_with_capacity(capacity: usize) -> Result<HeaderMap<T>, MaxSizeReached> {
+        HeaderMap::try_with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
+    }
+
+    /// Create an empty `HeaderMap` that holds at most `limit` values.
+    ///
+    /// Once the map holds `limit` values, any operation that would add another
+    /// value fails with `MaxSizeReached` (or panics, for the variants that do
+    /// not return a `Result`). Replacing existing values is still allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST};
+    /// let mut map: HeaderMap = HeaderMap::with_limit(2);
+    ///
+    /// map.try_append(HOST, "example.com".parse().unwrap()).unwrap();
+    /// map.try_append(ACCEPT, "*/*".parse().unwrap()).unwrap();
+    /// assert!(map.try_append(ACCEPT, "text/html".parse().unwrap()).is_err());
+    ///
+    /// // Replacing a value does not grow the map.
+    /// map.try_insert(ACCEPT, "text/html".parse().unwrap()).unwrap();
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn with_limit(limit: usize) -> HeaderMap<T> {
+        HeaderMap {
+            limit,
+            ..HeaderMap::try_with_capacity(0).unwrap()
+        }
+    }
+}
+
+impl<T, S> HeaderMap<T, S> {
+    /// Create an empty `HeaderMap` which will use the given hash builder to
+    /// hash header names.
+    ///
+    /// The map will be created without any capacity. This function will not
+    /// allocate.
+    ///
+    /// The hash builder is used as long as the map sees no signs of a hash
+    /// flooding attack. If one is detected, the map switches to a randomly
+    /// keyed `SipHash` for the rest of its life, whatever `S` is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, HeaderValue};
+    /// # use http::header::HOST;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut map: HeaderMap<HeaderValue, _> = HeaderMap::with_hasher(RandomState::new());
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert_eq!(map[HOST], "example.com");
+    /// ```
+    pub fn with_hasher(hash_builder: S) -> HeaderMap<T, S> {
+        HeaderMap::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    /// Create an empty `HeaderMap` with the specified capacity, using the
+    /// given hash builder to hash header names.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if capacity exceeds max `HeaderMap` capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let map: HeaderMap<u32, _> = HeaderMap::with_capacity_and_hasher(10, RandomState::new());
+    ///
+    /// assert!(map.is_empty());
+    /// assert_eq!(12, map.capacity());
+    /// ```
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HeaderMap<T, S> {
+        Self::try_with_capacity_and_hasher(capacity, hash_builder).expect("size overflows MAX_SIZE")
+    }
+
+    /// Create an empty `HeaderMap` with the specified capacity, using the
+    /// given hash builder to hash header names.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if `HeaderMap` exceeds max capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let map: HeaderMap<u32, _> =
+    ///     HeaderMap::try_with_capacity_and_hasher(10, RandomState::new()).unwrap();
+    ///
+    /// assert!(map.is_empty());
+    /// assert_eq!(12, map.capacity());
+    /// ```
+    pub fn try_with_capacity_and_hasher(
+        capacity: usize,
+        hash_builder: S,
+    ) -> Result<HeaderMap<T, S>, MaxSizeReached> {
         if capacity == 0 {
             Ok(HeaderMap {
                 mask: 0,
-                indices: Box::new([]), // as a ZST, this doesn't actually allocate anything
+                indices: Indices::new(0),
                 entries: Vec::new(),
                 extra_values: Vec::new(),
                 danger: Danger::Green,
+                limit: usize::MAX,
+                hash_builder,
             })
         } else {
             let raw_cap = match to_raw_capacity(capacity).checked_next_power_of_two() {
@@ -520,14 +1047,39 @@ impl<T> HeaderMap<T> {
 
             Ok(HeaderMap {
                 mask: (raw_cap - 1) as Size,
-                indices: vec![Pos::none(); raw_cap].into_boxed_slice(),
+                indices: Indices::new(raw_cap),
                 entries: Vec::with_capacity(usable_capacity(raw_cap)),
                 extra_values: Vec::new(),
                 danger: Danger::Green,
+                limit: usize::MAX,
+                hash_builder,
             })
         }
     }
 
+    /// Returns a reference to the map's hash builder.
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+}
+
+impl<T, S: BuildHasher> HeaderMap<T, S> {
+    /// Returns the maximum number of values this map may hold.
+    ///
+    /// Maps without an explicit limit return `usize::MAX`, though the number
+    /// of distinct keys remains bounded by the `HeaderMap` capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let map: HeaderMap = HeaderMap::with_limit(100);
+    /// assert_eq!(map.limit(), 100);
+    /// ```
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
     /// Returns the number of headers stored in the map.
     ///
     /// This number represents the total number of **values** stored in the map.
@@ -620,7 +1172,11 @@ impl<T> HeaderMap<T> {
     pub fn clear(&mut self) {
         self.entries.clear();
         self.extra_values.clear();
-        self.danger = Danger::Green;
+
+        // Maps created by `new_secure` keep using the keyed hash.
+        if !self.danger.is_secure() {
+            self.danger = Danger::Green;
+        }
 
         for e in self.indices.iter_mut() {
             *e = Pos::none();
@@ -648,6 +1204,29 @@ impl<T> HeaderMap<T> {
         usable_capacity(self.indices.len())
     }
 
+    /// Returns the number of bytes the map has allocated for its own storage.
+    ///
+    /// This covers the hash table indices and the full capacity of the entry
+    /// and extra value storage. Any heap memory owned by the header names or
+    /// values themselves is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// assert_eq!(0, map.allocated_bytes());
+    ///
+    /// map.insert(HOST, "hello.world".parse().unwrap());
+    /// assert!(map.allocated_bytes() > 0);
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        self.indices.allocated_len() * mem::size_of::<Pos>()
+            + self.entries.capacity() * mem::size_of::<Bucket<T>>()
+            + self.extra_values.capacity() * mem::size_of::<ExtraValue<T>>()
+    }
+
     /// Reserves capacity for at least `additional` more headers to be inserted
     /// into the `HeaderMap`.
     ///
@@ -719,7 +1298,7 @@ impl<T> HeaderMap<T> {
 
             if self.entries.is_empty() {
                 self.mask = raw_cap as Size - 1;
-                self.indices = vec![Pos::none(); raw_cap].into_boxed_slice();
+                self.indices = Indices::new(raw_cap);
                 self.entries = Vec::with_capacity(usable_capacity(raw_cap));
             } else {
                 self.try_grow(raw_cap)?;
@@ -750,6 +1329,21 @@ impl<T> HeaderMap<T> {
     /// map.append(HOST, "world".parse().unwrap());
     /// assert_eq!(map.get("host").unwrap(), &"hello");
     /// ```
+    ///
+    /// Names can also be looked up from raw bytes, such as a slice of a
+    /// parser's input buffer. Byte string literals are fixed-size arrays, so
+    /// they need to be sliced first, as in `&b"x-missing"[..]`.
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_LENGTH;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_LENGTH, "42".parse().unwrap());
+    ///
+    /// let line: &[u8] = b"Content-Length: 42";
+    /// assert_eq!(map.get(&line[..14]).unwrap(), &"42");
+    /// assert!(map.get(&b"x-missing"[..]).is_none());
+    /// ```
     pub fn get<K>(&self, key: K) -> Option<&T>
     where
         K: AsHeaderName,
@@ -770,6 +1364,30 @@ impl<T> HeaderMap<T> {
         }
     }
 
+    /// Returns the first value of the first key in `keys` that is present.
+    ///
+    /// This is useful when a header may be spelled in several ways, such as
+    /// `x-forwarded-for` and its standardized replacement `forwarded`, and the
+    /// candidates should be tried in order of preference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{FORWARDED, X_FORWARDED_FOR};
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_any(&[X_FORWARDED_FOR, FORWARDED]).is_none());
+    ///
+    /// map.insert(FORWARDED, "for=192.0.2.60".parse().unwrap());
+    /// assert_eq!(map.get_any(&[X_FORWARDED_FOR, FORWARDED]).unwrap(), &"for=192.0.2.60");
+    ///
+    /// map.insert(X_FORWARDED_FOR, "192.0.2.43".parse().unwrap());
+    /// assert_eq!(map.get_any(&[X_FORWARDED_FOR, FORWARDED]).unwrap(), &"192.0.2.43");
+    /// ```
+    pub fn get_any(&self, keys: &[HeaderName]) -> Option<&T> {
+        keys.iter().find_map(|key| self.get2(&key))
+    }
+
     /// Returns a mutable reference to the value associated with the key.
     ///
     /// If there are multiple values associated with the key, then the first one
@@ -830,7 +1448,8 @@ impl<T> HeaderMap<T> {
         K: AsHeaderName,
     {
         GetAll {
-            map: self,
+            entries: &self.entries,
+            extra_values: &self.extra_values,
             index: key.find(self).map(|(_, i)| i),
         }
     }
@@ -843,16 +1462,88 @@ impl<T> HeaderMap<T> {
     /// # use http::HeaderMap;
     /// # use http::header::HOST;
     /// let mut map = HeaderMap::new();
-    /// assert!(!map.contains_key(HOST));
+    /// assert!(!map.contains_key(HOST));
+    ///
+    /// map.insert(HOST, "world".parse().unwrap());
+    /// assert!(map.contains_key("host"));
+    /// ```
+    pub fn contains_key<K>(&self, key: K) -> bool
+    where
+        K: AsHeaderName,
+    {
+        key.find(self).is_some()
+    }
+
+    /// Marks whether the values of `key` belong to the trailer section.
+    ///
+    /// This lets headers and trailers travel in one map, for example through
+    /// a gRPC or chunked encoding implementation, while staying
+    /// distinguishable. The mark belongs to the name: it covers every value
+    /// of `key`, including ones appended later, and is dropped when the name
+    /// is removed. It is not considered by `==`.
+    ///
+    /// Returns `false`, and does nothing, if the map has no value for `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_TYPE;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "application/grpc".parse().unwrap());
+    /// map.insert("grpc-status", "0".parse().unwrap());
+    ///
+    /// assert!(map.set_trailer("grpc-status", true));
+    /// assert!(!map.set_trailer("grpc-message", true));
+    ///
+    /// assert!(map.is_trailer("grpc-status"));
+    /// assert!(!map.is_trailer(CONTENT_TYPE));
+    /// ```
+    pub fn set_trailer<K>(&mut self, key: K, trailer: bool) -> bool
+    where
+        K: AsHeaderName,
+    {
+        match key.find(self) {
+            Some((_, found)) => {
+                self.entries[found].trailer = trailer;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true if `key` is present and marked as a trailer.
+    ///
+    /// See [`set_trailer`](HeaderMap::set_trailer).
+    pub fn is_trailer<K>(&self, key: K) -> bool
+    where
+        K: AsHeaderName,
+    {
+        key.find(self)
+            .map_or(false, |(_, found)| self.entries[found].trailer)
+    }
+
+    /// An iterator visiting the key-value pairs marked as trailers.
+    ///
+    /// Pairs are visited in the same order as [`iter`](HeaderMap::iter),
+    /// skipping every name that is not marked with
+    /// [`set_trailer`](HeaderMap::set_trailer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_TYPE;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "application/grpc".parse().unwrap());
+    /// map.insert("grpc-status", "0".parse().unwrap());
+    /// map.set_trailer("grpc-status", true);
     ///
-    /// map.insert(HOST, "world".parse().unwrap());
-    /// assert!(map.contains_key("host"));
+    /// let trailers: Vec<_> = map.trailers().map(|(k, _)| k.as_str()).collect();
+    /// assert_eq!(trailers, ["grpc-status"]);
     /// ```
-    pub fn contains_key<K>(&self, key: K) -> bool
-    where
-        K: AsHeaderName,
-    {
-        key.find(self).is_some()
+    pub fn trailers(&self) -> Trailers<'_, T> {
+        Trailers { inner: self.iter() }
     }
 
     /// An iterator visiting all key-value pairs.
@@ -877,11 +1568,62 @@ impl<T> HeaderMap<T> {
     /// }
     /// ```
     pub fn iter(&self) -> Iter<'_, T> {
-        Iter {
-            map: self,
-            entry: 0,
-            cursor: self.entries.first().map(|_| Cursor::Head),
-        }
+        Iter::new(&self.entries, &self.extra_values)
+    }
+
+    /// Returns a view of the map whose `Debug` output shows every value.
+    ///
+    /// The map's own `Debug` hides the values of credential headers such as
+    /// `Authorization`. Use this when they really need to be printed, for
+    /// example while debugging a test.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::AUTHORIZATION;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(AUTHORIZATION, "Bearer secret".parse().unwrap());
+    ///
+    /// assert_eq!(format!("{:?}", map), r#"{"authorization": <redacted>}"#);
+    /// assert_eq!(
+    ///     format!("{:?}", map.unredacted()),
+    ///     r#"{"authorization": "Bearer secret"}"#
+    /// );
+    /// ```
+    pub fn unredacted(&self) -> Unredacted<'_, T, S> {
+        Unredacted { map: self }
+    }
+
+    /// Returns true if both maps hold the same headers in the same order.
+    ///
+    /// `==` already requires the values of each name to appear in the same
+    /// order, but ignores the order of the names themselves. `strict_eq`
+    /// also requires the names to appear in the same order, as they would be
+    /// written on the wire. This matters for protocols such as HTTP message
+    /// signatures, which cover headers in the order they were sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST};
+    /// let mut a = HeaderMap::new();
+    /// a.insert(HOST, "example.com".parse().unwrap());
+    /// a.insert(ACCEPT, "*/*".parse().unwrap());
+    ///
+    /// let mut b = HeaderMap::new();
+    /// b.insert(ACCEPT, "*/*".parse().unwrap());
+    /// b.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert!(a == b);
+    /// assert!(!a.strict_eq(&b));
+    /// ```
+    pub fn strict_eq(&self, other: &HeaderMap<T, S>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.len() == other.len() && self.iter().eq(other.iter())
     }
 
     /// An iterator visiting all key-value pairs, with mutable value references.
@@ -907,7 +1649,8 @@ impl<T> HeaderMap<T> {
     /// ```
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
-            map: self as *mut _,
+            entries: &mut self.entries[..] as *mut _,
+            extra_values: &mut self.extra_values[..] as *mut _,
             entry: 0,
             cursor: self.entries.first().map(|_| Cursor::Head),
             lt: PhantomData,
@@ -1049,30 +1792,131 @@ impl<T> HeaderMap<T> {
         }
     }
 
-    fn value_iter(&self, idx: Option<usize>) -> ValueIter<'_, T> {
-        use self::Cursor::*;
+    /// Moves every header whose name matches `f` into a new map.
+    ///
+    /// All values of a matching name are moved, without being cloned, and
+    /// both maps keep the relative order of their headers. Trailer marks
+    /// move with their names. The returned map uses a clone of this map's
+    /// hasher and has the same value limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONNECTION, CONTENT_TYPE, TE};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    /// map.insert(CONNECTION, "close".parse().unwrap());
+    /// map.append(TE, "trailers".parse().unwrap());
+    ///
+    /// let hop_by_hop = map.split_off(|name| name == CONNECTION || name == TE);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(hop_by_hop.len(), 2);
+    /// assert_eq!(hop_by_hop[TE], "trailers");
+    /// ```
+    pub fn split_off<F>(&mut self, mut f: F) -> HeaderMap<T, S>
+    where
+        F: FnMut(&HeaderName) -> bool,
+        S: Clone,
+    {
+        let mut other = HeaderMap::with_hasher(self.hash_builder.clone());
+        other.limit = self.limit;
+        if self.danger.is_red() {
+            other.danger = self.danger.clone();
+        }
 
-        if let Some(idx) = idx {
-            let back = {
-                let entry = &self.entries[idx];
+        let trailers: Vec<bool> = self.entries.iter().map(|e| e.trailer).collect();
+        let drained: Vec<(Option<HeaderName>, T)> = self.drain().collect();
+
+        let mut trailers = trailers.into_iter();
+        let mut current: Option<(HeaderName, bool)> = None;
+        for (key, value) in drained {
+            if let Some(key) = key {
+                let moved = f(&key);
+                let trailer = trailers.next().unwrap_or(false);
+                let dst = if moved { &mut other } else { &mut *self };
+                dst.append(&key, value);
+                if trailer {
+                    dst.set_trailer(&key, true);
+                }
+                current = Some((key, moved));
+            } else if let Some((ref name, moved)) = current {
+                let dst = if moved { &mut other } else { &mut *self };
+                dst.append(name, value);
+            }
+        }
 
-                entry.links.map(|l| Values(l.tail)).unwrap_or(Head)
-            };
+        other
+    }
 
-            ValueIter {
-                map: self,
-                index: idx,
-                front: Some(Head),
-                back: Some(back),
-            }
-        } else {
-            ValueIter {
-                map: self,
-                index: usize::MAX,
-                front: None,
-                back: None,
+    /// Sorts the headers into the order given by `profile`.
+    ///
+    /// Headers named in the profile come first, in the order they are listed
+    /// there. All other headers follow, in their current relative order. The
+    /// values of each header keep their order as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{Profile, ACCEPT, HOST};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(ACCEPT, "*/*".parse().unwrap());
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// map.reorder(Profile::CHROME);
+    ///
+    /// let names: Vec<_> = map.keys().collect();
+    /// assert_eq!(names, [HOST, ACCEPT]);
+    /// ```
+    pub fn reorder(&mut self, profile: Profile) {
+        self.reorder_by(|a, b| profile.compare(a, b))
+    }
+
+    /// Sorts the headers with a comparator function.
+    ///
+    /// The sort is stable, so headers that compare equal keep their current
+    /// relative order. The values of each header keep their order as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST, USER_AGENT};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(USER_AGENT, "hyper".parse().unwrap());
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.insert(ACCEPT, "*/*".parse().unwrap());
+    ///
+    /// map.reorder_by(|a, b| a.as_str().cmp(b.as_str()));
+    ///
+    /// let names: Vec<_> = map.keys().collect();
+    /// assert_eq!(names, [ACCEPT, HOST, USER_AGENT]);
+    /// ```
+    pub fn reorder_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&HeaderName, &HeaderName) -> Ordering,
+    {
+        self.entries.sort_by(|a, b| compare(&a.key, &b.key));
+
+        // Point the value lists back at their (possibly moved) entries
+        for (index, entry) in self.entries.iter().enumerate() {
+            if let Some(links) = entry.links {
+                self.extra_values[links.next].prev = Link::Entry(index);
+                self.extra_values[links.tail].next = Link::Entry(index);
             }
         }
+
+        for pos in self.indices.iter_mut() {
+            *pos = Pos::none();
+        }
+
+        self.rebuild();
+    }
+
+    fn value_iter(&self, idx: Option<usize>) -> ValueIter<'_, T> {
+        ValueIter::new(&self.entries, &self.extra_values, idx)
     }
 
     fn value_iter_mut(&mut self, idx: usize) -> ValueIterMut<'_, T> {
@@ -1085,7 +1929,8 @@ impl<T> HeaderMap<T> {
         };
 
         ValueIterMut {
-            map: self as *mut _,
+            entries: &mut self.entries[..] as *mut _,
+            extra_values: &mut self.extra_values[..] as *mut _,
             index: idx,
             front: Some(Head),
             back: Some(back),
@@ -1121,7 +1966,7 @@ impl<T> HeaderMap<T> {
     /// assert_eq!(map["content-length"], 2);
     /// assert_eq!(map["x-hello"], 1);
     /// ```
-    pub fn entry<K>(&mut self, key: K) -> Entry<'_, T>
+    pub fn entry<K>(&mut self, key: K) -> Entry<'_, T, S>
     where
         K: IntoHeaderName,
     {
@@ -1142,7 +1987,7 @@ impl<T> HeaderMap<T> {
     /// error. However, to prevent breaking changes to the return type, the
     /// error will still say `InvalidHeaderName`, unlike other `try_*` methods
     /// which return a `MaxSizeReached` error.
-    pub fn try_entry<K>(&mut self, key: K) -> Result<Entry<'_, T>, InvalidHeaderName>
+    pub fn try_entry<K>(&mut self, key: K) -> Result<Entry<'_, T, S>, InvalidHeaderName>
     where
         K: AsHeaderName,
     {
@@ -1157,7 +2002,7 @@ impl<T> HeaderMap<T> {
         })
     }
 
-    fn try_entry2<K>(&mut self, key: K) -> Result<Entry<'_, T>, MaxSizeReached>
+    fn try_entry2<K>(&mut self, key: K) -> Result<Entry<'_, T, S>, MaxSizeReached>
     where
         K: Hash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
@@ -1413,6 +2258,110 @@ impl<T> HeaderMap<T> {
         key.try_append(self, value)
     }
 
+    /// Appends all key-value pairs of an iterator to the map.
+    ///
+    /// This is equivalent to calling [`append`](HeaderMap::append) for each
+    /// pair, but reserves space for the new keys up front. Runs of pairs with
+    /// the same key, which are common in parsed messages, only look the key up
+    /// once.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if capacity exceeds max `HeaderMap` capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{HOST, SET_COOKIE};
+    /// let mut map = HeaderMap::new();
+    /// map.append_all(vec![
+    ///     (HOST, "example.com".parse().unwrap()),
+    ///     (SET_COOKIE, "a=1".parse().unwrap()),
+    ///     (SET_COOKIE, "b=2".parse().unwrap()),
+    /// ]);
+    ///
+    /// assert_eq!(map.len(), 3);
+    /// assert_eq!(map.get_all(SET_COOKIE).iter().count(), 2);
+    /// ```
+    pub fn append_all<K, I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, T)>,
+        K: IntoHeaderName,
+    {
+        self.try_append_all(iter).expect("size overflows MAX_SIZE")
+    }
+
+    /// Appends all key-value pairs of an iterator to the map.
+    ///
+    /// This is equivalent to calling [`try_append`](HeaderMap::try_append)
+    /// for each pair, but reserves space for the new keys up front. Runs of
+    /// pairs with the same key, which are common in parsed messages, only look
+    /// the key up once.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if `HeaderMap` exceeds max capacity.
+    /// The pairs before the one that failed are still appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// let mut map: HeaderMap = HeaderMap::with_limit(2);
+    /// let pairs = vec![
+    ///     ("accept", "text/html".parse().unwrap()),
+    ///     ("accept", "*/*".parse().unwrap()),
+    ///     ("host", "example.com".parse().unwrap()),
+    /// ];
+    ///
+    /// assert!(map.try_append_all(pairs).is_err());
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn try_append_all<K, I>(&mut self, iter: I) -> Result<(), MaxSizeReached>
+    where
+        I: IntoIterator<Item = (K, T)>,
+        K: IntoHeaderName,
+    {
+        // Reserve like `Extend` does. This is only an optimization, so a hint
+        // that is too large is not an error in itself.
+        let iter = iter.into_iter();
+        let reserve = if self.is_empty() {
+            iter.size_hint().0
+        } else {
+            (iter.size_hint().0 + 1) / 2
+        };
+        let _ = self.try_reserve(reserve);
+
+        // Index of the entry the previous pair was appended to
+        let mut last: Option<usize> = None;
+
+        for (key, value) in iter {
+            if let Some(index) = last {
+                if key.is_name(&self.entries[index].key) {
+                    self.check_limit()?;
+                    append_value(
+                        index,
+                        &mut self.entries[index],
+                        &mut self.extra_values,
+                        value,
+                    );
+                    continue;
+                }
+            }
+
+            last = Some(match key.try_entry(self)? {
+                Entry::Occupied(mut e) => {
+                    e.try_append(value)?;
+                    e.index
+                }
+                Entry::Vacant(e) => e.try_insert_entry(value)?.index,
+            });
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn try_append2<K>(&mut self, key: K, value: T) -> Result<bool, MaxSizeReached>
     where
@@ -1438,6 +2387,7 @@ impl<T> HeaderMap<T> {
             },
             // Occupied
             {
+                self.check_limit()?;
                 append_value(pos, &mut self.entries[pos], &mut self.extra_values, value);
                 true
             },
@@ -1453,14 +2403,14 @@ impl<T> HeaderMap<T> {
     #[inline]
     fn find<K>(&self, key: &K) -> Option<(usize, usize)>
     where
-        K: Hash + Into<HeaderName> + ?Sized,
+        K: Hash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
     {
         if self.entries.is_empty() {
             return None;
         }
 
-        let hash = hash_elem_using(&self.danger, key);
+        let hash = hash_elem_using(&self.danger, &self.hash_builder, key);
         let mask = self.mask;
         let mut probe = desired_pos(mask, hash);
         let mut dist = 0;
@@ -1633,12 +2583,14 @@ impl<T> HeaderMap<T> {
         if self.entries.len() >= MAX_SIZE {
             return Err(MaxSizeReached::new());
         }
+        self.check_limit()?;
 
         self.entries.push(Bucket {
             hash,
             key,
             value,
             links: None,
+            trailer: false,
         });
 
         Ok(())
@@ -1647,7 +2599,7 @@ impl<T> HeaderMap<T> {
     fn rebuild(&mut self) {
         // Loop over all entries and re-insert them into the map
         'outer: for (index, entry) in self.entries.iter_mut().enumerate() {
-            let hash = hash_elem_using(&self.danger, &entry.key);
+            let hash = hash_elem_using(&self.danger, &self.hash_builder, &entry.key);
             let mut probe = desired_pos(self.mask, hash);
             let mut dist = 0;
 
@@ -1691,6 +2643,15 @@ impl<T> HeaderMap<T> {
         }
     }
 
+    /// Fails if adding another value would exceed the map's limit.
+    #[inline]
+    fn check_limit(&self) -> Result<(), MaxSizeReached> {
+        if self.len() >= self.limit {
+            return Err(MaxSizeReached::new());
+        }
+        Ok(())
+    }
+
     fn try_reserve_one(&mut self) -> Result<(), MaxSizeReached> {
         let len = self.entries.len();
 
@@ -1720,7 +2681,7 @@ impl<T> HeaderMap<T> {
             if len == 0 {
                 let new_raw_cap = 8;
                 self.mask = 8 - 1;
-                self.indices = vec![Pos::none(); new_raw_cap].into_boxed_slice();
+                self.indices = Indices::new(new_raw_cap);
                 self.entries = Vec::with_capacity(usable_capacity(new_raw_cap));
             } else {
                 let raw_cap = self.indices.len();
@@ -1751,10 +2712,7 @@ impl<T> HeaderMap<T> {
 
         // visit the entries in an order where we can simply reinsert them
         // into self.indices without any bucket stealing.
-        let old_indices = mem::replace(
-            &mut self.indices,
-            vec![Pos::none(); new_raw_cap].into_boxed_slice(),
-        );
+        let old_indices = mem::replace(&mut self.indices, Indices::new(new_raw_cap));
         self.mask = new_raw_cap.wrapping_sub(1) as Size;
 
         for &pos in &old_indices[first_ideal..] {
@@ -1916,7 +2874,7 @@ fn drain_all_extra_values<T>(
     vec
 }
 
-impl<'a, T> IntoIterator for &'a HeaderMap<T> {
+impl<'a, T, S: BuildHasher> IntoIterator for &'a HeaderMap<T, S> {
     type Item = (&'a HeaderName, &'a T);
     type IntoIter = Iter<'a, T>;
 
@@ -1925,7 +2883,7 @@ impl<'a, T> IntoIterator for &'a HeaderMap<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut HeaderMap<T> {
+impl<'a, T, S: BuildHasher> IntoIterator for &'a mut HeaderMap<T, S> {
     type Item = (&'a HeaderName, &'a mut T);
     type IntoIter = IterMut<'a, T>;
 
@@ -1934,7 +2892,7 @@ impl<'a, T> IntoIterator for &'a mut HeaderMap<T> {
     }
 }
 
-impl<T> IntoIterator for HeaderMap<T> {
+impl<T, S> IntoIterator for HeaderMap<T, S> {
     type Item = (Option<HeaderName>, T);
     type IntoIter = IntoIter<T>;
 
@@ -2043,7 +3001,7 @@ where
     }
 }
 
-impl<T> Extend<(Option<HeaderName>, T)> for HeaderMap<T> {
+impl<T, S: BuildHasher> Extend<(Option<HeaderName>, T)> for HeaderMap<T, S> {
     /// Extend a `HeaderMap` with the contents of another `HeaderMap`.
     ///
     /// This function expects the yielded items to follow the same structure as
@@ -2123,7 +3081,7 @@ impl<T> Extend<(Option<HeaderName>, T)> for HeaderMap<T> {
     }
 }
 
-impl<T> Extend<(HeaderName, T)> for HeaderMap<T> {
+impl<T, S: BuildHasher> Extend<(HeaderName, T)> for HeaderMap<T, S> {
     fn extend<I: IntoIterator<Item = (HeaderName, T)>>(&mut self, iter: I) {
         // Keys may be already present or show multiple times in the iterator.
         // Reserve the entire hint lower bound if the map is empty.
@@ -2145,8 +3103,8 @@ impl<T> Extend<(HeaderName, T)> for HeaderMap<T> {
     }
 }
 
-impl<T: PartialEq> PartialEq for HeaderMap<T> {
-    fn eq(&self, other: &HeaderMap<T>) -> bool {
+impl<T: PartialEq, S: BuildHasher> PartialEq for HeaderMap<T, S> {
+    fn eq(&self, other: &HeaderMap<T, S>) -> bool {
         if self.len() != other.len() {
             return false;
         }
@@ -2156,21 +3114,87 @@ impl<T: PartialEq> PartialEq for HeaderMap<T> {
     }
 }
 
-impl<T: Eq> Eq for HeaderMap<T> {}
+impl<T: Eq, S: BuildHasher> Eq for HeaderMap<T, S> {}
 
-impl<T: fmt::Debug> fmt::Debug for HeaderMap<T> {
+impl<T: fmt::Debug, S> fmt::Debug for HeaderMap<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_map().entries(self.iter()).finish()
+        f.debug_map()
+            .entries(
+                Iter::new(&self.entries, &self.extra_values).map(|(name, value)| {
+                    let redact = CREDENTIALS.contains(name);
+                    (name, DebugValue { value, redact })
+                }),
+            )
+            .finish()
+    }
+}
+
+/// A view of a `HeaderMap` whose `Debug` output shows every value.
+///
+/// This struct is returned by [`HeaderMap::unredacted`].
+pub struct Unredacted<'a, T, S = DefaultHashBuilder> {
+    map: &'a HeaderMap<T, S>,
+}
+
+impl<'a, T: fmt::Debug, S> fmt::Debug for Unredacted<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(Iter::new(&self.map.entries, &self.map.extra_values))
+            .finish()
+    }
+}
+
+/// Formats a `HeaderMap` with credential headers and sensitive values hidden.
+///
+/// Used by the `Debug` impls of requests and responses. On top of what the
+/// map's own `Debug` hides, any value marked sensitive is also shown as
+/// `<redacted>`.
+pub(crate) struct Redacted<'a>(pub(crate) &'a HeaderMap<HeaderValue>);
+
+impl<'a> fmt::Debug for Redacted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let redact = value.is_sensitive() || CREDENTIALS.contains(name);
+                (name, DebugValue { value, redact })
+            }))
+            .finish()
     }
 }
 
+struct DebugValue<'a, T> {
+    value: &'a T,
+    redact: bool,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugValue<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.redact {
+            f.write_str(REDACTED)
+        } else {
+            fmt::Debug::fmt(self.value, f)
+        }
+    }
+}
+
+/// Headers whose values are credentials, and are hidden when printed.
+pub(crate) const CREDENTIALS: [HeaderName; 4] = [
+    super::AUTHORIZATION,
+    super::PROXY_AUTHORIZATION,
+    super::COOKIE,
+    super::SET_COOKIE,
+];
+
+/// Printed in place of a hidden value.
+pub(crate) const REDACTED: &str = "<redacted>";
+
 impl<T> Default for HeaderMap<T> {
     fn default() -> Self {
         HeaderMap::try_with_capacity(0).expect("zero capacity should never fail")
     }
 }
 
-impl<K, T> ops::Index<K> for HeaderMap<T>
+impl<K, T, S: BuildHasher> ops::Index<K> for HeaderMap<T, S>
 where
     K: AsHeaderName,
 {
@@ -2247,6 +3271,17 @@ fn append_value<T>(
 
 // ===== impl Iter =====
 
+impl<'a, T> Iter<'a, T> {
+    fn new(entries: &'a [Bucket<T>], extra_values: &'a [ExtraValue<T>]) -> Iter<'a, T> {
+        Iter {
+            entries,
+            extra_values,
+            entry: 0,
+            cursor: entries.first().map(|_| Cursor::Head),
+        }
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = (&'a HeaderName, &'a T);
 
@@ -2254,7 +3289,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
         use self::Cursor::*;
 
         if self.cursor.is_none() {
-            if (self.entry + 1) >= self.map.entries.len() {
+            if (self.entry + 1) >= self.entries.len() {
                 return None;
             }
 
@@ -2262,7 +3297,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
             self.cursor = Some(Cursor::Head);
         }
 
-        let entry = &self.map.entries[self.entry];
+        let entry = &self.entries[self.entry];
 
         match self.cursor.unwrap() {
             Head => {
@@ -2270,7 +3305,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
                 Some((&entry.key, &entry.value))
             }
             Values(idx) => {
-                let extra = &self.map.extra_values[idx];
+                let extra = &self.extra_values[idx];
 
                 match extra.next {
                     Link::Entry(_) => self.cursor = None,
@@ -2283,10 +3318,9 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let map = self.map;
-        debug_assert!(map.entries.len() >= self.entry);
+        debug_assert!(self.entries.len() >= self.entry);
 
-        let lower = map.entries.len() - self.entry;
+        let lower = self.entries.len() - self.entry;
         // We could pessimistically guess at the upper bound, saying
         // that its lower + map.extra_values.len(). That could be
         // way over though, such as if we're near the end, and have
@@ -2297,6 +3331,35 @@ impl<'a, T> Iterator for Iter<'a, T> {
 
 impl<'a, T> FusedIterator for Iter<'a, T> {}
 
+// ===== impl Trailers =====
+
+impl<'a, T> Iterator for Trailers<'a, T> {
+    type Item = (&'a HeaderName, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = &mut self.inner;
+        loop {
+            if inner.cursor.is_none() {
+                if (inner.entry + 1) >= inner.entries.len() {
+                    return None;
+                }
+
+                inner.entry += 1;
+                inner.cursor = Some(Cursor::Head);
+            }
+
+            if inner.entries[inner.entry].trailer {
+                return inner.next();
+            }
+
+            // Skip the rest of this entry's values.
+            inner.cursor = None;
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for Trailers<'a, T> {}
+
 unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
 unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
 
@@ -2307,7 +3370,7 @@ impl<'a, T> IterMut<'a, T> {
         use self::Cursor::*;
 
         if self.cursor.is_none() {
-            if (self.entry + 1) >= unsafe { &*self.map }.entries.len() {
+            if (self.entry + 1) >= unsafe { &*self.entries }.len() {
                 return None;
             }
 
@@ -2315,7 +3378,7 @@ impl<'a, T> IterMut<'a, T> {
             self.cursor = Some(Cursor::Head);
         }
 
-        let entry = unsafe { &mut (*self.map).entries[self.entry] };
+        let entry = unsafe { &mut (*self.entries)[self.entry] };
 
         match self.cursor.unwrap() {
             Head => {
@@ -2323,7 +3386,7 @@ impl<'a, T> IterMut<'a, T> {
                 Some((&entry.key, &mut entry.value as *mut _))
             }
             Values(idx) => {
-                let extra = unsafe { &mut (*self.map).extra_values[idx] };
+                let extra = unsafe { &mut (*self.extra_values)[idx] };
 
                 match extra.next {
                     Link::Entry(_) => self.cursor = None,
@@ -2345,10 +3408,10 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let map = unsafe { &*self.map };
-        debug_assert!(map.entries.len() >= self.entry);
+        let entries = unsafe { &*self.entries };
+        debug_assert!(entries.len() >= self.entry);
 
-        let lower = map.entries.len() - self.entry;
+        let lower = entries.len() - self.entry;
         // We could pessimistically guess at the upper bound, saying
         // that its lower + map.extra_values.len(). That could be
         // way over though, such as if we're near the end, and have
@@ -2488,7 +3551,7 @@ unsafe impl<'a, T: Send> Send for Drain<'a, T> {}
 
 // ===== impl Entry =====
 
-impl<'a, T> Entry<'a, T> {
+impl<'a, T, S: BuildHasher> Entry<'a, T, S> {
     /// Ensures a value is in the entry by inserting the default if empty.
     ///
     /// Returns a mutable reference to the **first** value in the entry.
@@ -2675,7 +3738,7 @@ impl<'a, T> Entry<'a, T> {
 
 // ===== impl VacantEntry =====
 
-impl<'a, T> VacantEntry<'a, T> {
+impl<'a, T, S: BuildHasher> VacantEntry<'a, T, S> {
     /// Returns a reference to the entry's key
     ///
     /// # Examples
@@ -2771,7 +3834,7 @@ impl<'a, T> VacantEntry<'a, T> {
     ///
     /// assert_eq!(map["x-hello"], "world2");
     /// ```
-    pub fn insert_entry(self, value: T) -> OccupiedEntry<'a, T> {
+    pub fn insert_entry(self, value: T) -> OccupiedEntry<'a, T, S> {
         self.try_insert_entry(value)
             .expect("size overflows MAX_SIZE")
     }
@@ -2794,7 +3857,7 @@ impl<'a, T> VacantEntry<'a, T> {
     ///
     /// assert_eq!(map["x-hello"], "world2");
     /// ```
-    pub fn try_insert_entry(self, value: T) -> Result<OccupiedEntry<'a, T>, MaxSizeReached> {
+    pub fn try_insert_entry(self, value: T) -> Result<OccupiedEntry<'a, T, S>, MaxSizeReached> {
         // Ensure that there is space in the map
         let index =
             self.map
@@ -2810,10 +3873,76 @@ impl<'a, T> VacantEntry<'a, T> {
 
 // ===== impl GetAll =====
 
-impl<'a, T: 'a> GetAll<'a, T> {
-    /// Returns an iterator visiting all values associated with the entry.
-    ///
-    /// Values are iterated in insertion order.
+impl<'a, T: 'a> GetAll<'a, T> {
+    /// Returns an iterator visiting all values associated with the entry.
+    ///
+    /// Values are iterated in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::HOST;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "hello.world".parse().unwrap());
+    /// map.append(HOST, "hello.earth".parse().unwrap());
+    ///
+    /// let values = map.get_all("host");
+    /// let mut iter = values.iter();
+    /// assert_eq!(&"hello.world", iter.next().unwrap());
+    /// assert_eq!(&"hello.earth", iter.next().unwrap());
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn iter(&self) -> ValueIter<'a, T> {
+        // This creates a new GetAll struct so that the lifetime
+        // isn't bound to &self.
+        GetAll {
+            entries: self.entries,
+            extra_values: self.extra_values,
+            index: self.index,
+        }
+        .into_iter()
+    }
+
+    /// Returns the number of values associated with the entry.
+    ///
+    /// A header with at most one value is answered without visiting any
+    /// values; otherwise the values are counted by following their links,
+    /// which does not touch the rest of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{COOKIE, HOST};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.append(COOKIE, "a=1".parse().unwrap());
+    /// map.append(COOKIE, "b=2".parse().unwrap());
+    ///
+    /// assert_eq!(map.get_all(HOST).len(), 1);
+    /// assert_eq!(map.get_all(COOKIE).len(), 2);
+    /// assert_eq!(map.get_all("x-missing").len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        let index = match self.index {
+            Some(index) => index,
+            None => return 0,
+        };
+
+        let mut len = 1;
+        let mut next = self.entries[index].links.map(|l| l.next);
+        while let Some(idx) = next {
+            len += 1;
+            next = match self.extra_values[idx].next {
+                Link::Extra(i) => Some(i),
+                Link::Entry(_) => None,
+            };
+        }
+        len
+    }
+
+    /// Returns true if there are no values associated with the entry.
     ///
     /// # Examples
     ///
@@ -2821,23 +3950,32 @@ impl<'a, T: 'a> GetAll<'a, T> {
     /// # use http::HeaderMap;
     /// # use http::header::HOST;
     /// let mut map = HeaderMap::new();
-    /// map.insert(HOST, "hello.world".parse().unwrap());
-    /// map.append(HOST, "hello.earth".parse().unwrap());
+    /// assert!(map.get_all(HOST).is_empty());
     ///
-    /// let values = map.get_all("host");
-    /// let mut iter = values.iter();
-    /// assert_eq!(&"hello.world", iter.next().unwrap());
-    /// assert_eq!(&"hello.earth", iter.next().unwrap());
-    /// assert!(iter.next().is_none());
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// assert!(!map.get_all(HOST).is_empty());
     /// ```
-    pub fn iter(&self) -> ValueIter<'a, T> {
-        // This creates a new GetAll struct so that the lifetime
-        // isn't bound to &self.
-        GetAll {
-            map: self.map,
-            index: self.index,
-        }
-        .into_iter()
+    pub fn is_empty(&self) -> bool {
+        self.index.is_none()
+    }
+
+    /// Returns the value at position `index`, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::VIA;
+    /// let mut map = HeaderMap::new();
+    /// map.append(VIA, "1.1 a".parse().unwrap());
+    /// map.append(VIA, "1.1 b".parse().unwrap());
+    ///
+    /// let via = map.get_all(VIA);
+    /// assert_eq!(via.get(1).unwrap(), &"1.1 b");
+    /// assert!(via.get(2).is_none());
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.iter().nth(index)
     }
 }
 
@@ -2852,7 +3990,7 @@ impl<'a, T> IntoIterator for GetAll<'a, T> {
     type IntoIter = ValueIter<'a, T>;
 
     fn into_iter(self) -> ValueIter<'a, T> {
-        self.map.value_iter(self.index)
+        ValueIter::new(self.entries, self.extra_values, self.index)
     }
 }
 
@@ -2861,12 +3999,46 @@ impl<'a, 'b: 'a, T> IntoIterator for &'b GetAll<'a, T> {
     type IntoIter = ValueIter<'a, T>;
 
     fn into_iter(self) -> ValueIter<'a, T> {
-        self.map.value_iter(self.index)
+        ValueIter::new(self.entries, self.extra_values, self.index)
     }
 }
 
 // ===== impl ValueIter =====
 
+impl<'a, T: 'a> ValueIter<'a, T> {
+    fn new(
+        entries: &'a [Bucket<T>],
+        extra_values: &'a [ExtraValue<T>],
+        idx: Option<usize>,
+    ) -> ValueIter<'a, T> {
+        use self::Cursor::*;
+
+        if let Some(idx) = idx {
+            let back = {
+                let entry = &entries[idx];
+
+                entry.links.map(|l| Values(l.tail)).unwrap_or(Head)
+            };
+
+            ValueIter {
+                entries,
+                extra_values,
+                index: idx,
+                front: Some(Head),
+                back: Some(back),
+            }
+        } else {
+            ValueIter {
+                entries,
+                extra_values,
+                index: usize::MAX,
+                front: None,
+                back: None,
+            }
+        }
+    }
+}
+
 impl<'a, T: 'a> Iterator for ValueIter<'a, T> {
     type Item = &'a T;
 
@@ -2875,7 +4047,7 @@ impl<'a, T: 'a> Iterator for ValueIter<'a, T> {
 
         match self.front {
             Some(Head) => {
-                let entry = &self.map.entries[self.index];
+                let entry = &self.entries[self.index];
 
                 if self.back == Some(Head) {
                     self.front = None;
@@ -2893,7 +4065,7 @@ impl<'a, T: 'a> Iterator for ValueIter<'a, T> {
                 Some(&entry.value)
             }
             Some(Values(idx)) => {
-                let extra = &self.map.extra_values[idx];
+                let extra = &self.extra_values[idx];
 
                 if self.front == self.back {
                     self.front = None;
@@ -2931,10 +4103,10 @@ impl<'a, T: 'a> DoubleEndedIterator for ValueIter<'a, T> {
             Some(Head) => {
                 self.front = None;
                 self.back = None;
-                Some(&self.map.entries[self.index].value)
+                Some(&self.entries[self.index].value)
             }
             Some(Values(idx)) => {
-                let extra = &self.map.extra_values[idx];
+                let extra = &self.extra_values[idx];
 
                 if self.front == self.back {
                     self.front = None;
@@ -2963,7 +4135,7 @@ impl<'a, T: 'a> Iterator for ValueIterMut<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         use self::Cursor::*;
 
-        let entry = unsafe { &mut (*self.map).entries[self.index] };
+        let entry = unsafe { &mut (*self.entries)[self.index] };
 
         match self.front {
             Some(Head) => {
@@ -2983,7 +4155,7 @@ impl<'a, T: 'a> Iterator for ValueIterMut<'a, T> {
                 Some(&mut entry.value)
             }
             Some(Values(idx)) => {
-                let extra = unsafe { &mut (*self.map).extra_values[idx] };
+                let extra = unsafe { &mut (*self.extra_values)[idx] };
 
                 if self.front == self.back {
                     self.front = None;
@@ -3006,7 +4178,7 @@ impl<'a, T: 'a> DoubleEndedIterator for ValueIterMut<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         use self::Cursor::*;
 
-        let entry = unsafe { &mut (*self.map).entries[self.index] };
+        let entry = unsafe { &mut (*self.entries)[self.index] };
 
         match self.back {
             Some(Head) => {
@@ -3015,7 +4187,7 @@ impl<'a, T: 'a> DoubleEndedIterator for ValueIterMut<'a, T> {
                 Some(&mut entry.value)
             }
             Some(Values(idx)) => {
-                let extra = unsafe { &mut (*self.map).extra_values[idx] };
+                let extra = unsafe { &mut (*self.extra_values)[idx] };
 
                 if self.front == self.back {
                     self.front = None;
@@ -3092,7 +4264,7 @@ impl<T> Drop for IntoIter<T> {
 
 // ===== impl OccupiedEntry =====
 
-impl<'a, T> OccupiedEntry<'a, T> {
+impl<'a, T, S: BuildHasher> OccupiedEntry<'a, T, S> {
     /// Returns a reference to the entry's key.
     ///
     /// # Examples
@@ -3258,10 +4430,38 @@ impl<'a, T> OccupiedEntry<'a, T> {
     /// assert_eq!("world", *i.next().unwrap());
     /// assert_eq!("earth", *i.next().unwrap());
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the map's limit is reached.
     pub fn append(&mut self, value: T) {
+        self.try_append(value).expect("size overflows limit")
+    }
+
+    /// Insert the value into the entry.
+    ///
+    /// The new value is appended to the end of the entry's value list. All
+    /// previous values associated with the entry are retained.
+    ///
+    /// An error is returned if the map's limit is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderMap, Entry, HOST};
+    /// let mut map: HeaderMap = HeaderMap::with_limit(1);
+    /// map.insert(HOST, "world".parse().unwrap());
+    ///
+    /// if let Entry::Occupied(mut e) = map.entry("host") {
+    ///     assert!(e.try_append("earth".parse().unwrap()).is_err());
+    /// }
+    /// ```
+    pub fn try_append(&mut self, value: T) -> Result<(), MaxSizeReached> {
+        self.map.check_limit()?;
         let idx = self.index;
         let entry = &mut self.map.entries[idx];
         append_value(idx, entry, &mut self.map.extra_values, value);
+        Ok(())
     }
 
     /// Remove the entry from the map.
@@ -3392,7 +4592,7 @@ impl<'a, T> OccupiedEntry<'a, T> {
     }
 }
 
-impl<'a, T> IntoIterator for OccupiedEntry<'a, T> {
+impl<'a, T, S: BuildHasher> IntoIterator for OccupiedEntry<'a, T, S> {
     type Item = &'a mut T;
     type IntoIter = ValueIterMut<'a, T>;
 
@@ -3401,7 +4601,7 @@ impl<'a, T> IntoIterator for OccupiedEntry<'a, T> {
     }
 }
 
-impl<'a, 'b: 'a, T> IntoIterator for &'b OccupiedEntry<'a, T> {
+impl<'a, 'b: 'a, T, S: BuildHasher> IntoIterator for &'b OccupiedEntry<'a, T, S> {
     type Item = &'a T;
     type IntoIter = ValueIter<'a, T>;
 
@@ -3410,7 +4610,7 @@ impl<'a, 'b: 'a, T> IntoIterator for &'b OccupiedEntry<'a, T> {
     }
 }
 
-impl<'a, 'b: 'a, T> IntoIterator for &'b mut OccupiedEntry<'a, T> {
+impl<'a, 'b: 'a, T, S: BuildHasher> IntoIterator for &'b mut OccupiedEntry<'a, T, S> {
     type Item = &'a mut T;
     type IntoIter = ValueIterMut<'a, T>;
 
@@ -3526,9 +4726,56 @@ impl Pos {
     }
 }
 
+impl Indices {
+    fn new(raw_cap: usize) -> Indices {
+        if raw_cap <= INLINE_INDICES {
+            Indices::Inline {
+                slots: [Pos::none(); INLINE_INDICES],
+                len: raw_cap as u8,
+            }
+        } else {
+            Indices::Heap(vec![Pos::none(); raw_cap].into_boxed_slice())
+        }
+    }
+
+    /// Returns the number of slots stored on the heap.
+    fn allocated_len(&self) -> usize {
+        match *self {
+            Indices::Inline { .. } => 0,
+            Indices::Heap(ref slots) => slots.len(),
+        }
+    }
+}
+
+impl ops::Deref for Indices {
+    type Target = [Pos];
+
+    #[inline]
+    fn deref(&self) -> &[Pos] {
+        match *self {
+            Indices::Inline { ref slots, len } => &slots[..len as usize],
+            Indices::Heap(ref slots) => slots,
+        }
+    }
+}
+
+impl ops::DerefMut for Indices {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [Pos] {
+        match *self {
+            Indices::Inline { ref mut slots, len } => &mut slots[..len as usize],
+            Indices::Heap(ref mut slots) => slots,
+        }
+    }
+}
+
 impl Danger {
     fn is_red(&self) -> bool {
-        matches!(*self, Danger::Red(_))
+        matches!(*self, Danger::Red(_) | Danger::Secure(_))
+    }
+
+    fn is_secure(&self) -> bool {
+        matches!(*self, Danger::Secure(_))
     }
 
     fn set_red(&mut self) {
@@ -3576,6 +4823,271 @@ impl fmt::Display for MaxSizeReached {
 
 impl std::error::Error for MaxSizeReached {}
 
+// ===== impl DefaultHashBuilder =====
+
+impl BuildHasher for DefaultHashBuilder {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        DefaultHasher::default()
+    }
+}
+
+impl fmt::Debug for DefaultHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultHasher").finish()
+    }
+}
+
+impl Hasher for DefaultHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+}
+
+// ===== impl Serialize =====
+
+/// Serializes a `HeaderMap`, writing keys with a single value as a plain
+/// value instead of a list.
+///
+/// This struct is returned by [`HeaderMap::collapsed`].
+#[cfg(feature = "serde1")]
+#[derive(Debug)]
+pub struct CollapsedHeaders<'a, T, S = DefaultHashBuilder> {
+    map: &'a HeaderMap<T, S>,
+}
+
+#[cfg(feature = "serde1")]
+impl<T, S> HeaderMap<T, S> {
+    /// Returns a view of the map that serializes single values unwrapped.
+    ///
+    /// By default a `HeaderMap` serializes as a map of header name to a list
+    /// of values. This view instead writes a header with exactly one value as
+    /// that value, if the format is human readable and the value is a valid
+    /// string. Other values stay in a list, since a bare byte array could not
+    /// be told apart from a list of values when reading the map back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, SET_COOKIE};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(ACCEPT, "text/html".parse().unwrap());
+    /// map.append(SET_COOKIE, "a=1".parse().unwrap());
+    /// map.append(SET_COOKIE, "b=2".parse().unwrap());
+    ///
+    /// assert_eq!(
+    ///     serde_json::to_string(&map).unwrap(),
+    ///     r#"{"accept":["text/html"],"set-cookie":["a=1","b=2"]}"#,
+    /// );
+    /// assert_eq!(
+    ///     serde_json::to_string(&map.collapsed()).unwrap(),
+    ///     r#"{"accept":"text/html","set-cookie":["a=1","b=2"]}"#,
+    /// );
+    /// ```
+    pub fn collapsed(&self) -> CollapsedHeaders<'_, T, S> {
+        CollapsedHeaders { map: self }
+    }
+}
+
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::{CollapsedHeaders, GetAll, HeaderMap, HeaderName, HeaderValue};
+    use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+    use std::fmt;
+    use std::hash::BuildHasher;
+    use std::marker::PhantomData;
+
+    impl<T: Serialize, H: BuildHasher> Serialize for HeaderMap<T, H> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self, |_| false, serializer)
+        }
+    }
+
+    impl<'a, H: BuildHasher> Serialize for CollapsedHeaders<'a, HeaderValue, H> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let readable = serializer.is_human_readable();
+            serialize(
+                self.map,
+                |value| readable && value.to_str().is_ok(),
+                serializer,
+            )
+        }
+    }
+
+    fn serialize<T, H, S, F>(
+        map: &HeaderMap<T, H>,
+        collapse: F,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        H: BuildHasher,
+        S: Serializer,
+        F: Fn(&T) -> bool,
+    {
+        let mut state = serializer.serialize_map(Some(map.keys_len()))?;
+        for key in map.keys() {
+            let values = map.get_all(key);
+            let mut iter = values.iter();
+            match (iter.next(), iter.next()) {
+                (Some(value), None) if collapse(value) => state.serialize_entry(key, value)?,
+                _ => state.serialize_entry(key, &Values(values))?,
+            }
+        }
+        state.end()
+    }
+
+    struct Values<'a, T>(GetAll<'a, T>);
+
+    impl<'a, T: Serialize> Serialize for Values<'a, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.0.iter().count()))?;
+            for value in self.0.iter() {
+                seq.serialize_element(value)?;
+            }
+            seq.end()
+        }
+    }
+
+    // Accepts both a single value and a list of values for each header, so
+    // that both the plain and `collapsed` forms can be read back.
+    impl<'de, H: BuildHasher + Default> de::Deserialize<'de> for HeaderMap<HeaderValue, H> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(HeaderMapVisitor(PhantomData))
+        }
+    }
+
+    struct HeaderMapVisitor<H>(PhantomData<H>);
+
+    impl<'de, H: BuildHasher + Default> Visitor<'de> for HeaderMapVisitor<H> {
+        type Value = HeaderMap<HeaderValue, H>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map of header names to values")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut map = HeaderMap::with_hasher(H::default());
+            while let Some(key) = access.next_key::<String>()? {
+                let name = HeaderName::from_bytes(key.as_bytes()).map_err(|_| {
+                    de::Error::custom(format_args!("invalid header name `{}`", key))
+                })?;
+                access.next_value_seed(ValuesSeed {
+                    name: &name,
+                    map: &mut map,
+                })?;
+            }
+            Ok(map)
+        }
+    }
+
+    /// Appends either a single value or a list of values for `name`.
+    struct ValuesSeed<'a, H> {
+        name: &'a HeaderName,
+        map: &'a mut HeaderMap<HeaderValue, H>,
+    }
+
+    impl<'a, H: BuildHasher> ValuesSeed<'a, H> {
+        fn append<E: de::Error>(&mut self, value: HeaderValue) -> Result<(), E> {
+            self.map
+                .try_append(self.name, value)
+                .map(drop)
+                .map_err(E::custom)
+        }
+    }
+
+    impl<'a, 'de, H: BuildHasher> DeserializeSeed<'de> for ValuesSeed<'a, H> {
+        type Value = ();
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(self)
+            } else {
+                deserializer.deserialize_seq(self)
+            }
+        }
+    }
+
+    impl<'a, 'de, H: BuildHasher> Visitor<'de> for ValuesSeed<'a, H> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a value or list of values for header `{}`", self.name)
+        }
+
+        fn visit_str<E: de::Error>(mut self, v: &str) -> Result<(), E> {
+            let value = ValueSeed { name: self.name }.visit_str(v)?;
+            self.append(value)
+        }
+
+        fn visit_bytes<E: de::Error>(mut self, v: &[u8]) -> Result<(), E> {
+            let value = ValueSeed { name: self.name }.visit_bytes(v)?;
+            self.append(value)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+            while let Some(value) = seq.next_element_seed(ValueSeed { name: self.name })? {
+                self.append(value)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Deserializes one value, naming the header it belongs to on failure.
+    struct ValueSeed<'a> {
+        name: &'a HeaderName,
+    }
+
+    impl<'a, 'de> DeserializeSeed<'de> for ValueSeed<'a> {
+        type Value = HeaderValue;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<HeaderValue, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(self)
+            } else {
+                deserializer.deserialize_bytes(self)
+            }
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for ValueSeed<'a> {
+        type Value = HeaderValue;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a value for header `{}`", self.name)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<HeaderValue, E> {
+            self.visit_bytes(v.as_bytes())
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<HeaderValue, E> {
+            HeaderValue::from_bytes(v)
+                .map_err(|_| E::custom(format_args!("invalid value for header `{}`", self.name)))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<HeaderValue, A::Error> {
+            let mut bytes = Vec::new();
+            while let Some(b) = seq.next_element::<u8>()? {
+                bytes.push(b);
+            }
+            self.visit_bytes(&bytes)
+        }
+    }
+}
+
 // ===== impl Utils =====
 
 #[inline]
@@ -3605,24 +5117,23 @@ fn probe_distance(mask: Size, hash: HashValue, current: usize) -> usize {
     current.wrapping_sub(desired_pos(mask, hash)) & mask as usize
 }
 
-fn hash_elem_using<K>(danger: &Danger, k: &K) -> HashValue
+fn hash_elem_using<K, S>(danger: &Danger, hash_builder: &S, k: &K) -> HashValue
 where
     K: Hash + ?Sized,
+    S: BuildHasher,
 {
-    use fnv::FnvHasher;
-
     const MASK: u64 = (MAX_SIZE as u64) - 1;
 
     let hash = match *danger {
         // Safe hash
-        Danger::Red(ref hasher) => {
+        Danger::Red(ref hasher) | Danger::Secure(ref hasher) => {
             let mut h = hasher.build_hasher();
             k.hash(&mut h);
             h.finish()
         }
         // Fast hash
         _ => {
-            let mut h = FnvHasher::default();
+            let mut h = hash_builder.build_hasher();
             k.hash(&mut h);
             h.finish()
         }
@@ -3639,6 +5150,7 @@ where
 
 mod into_header_name {
     use super::{Entry, HdrName, HeaderMap, HeaderName, MaxSizeReached};
+    use std::hash::BuildHasher;
 
     /// A marker trait used to identify values that can be used as insert keys
     /// to a `HeaderMap`.
@@ -3654,81 +5166,130 @@ mod into_header_name {
     // without breaking any external crate.
     pub trait Sealed {
         #[doc(hidden)]
-        fn try_insert<T>(self, map: &mut HeaderMap<T>, val: T)
-            -> Result<Option<T>, MaxSizeReached>;
+        fn try_insert<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+            val: T,
+        ) -> Result<Option<T>, MaxSizeReached>;
+
+        #[doc(hidden)]
+        fn try_append<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+            val: T,
+        ) -> Result<bool, MaxSizeReached>;
 
         #[doc(hidden)]
-        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T) -> Result<bool, MaxSizeReached>;
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, MaxSizeReached>;
 
         #[doc(hidden)]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, MaxSizeReached>;
+        fn is_name(&self, name: &HeaderName) -> bool;
     }
 
     // ==== impls ====
 
     impl Sealed for HeaderName {
         #[inline]
-        fn try_insert<T>(
+        fn try_insert<T, S: BuildHasher>(
             self,
-            map: &mut HeaderMap<T>,
+            map: &mut HeaderMap<T, S>,
             val: T,
         ) -> Result<Option<T>, MaxSizeReached> {
             map.try_insert2(self, val)
         }
 
         #[inline]
-        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T) -> Result<bool, MaxSizeReached> {
+        fn try_append<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+            val: T,
+        ) -> Result<bool, MaxSizeReached> {
             map.try_append2(self, val)
         }
 
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, MaxSizeReached> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, MaxSizeReached> {
             map.try_entry2(self)
         }
+
+        #[inline]
+        fn is_name(&self, name: &HeaderName) -> bool {
+            *name == *self
+        }
     }
 
     impl IntoHeaderName for HeaderName {}
 
-    impl<'a> Sealed for &'a HeaderName {
+    impl Sealed for &HeaderName {
         #[inline]
-        fn try_insert<T>(
+        fn try_insert<T, S: BuildHasher>(
             self,
-            map: &mut HeaderMap<T>,
+            map: &mut HeaderMap<T, S>,
             val: T,
         ) -> Result<Option<T>, MaxSizeReached> {
             map.try_insert2(self, val)
         }
         #[inline]
-        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T) -> Result<bool, MaxSizeReached> {
+        fn try_append<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+            val: T,
+        ) -> Result<bool, MaxSizeReached> {
             map.try_append2(self, val)
         }
 
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, MaxSizeReached> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, MaxSizeReached> {
             map.try_entry2(self)
         }
+
+        #[inline]
+        fn is_name(&self, name: &HeaderName) -> bool {
+            name == *self
+        }
     }
 
-    impl<'a> IntoHeaderName for &'a HeaderName {}
+    impl IntoHeaderName for &HeaderName {}
 
     impl Sealed for &'static str {
         #[inline]
-        fn try_insert<T>(
+        fn try_insert<T, S: BuildHasher>(
             self,
-            map: &mut HeaderMap<T>,
+            map: &mut HeaderMap<T, S>,
             val: T,
         ) -> Result<Option<T>, MaxSizeReached> {
             HdrName::from_static(self, move |hdr| map.try_insert2(hdr, val))
         }
         #[inline]
-        fn try_append<T>(self, map: &mut HeaderMap<T>, val: T) -> Result<bool, MaxSizeReached> {
+        fn try_append<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+            val: T,
+        ) -> Result<bool, MaxSizeReached> {
             HdrName::from_static(self, move |hdr| map.try_append2(hdr, val))
         }
 
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, MaxSizeReached> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, MaxSizeReached> {
             HdrName::from_static(self, move |hdr| map.try_entry2(hdr))
         }
+
+        #[inline]
+        fn is_name(&self, name: &HeaderName) -> bool {
+            name.as_str() == *self
+        }
     }
 
     impl IntoHeaderName for &'static str {}
@@ -3736,6 +5297,7 @@ mod into_header_name {
 
 mod as_header_name {
     use super::{Entry, HdrName, HeaderMap, HeaderName, InvalidHeaderName, MaxSizeReached};
+    use std::hash::BuildHasher;
 
     /// A marker trait used to identify values that can be used as search keys
     /// to a `HeaderMap`.
@@ -3770,10 +5332,13 @@ mod as_header_name {
     // without breaking any external crate.
     pub trait Sealed {
         #[doc(hidden)]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError>;
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError>;
 
         #[doc(hidden)]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)>;
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)>;
 
         #[doc(hidden)]
         fn as_str(&self) -> &str;
@@ -3783,12 +5348,15 @@ mod as_header_name {
 
     impl Sealed for HeaderName {
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
             Ok(map.try_entry2(self)?)
         }
 
         #[inline]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)> {
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
             map.find(self)
         }
 
@@ -3799,14 +5367,17 @@ mod as_header_name {
 
     impl AsHeaderName for HeaderName {}
 
-    impl<'a> Sealed for &'a HeaderName {
+    impl Sealed for &HeaderName {
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
             Ok(map.try_entry2(self)?)
         }
 
         #[inline]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)> {
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
             map.find(*self)
         }
 
@@ -3815,18 +5386,21 @@ mod as_header_name {
         }
     }
 
-    impl<'a> AsHeaderName for &'a HeaderName {}
+    impl AsHeaderName for &HeaderName {}
 
-    impl<'a> Sealed for &'a str {
+    impl Sealed for &str {
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
             Ok(HdrName::from_bytes(self.as_bytes(), move |hdr| {
                 map.try_entry2(hdr)
             })??)
         }
 
         #[inline]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)> {
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
             HdrName::from_bytes(self.as_bytes(), move |hdr| map.find(&hdr)).unwrap_or(None)
         }
 
@@ -3835,16 +5409,19 @@ mod as_header_name {
         }
     }
 
-    impl<'a> AsHeaderName for &'a str {}
+    impl AsHeaderName for &str {}
 
     impl Sealed for String {
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
             self.as_str().try_entry(map)
         }
 
         #[inline]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)> {
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
             Sealed::find(&self.as_str(), map)
         }
 
@@ -3855,14 +5432,17 @@ mod as_header_name {
 
     impl AsHeaderName for String {}
 
-    impl<'a> Sealed for &'a String {
+    impl Sealed for &String {
         #[inline]
-        fn try_entry<T>(self, map: &mut HeaderMap<T>) -> Result<Entry<'_, T>, TryEntryError> {
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
             self.as_str().try_entry(map)
         }
 
         #[inline]
-        fn find<T>(&self, map: &HeaderMap<T>) -> Option<(usize, usize)> {
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
             Sealed::find(*self, map)
         }
 
@@ -3871,7 +5451,36 @@ mod as_header_name {
         }
     }
 
-    impl<'a> AsHeaderName for &'a String {}
+    impl AsHeaderName for &String {}
+
+    // Lets parsers look up names straight from their input, without first
+    // checking that it is UTF-8.
+    //
+    // There is no impl for `&[u8; N]`, since const generics are newer than
+    // the minimum supported Rust version; byte string literals must be
+    // sliced. Nor is there an `IntoHeaderName` counterpart: `insert` and
+    // `append` have no way to report an invalid name, so byte input should
+    // go through `HeaderName::from_bytes` first.
+    impl Sealed for &[u8] {
+        #[inline]
+        fn try_entry<T, S: BuildHasher>(
+            self,
+            map: &mut HeaderMap<T, S>,
+        ) -> Result<Entry<'_, T, S>, TryEntryError> {
+            Ok(HdrName::from_bytes(self, move |hdr| map.try_entry2(hdr))??)
+        }
+
+        #[inline]
+        fn find<T, S: BuildHasher>(&self, map: &HeaderMap<T, S>) -> Option<(usize, usize)> {
+            HdrName::from_bytes(self, move |hdr| map.find(&hdr)).unwrap_or(None)
+        }
+
+        fn as_str(&self) -> &str {
+            std::str::from_utf8(self).unwrap_or("<invalid header name>")
+        }
+    }
+
+    impl AsHeaderName for &[u8] {}
 }
 
 #[test]
@@ -3901,3 +5510,17 @@ fn skip_duplicates_during_key_iteration() {
     map.try_append("a", HeaderValue::from_static("b")).unwrap();
     assert_eq!(map.keys().count(), map.keys_len());
 }
+
+#[test]
+fn clear_keeps_keyed_hash_only_for_secure_maps() {
+    let mut map = HeaderMap::new_secure();
+    map.insert("a", HeaderValue::from_static("1"));
+    map.clear();
+    assert!(map.danger.is_secure());
+
+    let mut map = HeaderMap::new();
+    map.insert("a", HeaderValue::from_static("1"));
+    map.danger = Danger::Red(RandomState::new());
+    map.clear();
+    assert!(!map.danger.is_red());
+}
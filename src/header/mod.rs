@@ -70,16 +70,38 @@
 //! [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
 //! [Robin Hood hashing]: https://en.wikipedia.org/wiki/Hash_table#Robin_Hood_hashing
 
+mod content_coding;
+mod cookie;
+mod date;
+mod list;
 mod map;
 mod name;
+mod order;
+mod parse;
+mod quality;
+mod server_timing;
+pub mod sfv;
+pub mod typed;
 mod value;
 
+pub use self::content_coding::ContentCoding;
+pub use self::cookie::{Cookies, SetCookie, SetCookies};
+pub use self::date::InvalidHttpDate;
+pub use self::list::SplitList;
+#[cfg(feature = "serde1")]
+pub use self::map::CollapsedHeaders;
 pub use self::map::{
-    AsHeaderName, Drain, Entry, GetAll, HeaderMap, IntoHeaderName, IntoIter, Iter, IterMut, Keys,
-    MaxSizeReached, OccupiedEntry, VacantEntry, ValueDrain, ValueIter, ValueIterMut, Values,
-    ValuesMut,
+    AsHeaderName, DefaultHashBuilder, DefaultHasher, Drain, Entry, GetAll, HeaderMap,
+    IntoHeaderName, IntoIter, Iter, IterMut, Keys, MaxSizeReached, OccupiedEntry, Trailers,
+    Unredacted, VacantEntry, ValueDrain, ValueIter, ValueIterMut, Values, ValuesMut,
 };
-pub use self::name::{HeaderName, InvalidHeaderName};
+pub(crate) use self::map::{Redacted, CREDENTIALS, REDACTED};
+pub use self::name::{HeaderName, InvalidHeaderName, NameValidation};
+pub use self::order::Profile;
+pub use self::parse::InvalidHeaderBlock;
+pub(crate) use self::parse::{parse_request_head, parse_response_head};
+pub use self::quality::QualityItem;
+pub use self::server_timing::{InvalidServerTiming, ServerTiming};
 pub use self::value::{HeaderValue, InvalidHeaderValue, ToStrError};
 
 // Use header name constants
@@ -116,6 +138,9 @@ pub use self::name::{
     CONTENT_SECURITY_POLICY_REPORT_ONLY,
     CONTENT_TYPE,
     COOKIE,
+    CROSS_ORIGIN_EMBEDDER_POLICY,
+    CROSS_ORIGIN_OPENER_POLICY,
+    CROSS_ORIGIN_RESOURCE_POLICY,
     DNT,
     DATE,
     ETAG,
@@ -134,6 +159,7 @@ pub use self::name::{
     LOCATION,
     MAX_FORWARDS,
     ORIGIN,
+    PERMISSIONS_POLICY,
     PRAGMA,
     PROXY_AUTHENTICATE,
     PROXY_AUTHORIZATION,
@@ -144,12 +170,17 @@ pub use self::name::{
     REFERRER_POLICY,
     REFRESH,
     RETRY_AFTER,
+    SEC_FETCH_DEST,
+    SEC_FETCH_MODE,
+    SEC_FETCH_SITE,
+    SEC_FETCH_USER,
     SEC_WEBSOCKET_ACCEPT,
     SEC_WEBSOCKET_EXTENSIONS,
     SEC_WEBSOCKET_KEY,
     SEC_WEBSOCKET_PROTOCOL,
     SEC_WEBSOCKET_VERSION,
     SERVER,
+    SERVER_TIMING,
     SET_COOKIE,
     STRICT_TRANSPORT_SECURITY,
     TE,
@@ -164,7 +195,11 @@ pub use self::name::{
     WWW_AUTHENTICATE,
     X_CONTENT_TYPE_OPTIONS,
     X_DNS_PREFETCH_CONTROL,
+    X_FORWARDED_FOR,
+    X_FORWARDED_HOST,
+    X_FORWARDED_PROTO,
     X_FRAME_OPTIONS,
+    X_REAL_IP,
     X_XSS_PROTECTION,
 };
 
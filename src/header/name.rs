@@ -62,11 +62,27 @@ pub struct InvalidHeaderName {
     _priv: (),
 }
 
+/// How strictly `HeaderName::from_bytes_with` validates a header name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NameValidation {
+    /// Only accept names made of `token` characters, as defined in RFC 9110.
+    ///
+    /// This is what `HeaderName::from_bytes` does.
+    Strict,
+    /// Also accept any other visible ASCII character except `:`.
+    ///
+    /// Some legacy clients send names containing characters such as `/` or
+    /// `@`, which are not valid in a token. Names accepted this way can
+    /// still be used as `HeaderMap` keys, but can only be looked up through
+    /// their `HeaderName`, not a string.
+    Lenient,
+}
+
 macro_rules! standard_headers {
     (
         $(
             $(#[$docs:meta])*
-            ($konst:ident, $upcase:ident, $name_bytes:literal);
+            ($konst:ident, $upcase:ident, $name_bytes:literal, $hpack:expr, $qpack:expr);
         )+
     ) => {
         #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -102,6 +118,26 @@ macro_rules! standard_headers {
                     _ => None,
                 }
             }
+
+            // Index of the first entry with this name in the HPACK static
+            // table (RFC 7541, Appendix A). Indices start at 1.
+            fn hpack_static_index(&self) -> Option<usize> {
+                match *self {
+                    $(
+                    StandardHeader::$konst => $hpack,
+                    )+
+                }
+            }
+
+            // Index of the first entry with this name in the QPACK static
+            // table (RFC 9204, Appendix A). Indices start at 0.
+            fn qpack_static_index(&self) -> Option<usize> {
+                match *self {
+                    $(
+                    StandardHeader::$konst => $qpack,
+                    )+
+                }
+            }
         }
 
         #[cfg(test)]
@@ -153,6 +189,9 @@ macro_rules! standard_headers {
 // strings are always converted to the static values (when they match) before
 // being hashed. This means that it is impossible to compare the static hash
 // code of CONTENT_LENGTH with "content-length".
+//
+// The last two columns are the indices of each header in the HPACK and QPACK
+// static tables, if it has one.
 standard_headers! {
     /// Advertises which content types the client is able to understand.
     ///
@@ -164,7 +203,7 @@ standard_headers! {
     /// where the request is done: when fetching a CSS stylesheet a different
     /// value is set for the request than when fetching an image, video or a
     /// script.
-    (Accept, ACCEPT, b"accept");
+    (Accept, ACCEPT, b"accept", Some(19), Some(29));
 
     /// Advertises which character set the client is able to understand.
     ///
@@ -179,7 +218,7 @@ standard_headers! {
     /// theoretically send back a 406 (Not Acceptable) error code. But, for a
     /// better user experience, this is rarely done and the more common way is
     /// to ignore the Accept-Charset header in this case.
-    (AcceptCharset, ACCEPT_CHARSET, b"accept-charset");
+    (AcceptCharset, ACCEPT_CHARSET, b"accept-charset", Some(15), None);
 
     /// Advertises which content encoding the client is able to understand.
     ///
@@ -207,7 +246,7 @@ standard_headers! {
     /// forbidden, by an identity;q=0 or a *;q=0 without another explicitly set
     /// value for identity, the server must never send back a 406 Not Acceptable
     /// error.
-    (AcceptEncoding, ACCEPT_ENCODING, b"accept-encoding");
+    (AcceptEncoding, ACCEPT_ENCODING, b"accept-encoding", Some(16), Some(31));
 
     /// Advertises which languages the client is able to understand.
     ///
@@ -232,7 +271,7 @@ standard_headers! {
     /// send back a 406 (Not Acceptable) error code. But, for a better user
     /// experience, this is rarely done and more common way is to ignore the
     /// Accept-Language header in this case.
-    (AcceptLanguage, ACCEPT_LANGUAGE, b"accept-language");
+    (AcceptLanguage, ACCEPT_LANGUAGE, b"accept-language", Some(17), Some(72));
 
     /// Marker used by the server to advertise partial request support.
     ///
@@ -242,7 +281,7 @@ standard_headers! {
     ///
     /// In presence of an Accept-Ranges header, the browser may try to resume an
     /// interrupted download, rather than to start it from the start again.
-    (AcceptRanges, ACCEPT_RANGES, b"accept-ranges");
+    (AcceptRanges, ACCEPT_RANGES, b"accept-ranges", Some(18), Some(32));
 
     /// Preflight response indicating if the response to the request can be
     /// exposed to the page.
@@ -267,7 +306,7 @@ standard_headers! {
     /// be set on both sides (the Access-Control-Allow-Credentials header and in
     /// the XHR or Fetch request) in order for the CORS request with credentials
     /// to succeed.
-    (AccessControlAllowCredentials, ACCESS_CONTROL_ALLOW_CREDENTIALS, b"access-control-allow-credentials");
+    (AccessControlAllowCredentials, ACCESS_CONTROL_ALLOW_CREDENTIALS, b"access-control-allow-credentials", None, Some(73));
 
     /// Preflight response indicating permitted HTTP headers.
     ///
@@ -283,33 +322,33 @@ standard_headers! {
     ///
     /// This header is required if the request has an
     /// Access-Control-Request-Headers header.
-    (AccessControlAllowHeaders, ACCESS_CONTROL_ALLOW_HEADERS, b"access-control-allow-headers");
+    (AccessControlAllowHeaders, ACCESS_CONTROL_ALLOW_HEADERS, b"access-control-allow-headers", None, Some(33));
 
     /// Preflight header response indicating permitted access methods.
     ///
     /// The Access-Control-Allow-Methods response header specifies the method or
     /// methods allowed when accessing the resource in response to a preflight
     /// request.
-    (AccessControlAllowMethods, ACCESS_CONTROL_ALLOW_METHODS, b"access-control-allow-methods");
+    (AccessControlAllowMethods, ACCESS_CONTROL_ALLOW_METHODS, b"access-control-allow-methods", None, Some(76));
 
     /// Indicates whether the response can be shared with resources with the
     /// given origin.
-    (AccessControlAllowOrigin, ACCESS_CONTROL_ALLOW_ORIGIN, b"access-control-allow-origin");
+    (AccessControlAllowOrigin, ACCESS_CONTROL_ALLOW_ORIGIN, b"access-control-allow-origin", Some(20), Some(35));
 
     /// Indicates which headers can be exposed as part of the response by
     /// listing their names.
-    (AccessControlExposeHeaders, ACCESS_CONTROL_EXPOSE_HEADERS, b"access-control-expose-headers");
+    (AccessControlExposeHeaders, ACCESS_CONTROL_EXPOSE_HEADERS, b"access-control-expose-headers", None, Some(79));
 
     /// Indicates how long the results of a preflight request can be cached.
-    (AccessControlMaxAge, ACCESS_CONTROL_MAX_AGE, b"access-control-max-age");
+    (AccessControlMaxAge, ACCESS_CONTROL_MAX_AGE, b"access-control-max-age", None, None);
 
     /// Informs the server which HTTP headers will be used when an actual
     /// request is made.
-    (AccessControlRequestHeaders, ACCESS_CONTROL_REQUEST_HEADERS, b"access-control-request-headers");
+    (AccessControlRequestHeaders, ACCESS_CONTROL_REQUEST_HEADERS, b"access-control-request-headers", None, Some(80));
 
     /// Informs the server know which HTTP method will be used when the actual
     /// request is made.
-    (AccessControlRequestMethod, ACCESS_CONTROL_REQUEST_METHOD, b"access-control-request-method");
+    (AccessControlRequestMethod, ACCESS_CONTROL_REQUEST_METHOD, b"access-control-request-method", None, Some(81));
 
     /// Indicates the time in seconds the object has been in a proxy cache.
     ///
@@ -317,7 +356,7 @@ standard_headers! {
     /// probably just fetched from the origin server; otherwise It is usually
     /// calculated as a difference between the proxy's current date and the Date
     /// general header included in the HTTP response.
-    (Age, AGE, b"age");
+    (Age, AGE, b"age", Some(21), Some(2));
 
     /// Lists the set of methods support by a resource.
     ///
@@ -326,16 +365,16 @@ standard_headers! {
     /// empty Allow header indicates that the resource allows no request
     /// methods, which might occur temporarily for a given resource, for
     /// example.
-    (Allow, ALLOW, b"allow");
+    (Allow, ALLOW, b"allow", Some(22), None);
 
     /// Advertises the availability of alternate services to clients.
-    (AltSvc, ALT_SVC, b"alt-svc");
+    (AltSvc, ALT_SVC, b"alt-svc", None, Some(83));
 
     /// Contains the credentials to authenticate a user agent with a server.
     ///
     /// Usually this header is included after the server has responded with a
     /// 401 Unauthorized status and the WWW-Authenticate header.
-    (Authorization, AUTHORIZATION, b"authorization");
+    (Authorization, AUTHORIZATION, b"authorization", Some(23), Some(84));
 
     /// Specifies directives for caching mechanisms in both requests and
     /// responses.
@@ -343,19 +382,19 @@ standard_headers! {
     /// Caching directives are unidirectional, meaning that a given directive in
     /// a request is not implying that the same directive is to be given in the
     /// response.
-    (CacheControl, CACHE_CONTROL, b"cache-control");
+    (CacheControl, CACHE_CONTROL, b"cache-control", Some(24), Some(36));
 
     /// Indicates how caches have handled a response and its corresponding request.
     ///
     /// See [RFC 9211](https://www.rfc-editor.org/rfc/rfc9211.html).
-    (CacheStatus, CACHE_STATUS, b"cache-status");
+    (CacheStatus, CACHE_STATUS, b"cache-status", None, None);
 
     /// Specifies directives that allow origin servers to control the behavior of CDN caches
     /// interposed between them and clients separately from other caches that might handle the
     /// response.
     ///
     /// See [RFC 9213](https://www.rfc-editor.org/rfc/rfc9213.html).
-    (CdnCacheControl, CDN_CACHE_CONTROL, b"cdn-cache-control");
+    (CdnCacheControl, CDN_CACHE_CONTROL, b"cdn-cache-control", None, None);
 
     /// Controls whether or not the network connection stays open after the
     /// current transaction finishes.
@@ -370,7 +409,7 @@ standard_headers! {
     /// to consume them and not to forward them further. Standard hop-by-hop
     /// headers can be listed too (it is often the case of Keep-Alive, but this
     /// is not mandatory.
-    (Connection, CONNECTION, b"connection");
+    (Connection, CONNECTION, b"connection", None, None);
 
     /// Indicates if the content is expected to be displayed inline.
     ///
@@ -390,7 +429,7 @@ standard_headers! {
     /// to HTTP forms and POST requests. Only the value form-data, as well as
     /// the optional directive name and filename, can be used in the HTTP
     /// context.
-    (ContentDisposition, CONTENT_DISPOSITION, b"content-disposition");
+    (ContentDisposition, CONTENT_DISPOSITION, b"content-disposition", Some(25), Some(3));
 
     /// Used to compress the media-type.
     ///
@@ -402,7 +441,7 @@ standard_headers! {
     /// use this field, but some types of resources, like jpeg images, are
     /// already compressed.  Sometimes using additional compression doesn't
     /// reduce payload size and can even make the payload longer.
-    (ContentEncoding, CONTENT_ENCODING, b"content-encoding");
+    (ContentEncoding, CONTENT_ENCODING, b"content-encoding", Some(26), Some(42));
 
     /// Used to describe the languages intended for the audience.
     ///
@@ -417,13 +456,13 @@ standard_headers! {
     /// intended for all language audiences. Multiple language tags are also
     /// possible, as well as applying the Content-Language header to various
     /// media types and not only to textual documents.
-    (ContentLanguage, CONTENT_LANGUAGE, b"content-language");
+    (ContentLanguage, CONTENT_LANGUAGE, b"content-language", Some(27), None);
 
     /// Indicates the size of the entity-body.
     ///
     /// The header value must be a decimal indicating the number of octets sent
     /// to the recipient.
-    (ContentLength, CONTENT_LENGTH, b"content-length");
+    (ContentLength, CONTENT_LENGTH, b"content-length", Some(28), Some(4));
 
     /// Indicates an alternate location for the returned data.
     ///
@@ -436,10 +475,10 @@ standard_headers! {
     /// without the need of further content negotiation. Location is a header
     /// associated with the response, while Content-Location is associated with
     /// the entity returned.
-    (ContentLocation, CONTENT_LOCATION, b"content-location");
+    (ContentLocation, CONTENT_LOCATION, b"content-location", Some(29), None);
 
     /// Indicates where in a full body message a partial message belongs.
-    (ContentRange, CONTENT_RANGE, b"content-range");
+    (ContentRange, CONTENT_RANGE, b"content-range", Some(30), None);
 
     /// Allows controlling resources the user agent is allowed to load for a
     /// given page.
@@ -447,7 +486,7 @@ standard_headers! {
     /// With a few exceptions, policies mostly involve specifying server origins
     /// and script endpoints. This helps guard against cross-site scripting
     /// attacks (XSS).
-    (ContentSecurityPolicy, CONTENT_SECURITY_POLICY, b"content-security-policy");
+    (ContentSecurityPolicy, CONTENT_SECURITY_POLICY, b"content-security-policy", None, Some(85));
 
     /// Allows experimenting with policies by monitoring their effects.
     ///
@@ -455,7 +494,7 @@ standard_headers! {
     /// developers to experiment with policies by monitoring (but not enforcing)
     /// their effects. These violation reports consist of JSON documents sent
     /// via an HTTP POST request to the specified URI.
-    (ContentSecurityPolicyReportOnly, CONTENT_SECURITY_POLICY_REPORT_ONLY, b"content-security-policy-report-only");
+    (ContentSecurityPolicyReportOnly, CONTENT_SECURITY_POLICY_REPORT_ONLY, b"content-security-policy-report-only", None, None);
 
     /// Used to indicate the media type of the resource.
     ///
@@ -467,23 +506,50 @@ standard_headers! {
     ///
     /// In requests, (such as POST or PUT), the client tells the server what
     /// type of data is actually sent.
-    (ContentType, CONTENT_TYPE, b"content-type");
+    (ContentType, CONTENT_TYPE, b"content-type", Some(31), Some(44));
 
     /// Contains stored HTTP cookies previously sent by the server with the
     /// Set-Cookie header.
     ///
     /// The Cookie header might be omitted entirely, if the privacy setting of
     /// the browser are set to block them, for example.
-    (Cookie, COOKIE, b"cookie");
+    (Cookie, COOKIE, b"cookie", Some(32), Some(5));
+
+    /// Controls which cross-origin resources a document may load.
+    ///
+    /// With `require-corp`, a document can only load cross-origin resources
+    /// that explicitly allow it, through CORS or
+    /// `cross-origin-resource-policy`. Together with
+    /// `cross-origin-opener-policy`, this makes a document cross-origin
+    /// isolated, which browsers require for features such as
+    /// `SharedArrayBuffer`.
+    (CrossOriginEmbedderPolicy, CROSS_ORIGIN_EMBEDDER_POLICY, b"cross-origin-embedder-policy", None, None);
+
+    /// Controls whether a document shares a browsing context group with
+    /// cross-origin documents.
+    ///
+    /// With `same-origin`, cross-origin documents opened from this one, or
+    /// that open it, are placed in a separate browsing context group and
+    /// cannot reach it through `window.opener`. This protects against
+    /// cross-origin attacks such as XS-Leaks.
+    (CrossOriginOpenerPolicy, CROSS_ORIGIN_OPENER_POLICY, b"cross-origin-opener-policy", None, None);
+
+    /// Controls which origins may embed a resource.
+    ///
+    /// Browsers block no-cors requests for the resource from origins that the
+    /// policy (`same-site`, `same-origin` or `cross-origin`) does not allow.
+    /// This protects against speculative side-channel attacks such as
+    /// Spectre.
+    (CrossOriginResourcePolicy, CROSS_ORIGIN_RESOURCE_POLICY, b"cross-origin-resource-policy", None, None);
 
     /// Indicates the client's tracking preference.
     ///
     /// This header lets users indicate whether they would prefer privacy rather
     /// than personalized content.
-    (Dnt, DNT, b"dnt");
+    (Dnt, DNT, b"dnt", None, None);
 
     /// Contains the date and time at which the message was originated.
-    (Date, DATE, b"date");
+    (Date, DATE, b"date", Some(33), Some(6));
 
     /// Identifier for a specific version of a resource.
     ///
@@ -499,7 +565,7 @@ standard_headers! {
     /// to quickly determine whether two representations of a resource are the
     /// same, but they might also be set to persist indefinitely by a tracking
     /// server.
-    (Etag, ETAG, b"etag");
+    (Etag, ETAG, b"etag", Some(34), Some(7));
 
     /// Indicates expectations that need to be fulfilled by the server in order
     /// to properly handle the request.
@@ -518,7 +584,7 @@ standard_headers! {
     ///
     /// No common browsers send the Expect header, but some other clients such
     /// as cURL do so by default.
-    (Expect, EXPECT, b"expect");
+    (Expect, EXPECT, b"expect", Some(35), None);
 
     /// Contains the date/time after which the response is considered stale.
     ///
@@ -527,7 +593,7 @@ standard_headers! {
     ///
     /// If there is a Cache-Control header with the "max-age" or "s-max-age"
     /// directive in the response, the Expires header is ignored.
-    (Expires, EXPIRES, b"expires");
+    (Expires, EXPIRES, b"expires", Some(36), None);
 
     /// Contains information from the client-facing side of proxy servers that
     /// is altered or lost when a proxy is involved in the path of the request.
@@ -539,7 +605,7 @@ standard_headers! {
     /// location-dependent content and by design it exposes privacy sensitive
     /// information, such as the IP address of the client. Therefore the user's
     /// privacy must be kept in mind when deploying this header.
-    (Forwarded, FORWARDED, b"forwarded");
+    (Forwarded, FORWARDED, b"forwarded", None, Some(88));
 
     /// Contains an Internet email address for a human user who controls the
     /// requesting user agent.
@@ -548,7 +614,7 @@ standard_headers! {
     /// header should be sent, so you can be contacted if problems occur on
     /// servers, such as if the robot is sending excessive, unwanted, or invalid
     /// requests.
-    (From, FROM, b"from");
+    (From, FROM, b"from", Some(37), None);
 
     /// Specifies the domain name of the server and (optionally) the TCP port
     /// number on which the server is listening.
@@ -559,7 +625,7 @@ standard_headers! {
     /// A Host header field must be sent in all HTTP/1.1 request messages. A 400
     /// (Bad Request) status code will be sent to any HTTP/1.1 request message
     /// that lacks a Host header field or contains more than one.
-    (Host, HOST, b"host");
+    (Host, HOST, b"host", Some(38), None);
 
     /// Makes a request conditional based on the E-Tag.
     ///
@@ -584,7 +650,7 @@ standard_headers! {
     /// that has been done since the original resource was fetched. If the
     /// request cannot be fulfilled, the 412 (Precondition Failed) response is
     /// returned.
-    (IfMatch, IF_MATCH, b"if-match");
+    (IfMatch, IF_MATCH, b"if-match", Some(39), None);
 
     /// Makes a request conditional based on the modification date.
     ///
@@ -601,7 +667,7 @@ standard_headers! {
     ///
     /// The most common use case is to update a cached entity that has no
     /// associated ETag.
-    (IfModifiedSince, IF_MODIFIED_SINCE, b"if-modified-since");
+    (IfModifiedSince, IF_MODIFIED_SINCE, b"if-modified-since", Some(40), Some(8));
 
     /// Makes a request conditional based on the E-Tag.
     ///
@@ -637,7 +703,7 @@ standard_headers! {
     /// guaranteeing that another upload didn't happen before, losing the data
     /// of the previous put; this problems is the variation of the lost update
     /// problem.
-    (IfNoneMatch, IF_NONE_MATCH, b"if-none-match");
+    (IfNoneMatch, IF_NONE_MATCH, b"if-none-match", Some(41), Some(9));
 
     /// Makes a request conditional based on range.
     ///
@@ -653,7 +719,7 @@ standard_headers! {
     /// The most common use case is to resume a download, to guarantee that the
     /// stored resource has not been modified since the last fragment has been
     /// received.
-    (IfRange, IF_RANGE, b"if-range");
+    (IfRange, IF_RANGE, b"if-range", Some(42), Some(89));
 
     /// Makes the request conditional based on the last modification date.
     ///
@@ -674,17 +740,17 @@ standard_headers! {
     /// * In conjunction with a range request with a If-Range header, it can be
     /// used to ensure that the new fragment requested comes from an unmodified
     /// document.
-    (IfUnmodifiedSince, IF_UNMODIFIED_SINCE, b"if-unmodified-since");
+    (IfUnmodifiedSince, IF_UNMODIFIED_SINCE, b"if-unmodified-since", Some(43), None);
 
     /// The Last-Modified header contains the date and time when the origin believes
     /// the resource was last modified.
     ///
     /// The value is a valid Date/Time string defined in [RFC9910](https://datatracker.ietf.org/doc/html/rfc9110#section-5.6.7)
-    (LastModified, LAST_MODIFIED, b"last-modified");
+    (LastModified, LAST_MODIFIED, b"last-modified", Some(44), Some(10));
 
     /// Allows the server to point an interested client to another resource
     /// containing metadata about the requested resource.
-    (Link, LINK, b"link");
+    (Link, LINK, b"link", Some(45), Some(11));
 
     /// Indicates the URL to redirect a page to.
     ///
@@ -715,11 +781,11 @@ standard_headers! {
     /// when content negotiation happened, without the need of further content
     /// negotiation. Location is a header associated with the response, while
     /// Content-Location is associated with the entity returned.
-    (Location, LOCATION, b"location");
+    (Location, LOCATION, b"location", Some(46), Some(12));
 
     /// Indicates the max number of intermediaries the request should be sent
     /// through.
-    (MaxForwards, MAX_FORWARDS, b"max-forwards");
+    (MaxForwards, MAX_FORWARDS, b"max-forwards", Some(47), None);
 
     /// Indicates where a fetch originates from.
     ///
@@ -727,7 +793,15 @@ standard_headers! {
     /// sent with CORS requests, as well as with POST requests. It is similar to
     /// the Referer header, but, unlike this header, it doesn't disclose the
     /// whole path.
-    (Origin, ORIGIN, b"origin");
+    (Origin, ORIGIN, b"origin", None, Some(90));
+
+    /// Controls which browser features a document and its frames may use.
+    ///
+    /// The value is a structured field dictionary mapping features, such as
+    /// `camera` or `geolocation`, to the origins allowed to use them. For
+    /// example, `geolocation=()` disables geolocation entirely. This header
+    /// replaces `feature-policy`.
+    (PermissionsPolicy, PERMISSIONS_POLICY, b"permissions-policy", None, None);
 
     /// HTTP/1.0 header usually used for backwards compatibility.
     ///
@@ -735,7 +809,7 @@ standard_headers! {
     /// that may have various effects along the request-response chain. It is
     /// used for backwards compatibility with HTTP/1.0 caches where the
     /// Cache-Control HTTP/1.1 header is not yet present.
-    (Pragma, PRAGMA, b"pragma");
+    (Pragma, PRAGMA, b"pragma", None, None);
 
     /// Defines the authentication method that should be used to gain access to
     /// a proxy.
@@ -753,14 +827,14 @@ standard_headers! {
     ///
     /// The `proxy-authenticate` header is sent along with a `407 Proxy
     /// Authentication Required`.
-    (ProxyAuthenticate, PROXY_AUTHENTICATE, b"proxy-authenticate");
+    (ProxyAuthenticate, PROXY_AUTHENTICATE, b"proxy-authenticate", Some(48), None);
 
     /// Contains the credentials to authenticate a user agent to a proxy server.
     ///
     /// This header is usually included after the server has responded with a
     /// 407 Proxy Authentication Required status and the Proxy-Authenticate
     /// header.
-    (ProxyAuthorization, PROXY_AUTHORIZATION, b"proxy-authorization");
+    (ProxyAuthorization, PROXY_AUTHORIZATION, b"proxy-authorization", Some(49), None);
 
     /// Associates a specific cryptographic public key with a certain server.
     ///
@@ -768,14 +842,14 @@ standard_headers! {
     /// or several keys are pinned and none of them are used by the server, the
     /// browser will not accept the response as legitimate, and will not display
     /// it.
-    (PublicKeyPins, PUBLIC_KEY_PINS, b"public-key-pins");
+    (PublicKeyPins, PUBLIC_KEY_PINS, b"public-key-pins", None, None);
 
     /// Sends reports of pinning violation to the report-uri specified in the
     /// header.
     ///
     /// Unlike `Public-Key-Pins`, this header still allows browsers to connect
     /// to the server if the pinning is violated.
-    (PublicKeyPinsReportOnly, PUBLIC_KEY_PINS_REPORT_ONLY, b"public-key-pins-report-only");
+    (PublicKeyPinsReportOnly, PUBLIC_KEY_PINS_REPORT_ONLY, b"public-key-pins-report-only", None, None);
 
     /// Indicates the part of a document that the server should return.
     ///
@@ -785,7 +859,7 @@ standard_headers! {
     /// the ranges are invalid, the server returns the 416 Range Not Satisfiable
     /// error. The server can also ignore the Range header and return the whole
     /// document with a 200 status code.
-    (Range, RANGE, b"range");
+    (Range, RANGE, b"range", Some(50), Some(54));
 
     /// Contains the address of the previous web page from which a link to the
     /// currently requested page was followed.
@@ -793,15 +867,15 @@ standard_headers! {
     /// The Referer header allows servers to identify where people are visiting
     /// them from and may use that data for analytics, logging, or optimized
     /// caching, for example.
-    (Referer, REFERER, b"referer");
+    (Referer, REFERER, b"referer", Some(51), Some(13));
 
     /// Governs which referrer information should be included with requests
     /// made.
-    (ReferrerPolicy, REFERRER_POLICY, b"referrer-policy");
+    (ReferrerPolicy, REFERRER_POLICY, b"referrer-policy", None, None);
 
     /// Informs the web browser that the current page or frame should be
     /// refreshed.
-    (Refresh, REFRESH, b"refresh");
+    (Refresh, REFRESH, b"refresh", Some(52), None);
 
     /// The Retry-After response HTTP header indicates how long the user agent
     /// should wait before making a follow-up request. There are two main cases
@@ -813,20 +887,51 @@ standard_headers! {
     /// * When sent with a redirect response, such as 301 (Moved Permanently),
     /// it indicates the minimum time that the user agent is asked to wait
     /// before issuing the redirected request.
-    (RetryAfter, RETRY_AFTER, b"retry-after");
+    (RetryAfter, RETRY_AFTER, b"retry-after", Some(53), None);
+
+    /// Indicates the request's destination, that is, how the fetched data
+    /// will be used.
+    ///
+    /// This is a fetch metadata request header, set by browsers. Values
+    /// include `document`, `image`, `script` and `empty` for `fetch()`
+    /// calls. Servers can use it to reject requests whose destination does
+    /// not match the resource, such as a script loading a JSON endpoint.
+    (SecFetchDest, SEC_FETCH_DEST, b"sec-fetch-dest", None, None);
+
+    /// Indicates the mode of the request.
+    ///
+    /// This is a fetch metadata request header, set by browsers. Values
+    /// include `navigate`, `same-origin`, `no-cors` and `cors`. Servers can
+    /// use it to tell navigations apart from other requests.
+    (SecFetchMode, SEC_FETCH_MODE, b"sec-fetch-mode", None, None);
+
+    /// Indicates the relationship between the request's initiator and its
+    /// target.
+    ///
+    /// This is a fetch metadata request header, set by browsers. The value is
+    /// one of `same-origin`, `same-site`, `cross-site` or `none`, the last
+    /// being for user-initiated navigations. Resource isolation policies
+    /// commonly reject `cross-site` requests that are not navigations.
+    (SecFetchSite, SEC_FETCH_SITE, b"sec-fetch-site", None, None);
+
+    /// Indicates whether a navigation was triggered by user activation.
+    ///
+    /// This is a fetch metadata request header, set by browsers. It is only
+    /// sent for navigations, and then only with the value `?1`.
+    (SecFetchUser, SEC_FETCH_USER, b"sec-fetch-user", None, None);
 
     /// The |Sec-WebSocket-Accept| header field is used in the WebSocket
     /// opening handshake. It is sent from the server to the client to
     /// confirm that the server is willing to initiate the WebSocket
     /// connection.
-    (SecWebSocketAccept, SEC_WEBSOCKET_ACCEPT, b"sec-websocket-accept");
+    (SecWebSocketAccept, SEC_WEBSOCKET_ACCEPT, b"sec-websocket-accept", None, None);
 
     /// The |Sec-WebSocket-Extensions| header field is used in the WebSocket
     /// opening handshake. It is initially sent from the client to the
     /// server, and then subsequently sent from the server to the client, to
     /// agree on a set of protocol-level extensions to use for the duration
     /// of the connection.
-    (SecWebSocketExtensions, SEC_WEBSOCKET_EXTENSIONS, b"sec-websocket-extensions");
+    (SecWebSocketExtensions, SEC_WEBSOCKET_EXTENSIONS, b"sec-websocket-extensions", None, None);
 
     /// The |Sec-WebSocket-Key| header field is used in the WebSocket opening
     /// handshake. It is sent from the client to the server to provide part
@@ -835,14 +940,14 @@ standard_headers! {
     /// does not accept connections from non-WebSocket clients (e.g., HTTP
     /// clients) that are being abused to send data to unsuspecting WebSocket
     /// servers.
-    (SecWebSocketKey, SEC_WEBSOCKET_KEY, b"sec-websocket-key");
+    (SecWebSocketKey, SEC_WEBSOCKET_KEY, b"sec-websocket-key", None, None);
 
     /// The |Sec-WebSocket-Protocol| header field is used in the WebSocket
     /// opening handshake. It is sent from the client to the server and back
     /// from the server to the client to confirm the subprotocol of the
     /// connection.  This enables scripts to both select a subprotocol and be
     /// sure that the server agreed to serve that subprotocol.
-    (SecWebSocketProtocol, SEC_WEBSOCKET_PROTOCOL, b"sec-websocket-protocol");
+    (SecWebSocketProtocol, SEC_WEBSOCKET_PROTOCOL, b"sec-websocket-protocol", None, None);
 
     /// The |Sec-WebSocket-Version| header field is used in the WebSocket
     /// opening handshake.  It is sent from the client to the server to
@@ -850,7 +955,7 @@ standard_headers! {
     /// servers to correctly interpret the opening handshake and subsequent
     /// data being sent from the data, and close the connection if the server
     /// cannot interpret that data in a safe manner.
-    (SecWebSocketVersion, SEC_WEBSOCKET_VERSION, b"sec-websocket-version");
+    (SecWebSocketVersion, SEC_WEBSOCKET_VERSION, b"sec-websocket-version", None, None);
 
     /// Contains information about the software used by the origin server to
     /// handle the request.
@@ -859,13 +964,21 @@ standard_headers! {
     /// potentially reveal internal implementation details that might make it
     /// (slightly) easier for attackers to find and exploit known security
     /// holes.
-    (Server, SERVER, b"server");
+    (Server, SERVER, b"server", Some(54), Some(92));
+
+    /// Communicates one or more metrics and descriptions for the given
+    /// request-response cycle.
+    ///
+    /// The metrics are surfaced in a user agent's developer tools and can be
+    /// used to report server side timings, such as database query or cache
+    /// lookup times.
+    (ServerTiming, SERVER_TIMING, b"server-timing", None, None);
 
     /// Used to send cookies from the server to the user agent.
-    (SetCookie, SET_COOKIE, b"set-cookie");
+    (SetCookie, SET_COOKIE, b"set-cookie", Some(55), Some(14));
 
     /// Tells the client to communicate with HTTPS instead of using HTTP.
-    (StrictTransportSecurity, STRICT_TRANSPORT_SECURITY, b"strict-transport-security");
+    (StrictTransportSecurity, STRICT_TRANSPORT_SECURITY, b"strict-transport-security", Some(56), Some(55));
 
     /// Informs the server of transfer encodings willing to be accepted as part
     /// of the response.
@@ -875,11 +988,11 @@ standard_headers! {
     /// recipients and you that don't have to specify "chunked" using the TE
     /// header. However, it is useful for setting if the client is accepting
     /// trailer fields in a chunked transfer coding using the "trailers" value.
-    (Te, TE, b"te");
+    (Te, TE, b"te", None, None);
 
     /// Allows the sender to include additional fields at the end of chunked
     /// messages.
-    (Trailer, TRAILER, b"trailer");
+    (Trailer, TRAILER, b"trailer", None, None);
 
     /// Specifies the form of encoding used to safely transfer the entity to the
     /// client.
@@ -893,18 +1006,18 @@ standard_headers! {
     /// When present on a response to a `HEAD` request that has no body, it
     /// indicates the value that would have applied to the corresponding `GET`
     /// message.
-    (TransferEncoding, TRANSFER_ENCODING, b"transfer-encoding");
+    (TransferEncoding, TRANSFER_ENCODING, b"transfer-encoding", Some(57), None);
 
     /// Contains a string that allows identifying the requesting client's
     /// software.
-    (UserAgent, USER_AGENT, b"user-agent");
+    (UserAgent, USER_AGENT, b"user-agent", Some(58), Some(95));
 
     /// Used as part of the exchange to upgrade the protocol.
-    (Upgrade, UPGRADE, b"upgrade");
+    (Upgrade, UPGRADE, b"upgrade", None, None);
 
     /// Sends a signal to the server expressing the client’s preference for an
     /// encrypted and authenticated response.
-    (UpgradeInsecureRequests, UPGRADE_INSECURE_REQUESTS, b"upgrade-insecure-requests");
+    (UpgradeInsecureRequests, UPGRADE_INSECURE_REQUESTS, b"upgrade-insecure-requests", None, Some(94));
 
     /// Determines how to match future requests with cached responses.
     ///
@@ -916,7 +1029,7 @@ standard_headers! {
     ///
     /// The `vary` header should be set on a 304 Not Modified response exactly
     /// like it would have been set on an equivalent 200 OK response.
-    (Vary, VARY, b"vary");
+    (Vary, VARY, b"vary", Some(59), Some(58));
 
     /// Added by proxies to track routing.
     ///
@@ -925,7 +1038,7 @@ standard_headers! {
     /// It is used for tracking message forwards, avoiding request loops, and
     /// identifying the protocol capabilities of senders along the
     /// request/response chain.
-    (Via, VIA, b"via");
+    (Via, VIA, b"via", Some(60), None);
 
     /// General HTTP header contains information about possible problems with
     /// the status of the message.
@@ -933,11 +1046,11 @@ standard_headers! {
     /// More than one `warning` header may appear in a response. Warning header
     /// fields can in general be applied to any message, however some warn-codes
     /// are specific to caches and can only be applied to response messages.
-    (Warning, WARNING, b"warning");
+    (Warning, WARNING, b"warning", None, None);
 
     /// Defines the authentication method that should be used to gain access to
     /// a resource.
-    (WwwAuthenticate, WWW_AUTHENTICATE, b"www-authenticate");
+    (WwwAuthenticate, WWW_AUTHENTICATE, b"www-authenticate", Some(61), None);
 
     /// Marker used by the server to indicate that the MIME types advertised in
     /// the `content-type` headers should not be changed and be followed.
@@ -952,7 +1065,7 @@ standard_headers! {
     /// less aggressive.
     ///
     /// Site security testers usually expect this header to be set.
-    (XContentTypeOptions, X_CONTENT_TYPE_OPTIONS, b"x-content-type-options");
+    (XContentTypeOptions, X_CONTENT_TYPE_OPTIONS, b"x-content-type-options", None, Some(60));
 
     /// Controls DNS prefetching.
     ///
@@ -965,7 +1078,32 @@ standard_headers! {
     /// This prefetching is performed in the background, so that the DNS is
     /// likely to have been resolved by the time the referenced items are
     /// needed. This reduces latency when the user clicks a link.
-    (XDnsPrefetchControl, X_DNS_PREFETCH_CONTROL, b"x-dns-prefetch-control");
+    (XDnsPrefetchControl, X_DNS_PREFETCH_CONTROL, b"x-dns-prefetch-control", None, None);
+
+    /// Identifies the originating IP addresses of a client connecting through
+    /// proxies.
+    ///
+    /// Each proxy that forwards the request appends the address it received
+    /// the request from, giving a comma-separated list that starts with the
+    /// client. The values are only as trustworthy as the proxies that added
+    /// them. `forwarded` is the standardized replacement for this header.
+    (XForwardedFor, X_FORWARDED_FOR, b"x-forwarded-for", None, Some(96));
+
+    /// Identifies the original host requested by the client in the `host`
+    /// header.
+    ///
+    /// Reverse proxies may rewrite the `host` header when forwarding a
+    /// request. This header preserves the original value. It is superseded by
+    /// the `host` parameter of the `forwarded` header.
+    (XForwardedHost, X_FORWARDED_HOST, b"x-forwarded-host", None, None);
+
+    /// Identifies the protocol, `http` or `https`, the client used to connect
+    /// to a proxy.
+    ///
+    /// Proxies that terminate TLS forward requests over plain HTTP, so the
+    /// server cannot otherwise tell how the client connected. It is
+    /// superseded by the `proto` parameter of the `forwarded` header.
+    (XForwardedProto, X_FORWARDED_PROTO, b"x-forwarded-proto", None, None);
 
     /// Indicates whether or not a browser should be allowed to render a page in
     /// a frame.
@@ -975,7 +1113,13 @@ standard_headers! {
     ///
     /// The added security is only provided if the user accessing the document
     /// is using a browser supporting `x-frame-options`.
-    (XFrameOptions, X_FRAME_OPTIONS, b"x-frame-options");
+    (XFrameOptions, X_FRAME_OPTIONS, b"x-frame-options", None, Some(97));
+
+    /// Identifies the IP address of the client connecting through a proxy.
+    ///
+    /// Unlike `x-forwarded-for`, this holds a single address, set by the
+    /// proxy closest to the client. It is commonly set by nginx.
+    (XRealIp, X_REAL_IP, b"x-real-ip", None, None);
 
     /// Stop pages from loading when an XSS attack is detected.
     ///
@@ -986,7 +1130,7 @@ standard_headers! {
     /// implement a strong Content-Security-Policy that disables the use of
     /// inline JavaScript ('unsafe-inline'), they can still provide protections
     /// for users of older web browsers that don't yet support CSP.
-    (XXssProtection, X_XSS_PROTECTION, b"x-xss-protection");
+    (XXssProtection, X_XSS_PROTECTION, b"x-xss-protection", None, Some(61));
 }
 
 /// Valid header name characters
@@ -1070,6 +1214,11 @@ const HEADER_CHARS_H2: [u8; 256] = [
         0,     0,     0,     0,     0,     0                              // 25x
 ];
 
+/// Returns whether `s` is a non-empty `token`, as defined in RFC 9110.
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| HEADER_CHARS[b as usize] != 0)
+}
+
 fn parse_hdr<'a>(
     data: &'a [u8],
     b: &'a mut [MaybeUninit<u8>; SCRATCH_BUF_SIZE],
@@ -1148,6 +1297,56 @@ impl HeaderName {
         }
     }
 
+    /// Converts a slice of bytes to an HTTP header name, with the given
+    /// validation.
+    ///
+    /// With `NameValidation::Strict` this is the same as `from_bytes`. With
+    /// `NameValidation::Lenient`, names containing visible ASCII characters
+    /// outside the `token` grammar are also accepted. In both cases the name
+    /// is normalized to lowercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderName, NameValidation};
+    /// assert!(HeaderName::from_bytes_with(b"X-Legacy/Id", NameValidation::Strict).is_err());
+    ///
+    /// let name = HeaderName::from_bytes_with(b"X-Legacy/Id", NameValidation::Lenient).unwrap();
+    /// assert_eq!(name, "x-legacy/id");
+    /// ```
+    pub fn from_bytes_with(
+        src: &[u8],
+        validation: NameValidation,
+    ) -> Result<HeaderName, InvalidHeaderName> {
+        let err = match HeaderName::from_bytes(src) {
+            Ok(name) => return Ok(name),
+            Err(err) => err,
+        };
+
+        if validation == NameValidation::Strict
+            || src.is_empty()
+            || src.len() > super::MAX_HEADER_NAME_LEN
+        {
+            return Err(err);
+        }
+
+        use bytes::BufMut;
+        let mut dst = BytesMut::with_capacity(src.len());
+        for &b in src {
+            match b {
+                b':' => return Err(err),
+                0x21..=0x7e => dst.put_u8(b.to_ascii_lowercase()),
+                _ => return Err(err),
+            }
+        }
+
+        // Safety: the loop above only copies visible ASCII bytes into dst,
+        // which are valid UTF-8. Standard headers are all tokens, so
+        // `from_bytes` would have returned one.
+        let val = unsafe { ByteStr::from_utf8_unchecked(dst.freeze()) };
+        Ok(Custom(val).into())
+    }
+
     /// Converts a slice of bytes to an HTTP header name.
     ///
     /// This function expects the input to only contain lowercase characters.
@@ -1195,6 +1394,49 @@ impl HeaderName {
         }
     }
 
+    /// Converts a slice of bytes to an HTTP header name without validating.
+    ///
+    /// This skips the per-byte table lookup done by `from_lowercase`, for
+    /// decoders whose input is already known to be a valid lowercase name,
+    /// such as names taken from an HPACK or QPACK table this process built.
+    /// Standard headers are still recognized.
+    ///
+    /// ## Panics
+    /// In a debug build this will panic if `src` is not a valid lowercase
+    /// header name.
+    ///
+    /// ## Safety
+    /// `src` must be a valid lowercase header name, as accepted by
+    /// `from_lowercase`. In a release build it is undefined behaviour to
+    /// call this with `src` that is not valid UTF-8, and other invalid input
+    /// produces a `HeaderName` that breaks this crate's invariants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::*;
+    /// let hdr = unsafe { HeaderName::from_lowercase_unchecked(b"content-length") };
+    /// assert_eq!(CONTENT_LENGTH, hdr);
+    /// ```
+    pub unsafe fn from_lowercase_unchecked(src: &[u8]) -> HeaderName {
+        if cfg!(debug_assertions) {
+            match HeaderName::from_lowercase(src) {
+                Ok(name) => name,
+                Err(_err) => {
+                    panic!("HeaderName::from_lowercase_unchecked() with invalid bytes");
+                }
+            }
+        } else {
+            match StandardHeader::from_bytes(src) {
+                Some(std) => std.into(),
+                None => {
+                    let val = ByteStr::from_utf8_unchecked(Bytes::copy_from_slice(src));
+                    Custom(val).into()
+                }
+            }
+        }
+    }
+
     /// Converts a static string to a HTTP header name.
     ///
     /// This function requires the static string to only contain lowercase
@@ -1242,6 +1484,20 @@ impl HeaderName {
     /// assert_eq!(a, b);
     /// ```
     ///
+    /// Since this function is `const`, applications can define constants for
+    /// the custom headers they use often, just like the standard ones. Such a
+    /// name borrows the static string, so creating, cloning and inserting it
+    /// never allocates.
+    ///
+    /// ```
+    /// # use http::header::*;
+    /// pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.insert(X_REQUEST_ID, "f058ebd6".parse().unwrap());
+    /// assert_eq!(map["x-request-id"], "f058ebd6");
+    /// ```
+    ///
     /// ```should_panic
     /// # use http::header::*;
     /// #
@@ -1286,6 +1542,44 @@ impl HeaderName {
         }
     }
 
+    /// Creates an HTTP/2 or HTTP/3 pseudo-header name.
+    ///
+    /// Pseudo-headers carry the request and response control data in HTTP/2
+    /// and HTTP/3 header blocks. They are not valid header names otherwise,
+    /// so `from_bytes` and friends reject them, and they can only be created
+    /// through this constructor. The accepted names are `:method`,
+    /// `:scheme`, `:authority`, `:path`, `:status` and `:protocol`.
+    ///
+    /// For the same reason, a pseudo-header in a `HeaderMap` must be looked
+    /// up with its `HeaderName` rather than a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderName;
+    /// let method = HeaderName::from_pseudo(":method").unwrap();
+    /// assert!(method.is_pseudo());
+    /// assert_eq!(method.as_str(), ":method");
+    ///
+    /// assert!(HeaderName::from_pseudo(":foo").is_err());
+    /// assert!(HeaderName::from_bytes(b":method").is_err());
+    /// ```
+    pub fn from_pseudo(src: &str) -> Result<HeaderName, InvalidHeaderName> {
+        const PSEUDO: &[&str] = &[
+            ":method",
+            ":scheme",
+            ":authority",
+            ":path",
+            ":status",
+            ":protocol",
+        ];
+
+        match PSEUDO.iter().find(|&&name| name == src) {
+            Some(name) => Ok(Custom(ByteStr::from_static(name)).into()),
+            None => Err(InvalidHeaderName::new()),
+        }
+    }
+
     /// Returns a `str` representation of the header.
     ///
     /// The returned string will always be lower case.
@@ -1297,6 +1591,60 @@ impl HeaderName {
         }
     }
 
+    /// Returns `true` if this is a pseudo-header name created with
+    /// `from_pseudo`.
+    #[inline]
+    pub fn is_pseudo(&self) -> bool {
+        match self.inner {
+            Repr::Standard(_) => false,
+            Repr::Custom(ref v) => v.0.starts_with(':'),
+        }
+    }
+
+    /// Returns the index of this name in the HPACK static table, if it has
+    /// one.
+    ///
+    /// The index is that of the first table entry with this name, as used
+    /// for a literal with an indexed name (RFC 7541, Appendix A). Indices
+    /// start at 1. Pseudo-headers and custom names have no index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{self, HeaderName};
+    /// assert_eq!(header::ACCEPT_ENCODING.hpack_static_index(), Some(16));
+    /// assert_eq!(header::CONNECTION.hpack_static_index(), None);
+    /// assert_eq!(HeaderName::from_static("x-custom").hpack_static_index(), None);
+    /// ```
+    pub fn hpack_static_index(&self) -> Option<usize> {
+        match self.inner {
+            Repr::Standard(v) => v.hpack_static_index(),
+            Repr::Custom(_) => None,
+        }
+    }
+
+    /// Returns the index of this name in the QPACK static table, if it has
+    /// one.
+    ///
+    /// The index is that of the first table entry with this name (RFC 9204,
+    /// Appendix A). Indices start at 0. Pseudo-headers and custom names have
+    /// no index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{self, HeaderName};
+    /// assert_eq!(header::CONTENT_TYPE.qpack_static_index(), Some(44));
+    /// assert_eq!(header::HOST.qpack_static_index(), None);
+    /// assert_eq!(HeaderName::from_static("x-custom").qpack_static_index(), None);
+    /// ```
+    pub fn qpack_static_index(&self) -> Option<usize> {
+        match self.inner {
+            Repr::Standard(v) => v.qpack_static_index(),
+            Repr::Custom(_) => None,
+        }
+    }
+
     pub(super) fn into_bytes(self) -> Bytes {
         self.inner.into()
     }
@@ -1439,7 +1787,7 @@ impl<'a> PartialEq<&'a HeaderName> for HeaderName {
     }
 }
 
-impl<'a> PartialEq<HeaderName> for &'a HeaderName {
+impl PartialEq<HeaderName> for &HeaderName {
     #[inline]
     fn eq(&self, other: &HeaderName) -> bool {
         *other == *self
@@ -1493,7 +1841,7 @@ impl<'a> PartialEq<&'a str> for HeaderName {
     }
 }
 
-impl<'a> PartialEq<HeaderName> for &'a str {
+impl PartialEq<HeaderName> for &str {
     /// Performs a case-insensitive comparison of the string against the header
     /// name
     #[inline]
@@ -1648,7 +1996,7 @@ fn eq_ignore_ascii_case(lower: &[u8], s: &[u8]) -> bool {
     lower
         .iter()
         .zip(s)
-        .all(|(a, b)| *a == HEADER_CHARS[*b as usize])
+        .all(|(a, b)| *a == b.to_ascii_lowercase())
 }
 
 // Utility functions for MaybeUninit<>. These are drawn from unstable API's on
@@ -1671,6 +2019,45 @@ unsafe fn slice_assume_init<T>(slice: &[MaybeUninit<T>]) -> &[T] {
     &*(slice as *const [MaybeUninit<T>] as *const [T])
 }
 
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::HeaderName;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+
+    impl Serialize for HeaderName {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for HeaderName {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(HeaderNameVisitor)
+        }
+    }
+
+    struct HeaderNameVisitor;
+
+    impl<'de> Visitor<'de> for HeaderNameVisitor {
+        type Value = HeaderName;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a header name")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<HeaderName, E> {
+            HeaderName::from_bytes(v.as_bytes())
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<HeaderName, E> {
+            HeaderName::from_bytes(v).map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use self::StandardHeader::Vary;
@@ -1919,4 +2306,78 @@ mod tests {
         HeaderName::from_lowercase(&[0x1; 100]).unwrap_err();
         HeaderName::from_lowercase(&[0xFF; 100]).unwrap_err();
     }
+
+    #[test]
+    fn test_static_table_indices() {
+        // First and last entries of each table that have a regular name.
+        assert_eq!(ACCEPT_CHARSET.hpack_static_index(), Some(15));
+        assert_eq!(WWW_AUTHENTICATE.hpack_static_index(), Some(61));
+        assert_eq!(AGE.qpack_static_index(), Some(2));
+        assert_eq!(X_FRAME_OPTIONS.qpack_static_index(), Some(97));
+
+        // Names with several entries use the first one.
+        assert_eq!(CONTENT_TYPE.qpack_static_index(), Some(44));
+        assert_eq!(ACCEPT_LANGUAGE.qpack_static_index(), Some(72));
+
+        let custom = HeaderName::from_static("x-custom");
+        assert_eq!(custom.hpack_static_index(), None);
+        assert_eq!(custom.qpack_static_index(), None);
+    }
+
+    #[test]
+    fn test_from_lowercase_unchecked() {
+        let std = unsafe { HeaderName::from_lowercase_unchecked(b"content-length") };
+        assert_eq!(std, CONTENT_LENGTH);
+        assert!(matches!(std.inner, Repr::Standard(_)));
+
+        let custom = unsafe { HeaderName::from_lowercase_unchecked(b"x-custom") };
+        assert_eq!(custom, HeaderName::from_static("x-custom"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_from_lowercase_unchecked_invalid() {
+        let _ = unsafe { HeaderName::from_lowercase_unchecked(b"Content-Length") };
+    }
+
+    #[test]
+    fn test_pseudo() {
+        let mut map = crate::HeaderMap::new();
+        let path = HeaderName::from_pseudo(":path").unwrap();
+        map.insert(path.clone(), "/".parse().unwrap());
+        map.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+
+        assert_eq!(map[&path], "/");
+        assert!(path.is_pseudo());
+        assert!(!CONTENT_TYPE.is_pseudo());
+        assert!(!HeaderName::from_static("x-custom").is_pseudo());
+
+        assert!(HeaderName::from_pseudo(":Path").is_err());
+        assert!(HeaderName::from_pseudo("path").is_err());
+        assert!(HeaderName::from_lowercase(b":path").is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_with() {
+        use self::NameValidation::*;
+
+        assert_eq!(
+            HeaderName::from_bytes_with(b"Content-Length", Strict).unwrap(),
+            CONTENT_LENGTH
+        );
+        assert_eq!(
+            HeaderName::from_bytes_with(b"Content-Length", Lenient).unwrap(),
+            CONTENT_LENGTH
+        );
+
+        HeaderName::from_bytes_with(b"x-a/b", Strict).unwrap_err();
+        let name = HeaderName::from_bytes_with(b"X-A/B@(c)", Lenient).unwrap();
+        assert_eq!(name.as_str(), "x-a/b@(c)");
+        assert_eq!(name, "X-A/B@(c)");
+
+        for bad in [&b""[..], b"a b", b"a:b", b"a\x7f", b"caf\xc3\xa9"].iter() {
+            HeaderName::from_bytes_with(bad, Lenient).unwrap_err();
+        }
+    }
 }
@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+
+use super::HeaderName;
+
+/// A preferred order of header names.
+///
+/// Passed to [`HeaderMap::reorder`](super::HeaderMap::reorder), which sorts
+/// the headers named in the profile into the given order, and moves all other
+/// headers after them.
+///
+/// The predefined profiles approximate the order in which common clients send
+/// their request headers over HTTP/1.1. They are useful for testing servers
+/// and proxies that are sensitive to header order, but do not promise to
+/// match any particular client version.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderMap;
+/// # use http::header::{Profile, ACCEPT, HOST, USER_AGENT};
+/// let mut map = HeaderMap::new();
+/// map.insert(ACCEPT, "*/*".parse().unwrap());
+/// map.insert("x-trace", "1".parse().unwrap());
+/// map.insert(USER_AGENT, "curl/8.0".parse().unwrap());
+/// map.insert(HOST, "example.com".parse().unwrap());
+///
+/// map.reorder(Profile::CURL);
+///
+/// let names: Vec<_> = map.keys().map(|k| k.as_str()).collect();
+/// assert_eq!(names, ["host", "user-agent", "accept", "x-trace"]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Profile {
+    names: &'static [&'static str],
+}
+
+impl Profile {
+    /// The order used by Chromium based browsers for navigations.
+    pub const CHROME: Profile = Profile::new(&[
+        "host",
+        "connection",
+        "cache-control",
+        "sec-ch-ua",
+        "sec-ch-ua-mobile",
+        "sec-ch-ua-platform",
+        "upgrade-insecure-requests",
+        "user-agent",
+        "accept",
+        "sec-fetch-site",
+        "sec-fetch-mode",
+        "sec-fetch-user",
+        "sec-fetch-dest",
+        "referer",
+        "accept-encoding",
+        "accept-language",
+        "cookie",
+    ]);
+
+    /// The order used by Firefox for navigations.
+    pub const FIREFOX: Profile = Profile::new(&[
+        "host",
+        "user-agent",
+        "accept",
+        "accept-language",
+        "accept-encoding",
+        "referer",
+        "connection",
+        "cookie",
+        "upgrade-insecure-requests",
+        "sec-fetch-dest",
+        "sec-fetch-mode",
+        "sec-fetch-site",
+        "sec-fetch-user",
+        "priority",
+        "te",
+    ]);
+
+    /// The order used by the `curl` command line tool.
+    pub const CURL: Profile = Profile::new(&["host", "user-agent", "accept"]);
+
+    /// Creates a profile from lowercase header names, in the desired order.
+    pub const fn new(names: &'static [&'static str]) -> Profile {
+        Profile { names }
+    }
+
+    /// Returns the header names of this profile, in order.
+    pub fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    /// Compares two header names by their position in this profile.
+    ///
+    /// Names that are not part of the profile compare equal to each other,
+    /// and greater than all names that are.
+    pub fn compare(&self, a: &HeaderName, b: &HeaderName) -> Ordering {
+        self.rank(a).cmp(&self.rank(b))
+    }
+
+    fn rank(&self, name: &HeaderName) -> usize {
+        self.names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name.as_str()))
+            .unwrap_or(self.names.len())
+    }
+}
@@ -0,0 +1,277 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use super::{HeaderMap, HeaderName, HeaderValue};
+use crate::method::Method;
+use crate::status::{ReasonPhrase, StatusCode};
+use crate::uri::Uri;
+use crate::version::Version;
+
+/// A possible error when parsing an HTTP/1.1 header block, or a message head
+/// made of a start line and a header block.
+///
+/// The error records the byte offset into the block at which parsing failed.
+pub struct InvalidHeaderBlock {
+    kind: Kind,
+    position: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    ObsFold,
+    MissingColon,
+    InvalidName,
+    InvalidValue,
+    BareCarriageReturn,
+    TrailingData,
+    TooManyHeaders,
+    MalformedStartLine,
+    InvalidMethod,
+    InvalidUri,
+    InvalidVersion,
+    InvalidStatus,
+    InvalidReason,
+}
+
+pub(super) fn parse(src: &[u8]) -> Result<HeaderMap, InvalidHeaderBlock> {
+    parse_from(src, 0)
+}
+
+/// Parses a request line and the header block after it.
+pub(crate) fn parse_request_head(
+    src: &[u8],
+) -> Result<(Method, Uri, Version, HeaderMap), InvalidHeaderBlock> {
+    let (line, next) = next_line(src, 0)?;
+    let mut parts = line.splitn(3, |&b| b == b' ');
+    let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) => (method, target, version),
+        _ => return Err(InvalidHeaderBlock::new(Kind::MalformedStartLine, 0)),
+    };
+
+    let method =
+        Method::from_bytes(method).map_err(|_| InvalidHeaderBlock::new(Kind::InvalidMethod, 0))?;
+    let target_at = method.as_str().len() + 1;
+    let uri =
+        Uri::try_from(target).map_err(|_| InvalidHeaderBlock::new(Kind::InvalidUri, target_at))?;
+    let version_at = target_at + target.len() + 1;
+    let version = parse_version(version)
+        .ok_or_else(|| InvalidHeaderBlock::new(Kind::InvalidVersion, version_at))?;
+
+    Ok((method, uri, version, parse_from(src, next)?))
+}
+
+/// Parses a status line and the header block after it.
+pub(crate) fn parse_response_head(
+    src: &[u8],
+) -> Result<(Version, StatusCode, ReasonPhrase, HeaderMap), InvalidHeaderBlock> {
+    let (line, next) = next_line(src, 0)?;
+    let mut parts = line.splitn(3, |&b| b == b' ');
+    let (version, status) = match (parts.next(), parts.next()) {
+        (Some(version), Some(status)) => (version, status),
+        _ => return Err(InvalidHeaderBlock::new(Kind::MalformedStartLine, 0)),
+    };
+    // The reason phrase may be empty, and some servers leave out the space
+    // before it too.
+    let reason = parts.next().unwrap_or(b"");
+
+    let status_at = version.len() + 1;
+    let version =
+        parse_version(version).ok_or_else(|| InvalidHeaderBlock::new(Kind::InvalidVersion, 0))?;
+    let status = StatusCode::from_bytes(status)
+        .map_err(|_| InvalidHeaderBlock::new(Kind::InvalidStatus, status_at))?;
+    let reason_at = status_at + 4;
+    let reason = ReasonPhrase::try_from(reason)
+        .map_err(|_| InvalidHeaderBlock::new(Kind::InvalidReason, reason_at))?;
+
+    Ok((version, status, reason, parse_from(src, next)?))
+}
+
+/// Parses the `HTTP-version` of a start line, which must be HTTP/1.0 or
+/// HTTP/1.1.
+fn parse_version(src: &[u8]) -> Option<Version> {
+    match src {
+        b"HTTP/1.1" => Some(Version::HTTP_11),
+        b"HTTP/1.0" => Some(Version::HTTP_10),
+        _ => None,
+    }
+}
+
+fn parse_from(src: &[u8], mut pos: usize) -> Result<HeaderMap, InvalidHeaderBlock> {
+    let mut map = HeaderMap::new();
+
+    while pos < src.len() {
+        let (line, next) = next_line(src, pos)?;
+
+        if line.is_empty() {
+            // The empty line ends the block.
+            if next != src.len() {
+                return Err(InvalidHeaderBlock::new(Kind::TrailingData, next));
+            }
+            break;
+        }
+
+        if line[0] == b' ' || line[0] == b'\t' {
+            return Err(InvalidHeaderBlock::new(Kind::ObsFold, pos));
+        }
+
+        let colon = match line.iter().position(|&b| b == b':') {
+            Some(colon) => colon,
+            None => return Err(InvalidHeaderBlock::new(Kind::MissingColon, pos)),
+        };
+
+        // Whitespace between the name and the colon is not allowed, which
+        // `HeaderName` already rejects.
+        let name = HeaderName::from_bytes(&line[..colon])
+            .map_err(|_| InvalidHeaderBlock::new(Kind::InvalidName, pos))?;
+
+        let mut start = colon + 1;
+        let mut end = line.len();
+        while start < end && is_ows(line[start]) {
+            start += 1;
+        }
+        while end > start && is_ows(line[end - 1]) {
+            end -= 1;
+        }
+        let value = HeaderValue::from_bytes(&line[start..end])
+            .map_err(|_| InvalidHeaderBlock::new(Kind::InvalidValue, pos + start))?;
+
+        map.try_append(name, value)
+            .map_err(|_| InvalidHeaderBlock::new(Kind::TooManyHeaders, pos))?;
+
+        pos = next;
+    }
+
+    Ok(map)
+}
+
+/// Returns the line starting at `pos`, without its terminator, and the
+/// position of the following line.
+///
+/// Lines end with CRLF, or a bare LF, which RFC 9112 allows recipients to
+/// accept. The last line may also end at the end of the input.
+fn next_line(src: &[u8], pos: usize) -> Result<(&[u8], usize), InvalidHeaderBlock> {
+    let rest = &src[pos..];
+
+    for (i, &b) in rest.iter().enumerate() {
+        match b {
+            b'\n' => return Ok((&rest[..i], pos + i + 1)),
+            b'\r' => {
+                if rest.get(i + 1) == Some(&b'\n') {
+                    return Ok((&rest[..i], pos + i + 2));
+                }
+                return Err(InvalidHeaderBlock::new(Kind::BareCarriageReturn, pos + i));
+            }
+            _ => {}
+        }
+    }
+
+    Ok((rest, src.len()))
+}
+
+fn is_ows(b: u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+impl InvalidHeaderBlock {
+    fn new(kind: Kind, position: usize) -> InvalidHeaderBlock {
+        InvalidHeaderBlock { kind, position }
+    }
+
+    /// Returns the byte offset into the block at which parsing failed.
+    ///
+    /// This is the start of the offending line, of the value for an invalid
+    /// value, or of the offending part of a start line.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn description(&self) -> &str {
+        match self.kind {
+            Kind::ObsFold => "obsolete line folding",
+            Kind::MissingColon => "header line missing colon",
+            Kind::InvalidName => "invalid header name",
+            Kind::InvalidValue => "invalid header value",
+            Kind::BareCarriageReturn => "carriage return without line feed",
+            Kind::TrailingData => "data after end of header block",
+            Kind::TooManyHeaders => "too many headers",
+            Kind::MalformedStartLine => "malformed start line",
+            Kind::InvalidMethod => "invalid method",
+            Kind::InvalidUri => "invalid request target",
+            Kind::InvalidVersion => "invalid HTTP version",
+            Kind::InvalidStatus => "invalid status code",
+            Kind::InvalidReason => "invalid reason phrase",
+        }
+    }
+}
+
+impl fmt::Debug for InvalidHeaderBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidHeaderBlock")
+            .field("kind", &self.kind)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidHeaderBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.description(), self.position)
+    }
+}
+
+impl Error for InvalidHeaderBlock {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err_at(src: &[u8]) -> (String, usize) {
+        let err = parse(src).unwrap_err();
+        (err.description().to_owned(), err.position())
+    }
+
+    #[test]
+    fn parses_block() {
+        let map = parse(b"Host: example.com\r\nAccept:text/html \r\naccept: */*\r\n\r\n").unwrap();
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["host"], "example.com");
+        let accept: Vec<_> = map.get_all("accept").iter().collect();
+        assert_eq!(accept, ["text/html", "*/*"]);
+
+        assert!(parse(b"").unwrap().is_empty());
+        assert!(parse(b"\r\n").unwrap().is_empty());
+        assert_eq!(parse(b"a: 1\nb: 2").unwrap().len(), 2);
+        assert_eq!(parse(b"empty:\r\n").unwrap()["empty"], "");
+    }
+
+    #[test]
+    fn positioned_errors() {
+        assert_eq!(
+            err_at(b"a: 1\r\n b: 2\r\n"),
+            ("obsolete line folding".to_owned(), 6)
+        );
+        assert_eq!(
+            err_at(b"a: 1\r\nnope\r\n"),
+            ("header line missing colon".to_owned(), 6)
+        );
+        assert_eq!(
+            err_at(b"bad name: 1\r\n"),
+            ("invalid header name".to_owned(), 0)
+        );
+        assert_eq!(err_at(b"a : 1\r\n"), ("invalid header name".to_owned(), 0));
+        assert_eq!(
+            err_at(b"a: 1\r\nb: x\x7fy\r\n"),
+            ("invalid header value".to_owned(), 9)
+        );
+        assert_eq!(
+            err_at(b"a: 1\rb: 2\r\n"),
+            ("carriage return without line feed".to_owned(), 4)
+        );
+        assert_eq!(
+            err_at(b"a: 1\r\n\r\nbody"),
+            ("data after end of header block".to_owned(), 8)
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Reverse;
+
+use super::value::{HeaderValue, ToStrError};
+
+/// An element of a weighted list, such as `Accept-Encoding`.
+///
+/// Returned by [`HeaderValue::quality_items`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QualityItem<'a> {
+    item: &'a str,
+    quality: u16,
+}
+
+impl<'a> QualityItem<'a> {
+    /// Returns the element without its `q` parameter.
+    ///
+    /// Any parameters before `q`, such as those of a media type, are kept.
+    /// Extension parameters after `q` are dropped.
+    pub fn item(&self) -> &'a str {
+        self.item
+    }
+
+    /// Returns the weight of the element, in thousandths.
+    ///
+    /// Elements without a `q` parameter have a weight of 1000. A weight of 0
+    /// marks the element as "not acceptable".
+    pub fn quality(&self) -> u16 {
+        self.quality
+    }
+}
+
+impl HeaderValue {
+    /// Parses the value as a weighted list, most preferred first.
+    ///
+    /// Elements are split as with [`split_list`](HeaderValue::split_list),
+    /// and their `q` parameter is parsed as described in RFC 9110. Elements
+    /// of equal weight keep the order they were listed in. Elements with a
+    /// malformed weight are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("gzip;q=0.8, br;q=1.0, identity;q=0, zstd");
+    /// let items: Vec<_> = val
+    ///     .quality_items()
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|i| (i.item(), i.quality()))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     items,
+    ///     [("br", 1000), ("zstd", 1000), ("gzip", 800), ("identity", 0)]
+    /// );
+    /// ```
+    pub fn quality_items(&self) -> Result<Vec<QualityItem<'_>>, ToStrError> {
+        let mut items: Vec<QualityItem<'_>> =
+            self.split_list()?.filter_map(parse_element).collect();
+        // `sort_by_key` is stable, so equal weights keep their listed order.
+        items.sort_by_key(|i| Reverse(i.quality));
+        Ok(items)
+    }
+}
+
+fn parse_element(element: &str) -> Option<QualityItem<'_>> {
+    let bytes = element.as_bytes();
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes {
+            match b {
+                b'\\' => escaped = true,
+                b'"' => in_quotes = false,
+                _ => {}
+            }
+        } else if b == b'"' {
+            in_quotes = true;
+        } else if b == b';' {
+            let param = element[i + 1..].trim_start_matches(|c| c == ' ' || c == '\t');
+            let pb = param.as_bytes();
+            if pb.len() >= 2 && (pb[0] == b'q' || pb[0] == b'Q') && pb[1] == b'=' {
+                let weight = param[2..].split(';').next().unwrap_or("");
+                let item = element[..i].trim_end_matches(|c| c == ' ' || c == '\t');
+                return parse_quality(weight.trim_end_matches(|c| c == ' ' || c == '\t'))
+                    .map(|quality| QualityItem { item, quality });
+            }
+        }
+    }
+
+    Some(QualityItem {
+        item: element,
+        quality: 1000,
+    })
+}
+
+/// Parses a `qvalue`, `0` to `1` with at most three decimal places.
+fn parse_quality(s: &str) -> Option<u16> {
+    let bytes = s.as_bytes();
+    let (int, frac) = match bytes.split_first()? {
+        (&int, []) => (int, &[][..]),
+        (&int, [b'.', frac @ ..]) if frac.len() <= 3 => (int, frac),
+        _ => return None,
+    };
+
+    let mut quality = match int {
+        b'0' => 0,
+        b'1' => 1000,
+        _ => return None,
+    };
+    let mut scale = 100;
+    for &d in frac {
+        if !d.is_ascii_digit() {
+            return None;
+        }
+        quality += u16::from(d - b'0') * scale;
+        scale /= 10;
+    }
+
+    if quality > 1000 {
+        None
+    } else {
+        Some(quality)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(s: &str) -> Vec<(String, u16)> {
+        HeaderValue::from_str(s)
+            .unwrap()
+            .quality_items()
+            .unwrap()
+            .iter()
+            .map(|i| (i.item().to_owned(), i.quality()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_qvalues() {
+        assert_eq!(parse_quality("1"), Some(1000));
+        assert_eq!(parse_quality("1."), Some(1000));
+        assert_eq!(parse_quality("1.000"), Some(1000));
+        assert_eq!(parse_quality("0"), Some(0));
+        assert_eq!(parse_quality("0.5"), Some(500));
+        assert_eq!(parse_quality("0.05"), Some(50));
+        assert_eq!(parse_quality("0.001"), Some(1));
+        assert_eq!(parse_quality("1.001"), None);
+        assert_eq!(parse_quality("0.0001"), None);
+        assert_eq!(parse_quality("2"), None);
+        assert_eq!(parse_quality(".5"), None);
+        assert_eq!(parse_quality(""), None);
+        assert_eq!(parse_quality("0.x"), None);
+    }
+
+    #[test]
+    fn ranks_items() {
+        assert_eq!(
+            ranked("text/html;level=1, text/*;q=0.3, */*; Q=0.1;ext=1"),
+            [
+                ("text/html;level=1".to_owned(), 1000),
+                ("text/*".to_owned(), 300),
+                ("*/*".to_owned(), 100),
+            ]
+        );
+        assert_eq!(
+            ranked("a;q=0.5, b;q=bad, c;q=0.5"),
+            [("a".to_owned(), 500), ("c".to_owned(), 500)]
+        );
+        assert_eq!(
+            ranked(r#"text/plain;fmt="a;q=0", x"#),
+            [
+                (r#"text/plain;fmt="a;q=0""#.to_owned(), 1000),
+                ("x".to_owned(), 1000)
+            ]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use super::name::is_token;
+use super::HeaderValue;
+
+/// A single `Server-Timing` metric.
+///
+/// Metrics are built up from a name, and an optional duration (in
+/// milliseconds) and description. The name must be a token, and the
+/// description is quoted whenever it is not one.
+///
+/// # Examples
+///
+/// ```
+/// # use http::HeaderValue;
+/// # use http::header::ServerTiming;
+/// use std::convert::TryFrom;
+///
+/// let db = ServerTiming::new("db").duration(53.2);
+/// assert_eq!(HeaderValue::try_from(&db).unwrap(), "db;dur=53.2");
+///
+/// let cache = ServerTiming::new("cache").description("hit, warm");
+/// assert_eq!(HeaderValue::try_from(&cache).unwrap(), "cache;desc=\"hit, warm\"");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerTiming {
+    name: String,
+    duration: Option<f64>,
+    description: Option<String>,
+}
+
+/// A possible error when converting a `ServerTiming` to a `HeaderValue`.
+pub struct InvalidServerTiming {
+    _priv: (),
+}
+
+impl ServerTiming {
+    /// Creates a new metric with the given name.
+    pub fn new(name: &str) -> ServerTiming {
+        ServerTiming {
+            name: name.to_owned(),
+            duration: None,
+            description: None,
+        }
+    }
+
+    /// Sets the duration of this metric, in milliseconds.
+    ///
+    /// The duration must be finite and not negative for the metric to be
+    /// converted to a `HeaderValue`.
+    pub fn duration(self, millis: f64) -> ServerTiming {
+        ServerTiming {
+            duration: Some(millis),
+            ..self
+        }
+    }
+
+    /// Sets the human readable description of this metric.
+    pub fn description(self, description: &str) -> ServerTiming {
+        ServerTiming {
+            description: Some(description.to_owned()),
+            ..self
+        }
+    }
+
+    /// Returns the name of this metric.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn encode(&self) -> Result<String, InvalidServerTiming> {
+        if !is_token(&self.name) {
+            return Err(InvalidServerTiming::new());
+        }
+
+        let mut dst = self.name.clone();
+
+        if let Some(dur) = self.duration {
+            if !dur.is_finite() || dur.is_sign_negative() {
+                return Err(InvalidServerTiming::new());
+            }
+            write!(dst, ";dur={}", dur).unwrap();
+        }
+
+        if let Some(ref desc) = self.description {
+            dst.push_str(";desc=");
+            if is_token(desc) {
+                dst.push_str(desc);
+            } else {
+                dst.push('"');
+                for c in desc.chars() {
+                    match c {
+                        '"' | '\\' => {
+                            dst.push('\\');
+                            dst.push(c);
+                        }
+                        '\t' | ' '..='~' => dst.push(c),
+                        _ => return Err(InvalidServerTiming::new()),
+                    }
+                }
+                dst.push('"');
+            }
+        }
+
+        Ok(dst)
+    }
+}
+
+impl<'a> TryFrom<&'a ServerTiming> for HeaderValue {
+    type Error = InvalidServerTiming;
+
+    fn try_from(metric: &'a ServerTiming) -> Result<Self, Self::Error> {
+        let encoded = metric.encode()?;
+        HeaderValue::from_maybe_shared(encoded).map_err(|_| InvalidServerTiming::new())
+    }
+}
+
+impl TryFrom<ServerTiming> for HeaderValue {
+    type Error = InvalidServerTiming;
+
+    fn try_from(metric: ServerTiming) -> Result<Self, Self::Error> {
+        HeaderValue::try_from(&metric)
+    }
+}
+
+impl InvalidServerTiming {
+    fn new() -> InvalidServerTiming {
+        InvalidServerTiming { _priv: () }
+    }
+}
+
+impl fmt::Debug for InvalidServerTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidServerTiming")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidServerTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Server-Timing metric")
+    }
+}
+
+impl Error for InvalidServerTiming {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(metric: ServerTiming) -> Result<HeaderValue, InvalidServerTiming> {
+        HeaderValue::try_from(metric)
+    }
+
+    #[test]
+    fn name_only() {
+        assert_eq!(encode(ServerTiming::new("miss")).unwrap(), "miss");
+    }
+
+    #[test]
+    fn duration_and_token_description() {
+        let metric = ServerTiming::new("db").duration(53.2).description("hit");
+        assert_eq!(encode(metric).unwrap(), "db;dur=53.2;desc=hit");
+        assert_eq!(
+            encode(ServerTiming::new("app").duration(47.0)).unwrap(),
+            "app;dur=47"
+        );
+    }
+
+    #[test]
+    fn quotes_and_escapes_description() {
+        let metric = ServerTiming::new("cdn").description("edge \"fra\\1\"");
+        assert_eq!(
+            encode(metric).unwrap(),
+            "cdn;desc=\"edge \\\"fra\\\\1\\\"\""
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_metrics() {
+        assert!(encode(ServerTiming::new("")).is_err());
+        assert!(encode(ServerTiming::new("db query")).is_err());
+        assert!(encode(ServerTiming::new("db").duration(f64::NAN)).is_err());
+        assert!(encode(ServerTiming::new("db").duration(-1.5)).is_err());
+        assert!(encode(ServerTiming::new("db").duration(-0.0)).is_err());
+        assert!(encode(ServerTiming::new("db").description("a\nb")).is_err());
+        assert!(encode(ServerTiming::new("db").description("caf\u{e9}")).is_err());
+    }
+}
@@ -0,0 +1,972 @@
+//! Structured Field Values, as defined in
+//! [RFC 8941](https://datatracker.ietf.org/doc/html/rfc8941).
+//!
+//! Many newer headers, such as `Priority`, `Cache-Status` and the Client
+//! Hints, are defined as structured fields: an [`Item`], a [`List`] or a
+//! [`Dictionary`], built from a small set of [`BareItem`] types that may
+//! carry [`Parameters`].
+//!
+//! Each of the three field types can be parsed from a `HeaderValue` and
+//! converted back into one with `HeaderValue::try_from`. A field split over
+//! several lines can be parsed by first combining the lines with
+//! [`HeaderValue::join`](super::HeaderValue::join).
+//!
+//! # Examples
+//!
+//! ```
+//! use http::header::sfv::{BareItem, Dictionary};
+//! use http::header::HeaderValue;
+//! use std::convert::TryFrom;
+//!
+//! let priority = HeaderValue::from_static("u=1, i");
+//! let dict = Dictionary::parse(&priority).unwrap();
+//!
+//! assert_eq!(dict.get("u").unwrap().item().unwrap().bare_item, BareItem::Integer(1));
+//! assert_eq!(dict.get("i").unwrap().item().unwrap().bare_item, BareItem::Boolean(true));
+//! assert_eq!(HeaderValue::try_from(&dict).unwrap(), "u=1, i");
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use super::value::HeaderValue;
+
+/// The value of an [`Item`], without its parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BareItem {
+    /// An integer of at most 15 digits.
+    Integer(i64),
+    /// A decimal with at most 12 integer and 3 fractional digits.
+    Decimal(Decimal),
+    /// A string of printable ASCII characters.
+    String(String),
+    /// A token, such as `text/html` or `*`.
+    Token(String),
+    /// Arbitrary bytes, written as base64.
+    ByteSequence(Vec<u8>),
+    /// A boolean, written as `?1` or `?0`.
+    Boolean(bool),
+}
+
+/// An exact decimal number, stored in thousandths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(i64);
+
+/// A [`BareItem`] with [`Parameters`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The value of the item.
+    pub bare_item: BareItem,
+    /// The parameters of the item.
+    pub params: Parameters,
+}
+
+/// A parenthesized list of [`Item`]s, which may itself have parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InnerList {
+    /// The members of the list.
+    pub items: Vec<Item>,
+    /// The parameters of the list.
+    pub params: Parameters,
+}
+
+/// A member of a [`List`] or a [`Dictionary`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ListEntry {
+    /// A single item.
+    Item(Item),
+    /// An inner list of items.
+    InnerList(InnerList),
+}
+
+/// Ordered key-value pairs attached to an [`Item`] or [`InnerList`].
+///
+/// Keys are unique; when parsing, a repeated key replaces the earlier value
+/// in its original position.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Parameters(pub Vec<(String, BareItem)>);
+
+/// A structured field that is a list of members.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct List(pub Vec<ListEntry>);
+
+/// A structured field that is an ordered map from keys to members.
+///
+/// Keys are unique; when parsing, a repeated key replaces the earlier value
+/// in its original position.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dictionary(pub Vec<(String, ListEntry)>);
+
+/// A possible error when parsing or serializing a structured field.
+pub struct InvalidStructuredField {
+    _priv: (),
+}
+
+const MAX_INTEGER: i64 = 999_999_999_999_999;
+
+// ===== impl Decimal =====
+
+impl Decimal {
+    /// Creates a decimal from a number of thousandths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::sfv::Decimal;
+    /// assert_eq!(Decimal::from_thousandths(-1250).to_string(), "-1.25");
+    /// ```
+    pub fn from_thousandths(thousandths: i64) -> Decimal {
+        Decimal(thousandths)
+    }
+
+    /// Returns the value in thousandths.
+    pub fn thousandths(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the value as a floating point number.
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let abs = i128::from(self.0).abs();
+        if self.0 < 0 {
+            f.write_char('-')?;
+        }
+        write!(f, "{}.", abs / 1000)?;
+        let frac = format!("{:03}", abs % 1000);
+        let frac = frac.trim_end_matches('0');
+        f.write_str(if frac.is_empty() { "0" } else { frac })
+    }
+}
+
+// ===== impl Item / InnerList / ListEntry =====
+
+impl Item {
+    /// Creates an item without parameters.
+    pub fn new(bare_item: BareItem) -> Item {
+        Item {
+            bare_item,
+            params: Parameters::default(),
+        }
+    }
+
+    /// Parses a field value as a single item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// # use http::header::sfv::{BareItem, Item};
+    /// let item = Item::parse(&HeaderValue::from_static("\"hit\"; ttl=30")).unwrap();
+    ///
+    /// assert_eq!(item.bare_item, BareItem::String("hit".into()));
+    /// assert_eq!(item.params.get("ttl"), Some(&BareItem::Integer(30)));
+    /// ```
+    pub fn parse(value: &HeaderValue) -> Result<Item, InvalidStructuredField> {
+        Parser::parse_field(value, Parser::parse_item)
+    }
+}
+
+impl From<BareItem> for Item {
+    fn from(bare_item: BareItem) -> Item {
+        Item::new(bare_item)
+    }
+}
+
+impl ListEntry {
+    /// Returns the item, if this member is not an inner list.
+    pub fn item(&self) -> Option<&Item> {
+        match *self {
+            ListEntry::Item(ref item) => Some(item),
+            ListEntry::InnerList(_) => None,
+        }
+    }
+
+    /// Returns the inner list, if this member is one.
+    pub fn inner_list(&self) -> Option<&InnerList> {
+        match *self {
+            ListEntry::Item(_) => None,
+            ListEntry::InnerList(ref list) => Some(list),
+        }
+    }
+
+    /// Returns the parameters of this member.
+    pub fn params(&self) -> &Parameters {
+        match *self {
+            ListEntry::Item(ref item) => &item.params,
+            ListEntry::InnerList(ref list) => &list.params,
+        }
+    }
+}
+
+impl From<Item> for ListEntry {
+    fn from(item: Item) -> ListEntry {
+        ListEntry::Item(item)
+    }
+}
+
+impl From<InnerList> for ListEntry {
+    fn from(list: InnerList) -> ListEntry {
+        ListEntry::InnerList(list)
+    }
+}
+
+// ===== impl Parameters / List / Dictionary =====
+
+impl Parameters {
+    /// Returns the value of the parameter named `key`.
+    pub fn get(&self, key: &str) -> Option<&BareItem> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    fn insert(&mut self, key: String, value: BareItem) {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.0.push((key, value)),
+        }
+    }
+}
+
+impl List {
+    /// Parses a field value as a list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// # use http::header::sfv::{BareItem, List};
+    /// let list = List::parse(&HeaderValue::from_static("sugar, tea, (rum milk)")).unwrap();
+    ///
+    /// assert_eq!(list.0.len(), 3);
+    /// assert_eq!(list.0[2].inner_list().unwrap().items.len(), 2);
+    /// ```
+    pub fn parse(value: &HeaderValue) -> Result<List, InvalidStructuredField> {
+        Parser::parse_field(value, Parser::parse_list)
+    }
+}
+
+impl Dictionary {
+    /// Parses a field value as a dictionary.
+    pub fn parse(value: &HeaderValue) -> Result<Dictionary, InvalidStructuredField> {
+        Parser::parse_field(value, Parser::parse_dictionary)
+    }
+
+    /// Returns the member named `key`.
+    pub fn get(&self, key: &str) -> Option<&ListEntry> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+// ===== Parsing =====
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_field<T>(
+        value: &'a HeaderValue,
+        parse: fn(&mut Parser<'a>) -> Result<T, InvalidStructuredField>,
+    ) -> Result<T, InvalidStructuredField> {
+        let mut parser = Parser {
+            input: value.as_bytes(),
+            pos: 0,
+        };
+        parser.skip_sp();
+        let parsed = parse(&mut parser)?;
+        parser.skip_sp();
+        if parser.pos != parser.input.len() {
+            return Err(InvalidStructuredField::new());
+        }
+        Ok(parsed)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), InvalidStructuredField> {
+        match self.next() {
+            Some(b) if b == expected => Ok(()),
+            _ => Err(InvalidStructuredField::new()),
+        }
+    }
+
+    fn skip_sp(&mut self) {
+        while self.peek() == Some(b' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_ows(&mut self) {
+        while let Some(b' ') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes the comma between members, returning `false` at the end.
+    fn list_separator(&mut self) -> Result<bool, InvalidStructuredField> {
+        self.skip_ows();
+        if self.peek().is_none() {
+            return Ok(false);
+        }
+        self.expect(b',')?;
+        self.skip_ows();
+        if self.peek().is_none() {
+            // trailing comma
+            return Err(InvalidStructuredField::new());
+        }
+        Ok(true)
+    }
+
+    fn parse_list(&mut self) -> Result<List, InvalidStructuredField> {
+        let mut members = Vec::new();
+        if self.peek().is_none() {
+            return Ok(List(members));
+        }
+        loop {
+            members.push(self.parse_list_entry()?);
+            if !self.list_separator()? {
+                return Ok(List(members));
+            }
+        }
+    }
+
+    fn parse_dictionary(&mut self) -> Result<Dictionary, InvalidStructuredField> {
+        let mut dict = Dictionary::default();
+        if self.peek().is_none() {
+            return Ok(dict);
+        }
+        loop {
+            let key = self.parse_key()?;
+            let member = if self.peek() == Some(b'=') {
+                self.pos += 1;
+                self.parse_list_entry()?
+            } else {
+                ListEntry::Item(Item {
+                    bare_item: BareItem::Boolean(true),
+                    params: self.parse_parameters()?,
+                })
+            };
+            match dict.0.iter_mut().find(|(k, _)| *k == key) {
+                Some(entry) => entry.1 = member,
+                None => dict.0.push((key, member)),
+            }
+            if !self.list_separator()? {
+                return Ok(dict);
+            }
+        }
+    }
+
+    fn parse_list_entry(&mut self) -> Result<ListEntry, InvalidStructuredField> {
+        if self.peek() == Some(b'(') {
+            self.parse_inner_list().map(ListEntry::InnerList)
+        } else {
+            self.parse_item().map(ListEntry::Item)
+        }
+    }
+
+    fn parse_inner_list(&mut self) -> Result<InnerList, InvalidStructuredField> {
+        self.expect(b'(')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_sp();
+            if self.peek() == Some(b')') {
+                self.pos += 1;
+                let params = self.parse_parameters()?;
+                return Ok(InnerList { items, params });
+            }
+            items.push(self.parse_item()?);
+            match self.peek() {
+                Some(b' ') | Some(b')') => {}
+                _ => return Err(InvalidStructuredField::new()),
+            }
+        }
+    }
+
+    fn parse_item(&mut self) -> Result<Item, InvalidStructuredField> {
+        let bare_item = self.parse_bare_item()?;
+        let params = self.parse_parameters()?;
+        Ok(Item { bare_item, params })
+    }
+
+    fn parse_parameters(&mut self) -> Result<Parameters, InvalidStructuredField> {
+        let mut params = Parameters::default();
+        while self.peek() == Some(b';') {
+            self.pos += 1;
+            self.skip_sp();
+            let key = self.parse_key()?;
+            let value = if self.peek() == Some(b'=') {
+                self.pos += 1;
+                self.parse_bare_item()?
+            } else {
+                BareItem::Boolean(true)
+            };
+            params.insert(key, value);
+        }
+        Ok(params)
+    }
+
+    fn parse_key(&mut self) -> Result<String, InvalidStructuredField> {
+        let start = self.pos;
+        match self.peek() {
+            Some(b) if b.is_ascii_lowercase() || b == b'*' => self.pos += 1,
+            _ => return Err(InvalidStructuredField::new()),
+        }
+        while let Some(b) = self.peek() {
+            if !is_key_char(b) {
+                break;
+            }
+            self.pos += 1;
+        }
+        Ok(self.slice_to_string(start))
+    }
+
+    fn parse_bare_item(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        match self.peek() {
+            Some(b'-') => self.parse_number(),
+            Some(b) if b.is_ascii_digit() => self.parse_number(),
+            Some(b'"') => self.parse_string(),
+            Some(b) if b.is_ascii_alphabetic() || b == b'*' => self.parse_token(),
+            Some(b':') => self.parse_byte_sequence(),
+            Some(b'?') => self.parse_boolean(),
+            _ => Err(InvalidStructuredField::new()),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        if !self.peek().map_or(false, |b| b.is_ascii_digit()) {
+            return Err(InvalidStructuredField::new());
+        }
+
+        let start = self.pos;
+        let mut dot = None;
+        while let Some(b) = self.peek() {
+            if b.is_ascii_digit() {
+                self.pos += 1;
+            } else if b == b'.' && dot.is_none() {
+                if self.pos - start > 12 {
+                    return Err(InvalidStructuredField::new());
+                }
+                dot = Some(self.pos);
+                self.pos += 1;
+            } else {
+                break;
+            }
+            let len = self.pos - start;
+            if (dot.is_none() && len > 15) || (dot.is_some() && len > 16) {
+                return Err(InvalidStructuredField::new());
+            }
+        }
+
+        let sign = if negative { -1 } else { 1 };
+        let digits = &self.input[start..self.pos];
+        match dot {
+            None => Ok(BareItem::Integer(sign * parse_digits(digits))),
+            Some(dot) => {
+                let frac = &self.input[dot + 1..self.pos];
+                if frac.is_empty() || frac.len() > 3 {
+                    return Err(InvalidStructuredField::new());
+                }
+                let int = parse_digits(&self.input[start..dot]);
+                let mut thousandths = parse_digits(frac);
+                for _ in frac.len()..3 {
+                    thousandths *= 10;
+                }
+                Ok(BareItem::Decimal(Decimal(
+                    sign * (int * 1000 + thousandths),
+                )))
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        self.expect(b'"')?;
+        let mut s = String::new();
+        loop {
+            match self.next() {
+                Some(b'\\') => match self.next() {
+                    Some(b @ b'"') | Some(b @ b'\\') => s.push(b as char),
+                    _ => return Err(InvalidStructuredField::new()),
+                },
+                Some(b'"') => return Ok(BareItem::String(s)),
+                Some(b @ 0x20..=0x7e) => s.push(b as char),
+                _ => return Err(InvalidStructuredField::new()),
+            }
+        }
+    }
+
+    fn parse_token(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(b) = self.peek() {
+            if !(is_tchar(b) || b == b':' || b == b'/') {
+                break;
+            }
+            self.pos += 1;
+        }
+        Ok(BareItem::Token(self.slice_to_string(start)))
+    }
+
+    fn parse_byte_sequence(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        self.expect(b':')?;
+        let start = self.pos;
+        loop {
+            match self.next() {
+                Some(b':') => break,
+                Some(_) => {}
+                None => return Err(InvalidStructuredField::new()),
+            }
+        }
+        base64_decode(&self.input[start..self.pos - 1])
+            .map(BareItem::ByteSequence)
+            .ok_or_else(InvalidStructuredField::new)
+    }
+
+    fn parse_boolean(&mut self) -> Result<BareItem, InvalidStructuredField> {
+        self.expect(b'?')?;
+        match self.next() {
+            Some(b'1') => Ok(BareItem::Boolean(true)),
+            Some(b'0') => Ok(BareItem::Boolean(false)),
+            _ => Err(InvalidStructuredField::new()),
+        }
+    }
+
+    fn slice_to_string(&self, start: usize) -> String {
+        // Keys and tokens are checked to be ASCII as they are scanned.
+        String::from_utf8(self.input[start..self.pos].to_vec()).expect("ASCII")
+    }
+}
+
+fn parse_digits(digits: &[u8]) -> i64 {
+    // Callers bound the length, so this cannot overflow.
+    digits.iter().fold(0, |n, &d| n * 10 + i64::from(d - b'0'))
+}
+
+fn is_key_char(b: u8) -> bool {
+    b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b)
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+// ===== Serialization =====
+
+impl Item {
+    fn write(&self, dst: &mut String) -> Result<(), InvalidStructuredField> {
+        self.bare_item.write(dst)?;
+        self.params.write(dst)
+    }
+}
+
+impl InnerList {
+    fn write(&self, dst: &mut String) -> Result<(), InvalidStructuredField> {
+        dst.push('(');
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                dst.push(' ');
+            }
+            item.write(dst)?;
+        }
+        dst.push(')');
+        self.params.write(dst)
+    }
+}
+
+impl ListEntry {
+    fn write(&self, dst: &mut String) -> Result<(), InvalidStructuredField> {
+        match *self {
+            ListEntry::Item(ref item) => item.write(dst),
+            ListEntry::InnerList(ref list) => list.write(dst),
+        }
+    }
+}
+
+impl Parameters {
+    fn write(&self, dst: &mut String) -> Result<(), InvalidStructuredField> {
+        for (key, value) in &self.0 {
+            dst.push(';');
+            write_key(key, dst)?;
+            if *value != BareItem::Boolean(true) {
+                dst.push('=');
+                value.write(dst)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BareItem {
+    fn write(&self, dst: &mut String) -> Result<(), InvalidStructuredField> {
+        match *self {
+            BareItem::Integer(n) => {
+                if !(-MAX_INTEGER..=MAX_INTEGER).contains(&n) {
+                    return Err(InvalidStructuredField::new());
+                }
+                write!(dst, "{}", n).unwrap();
+            }
+            BareItem::Decimal(d) => {
+                if !(-MAX_INTEGER..=MAX_INTEGER).contains(&d.0) {
+                    return Err(InvalidStructuredField::new());
+                }
+                write!(dst, "{}", d).unwrap();
+            }
+            BareItem::String(ref s) => {
+                dst.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' | '\\' => {
+                            dst.push('\\');
+                            dst.push(c);
+                        }
+                        ' '..='~' => dst.push(c),
+                        _ => return Err(InvalidStructuredField::new()),
+                    }
+                }
+                dst.push('"');
+            }
+            BareItem::Token(ref t) => {
+                let valid = t.bytes().enumerate().all(|(i, b)| {
+                    if i == 0 {
+                        b.is_ascii_alphabetic() || b == b'*'
+                    } else {
+                        is_tchar(b) || b == b':' || b == b'/'
+                    }
+                });
+                if t.is_empty() || !valid {
+                    return Err(InvalidStructuredField::new());
+                }
+                dst.push_str(t);
+            }
+            BareItem::ByteSequence(ref bytes) => {
+                dst.push(':');
+                base64_encode(bytes, dst);
+                dst.push(':');
+            }
+            BareItem::Boolean(b) => dst.push_str(if b { "?1" } else { "?0" }),
+        }
+        Ok(())
+    }
+}
+
+fn write_key(key: &str, dst: &mut String) -> Result<(), InvalidStructuredField> {
+    let valid = key.bytes().enumerate().all(|(i, b)| {
+        if i == 0 {
+            b.is_ascii_lowercase() || b == b'*'
+        } else {
+            is_key_char(b)
+        }
+    });
+    if key.is_empty() || !valid {
+        return Err(InvalidStructuredField::new());
+    }
+    dst.push_str(key);
+    Ok(())
+}
+
+fn write_members<'a, I>(members: I, dst: &mut String) -> Result<(), InvalidStructuredField>
+where
+    I: Iterator<Item = (Option<&'a str>, &'a ListEntry)>,
+{
+    for (i, (key, member)) in members.enumerate() {
+        if i > 0 {
+            dst.push_str(", ");
+        }
+        match key {
+            Some(key) => {
+                write_key(key, dst)?;
+                match *member {
+                    ListEntry::Item(ref item) if item.bare_item == BareItem::Boolean(true) => {
+                        item.params.write(dst)?;
+                    }
+                    _ => {
+                        dst.push('=');
+                        member.write(dst)?;
+                    }
+                }
+            }
+            None => member.write(dst)?,
+        }
+    }
+    Ok(())
+}
+
+fn to_header_value(s: String) -> Result<HeaderValue, InvalidStructuredField> {
+    HeaderValue::from_maybe_shared(s).map_err(|_| InvalidStructuredField::new())
+}
+
+impl<'a> TryFrom<&'a Item> for HeaderValue {
+    type Error = InvalidStructuredField;
+
+    fn try_from(item: &'a Item) -> Result<Self, Self::Error> {
+        let mut dst = String::new();
+        item.write(&mut dst)?;
+        to_header_value(dst)
+    }
+}
+
+/// An empty list serializes to an empty value; RFC 8941 asks for the field
+/// to be omitted instead.
+impl<'a> TryFrom<&'a List> for HeaderValue {
+    type Error = InvalidStructuredField;
+
+    fn try_from(list: &'a List) -> Result<Self, Self::Error> {
+        let mut dst = String::new();
+        write_members(list.0.iter().map(|m| (None, m)), &mut dst)?;
+        to_header_value(dst)
+    }
+}
+
+/// An empty dictionary serializes to an empty value; RFC 8941 asks for the
+/// field to be omitted instead.
+impl<'a> TryFrom<&'a Dictionary> for HeaderValue {
+    type Error = InvalidStructuredField;
+
+    fn try_from(dict: &'a Dictionary) -> Result<Self, Self::Error> {
+        let mut dst = String::new();
+        write_members(dict.0.iter().map(|(k, m)| (Some(&k[..]), m)), &mut dst)?;
+        to_header_value(dst)
+    }
+}
+
+// ===== Base64 =====
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(src: &[u8], dst: &mut String) {
+    for chunk in src.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                dst.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                dst.push('=');
+            }
+        }
+    }
+}
+
+/// Decodes standard base64, with or without padding.
+fn base64_decode(src: &[u8]) -> Option<Vec<u8>> {
+    let unpadded = match src.iter().position(|&b| b == b'=') {
+        Some(i) => {
+            // Padding is only allowed to fill out the final group.
+            if src.len() % 4 != 0 || src.len() - i > 2 || src[i..].iter().any(|&b| b != b'=') {
+                return None;
+            }
+            &src[..i]
+        }
+        None => src,
+    };
+    if unpadded.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut dst = Vec::with_capacity(unpadded.len() * 3 / 4);
+    for chunk in unpadded.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = BASE64.iter().position(|&b| b == c)? as u32;
+            n |= v << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            dst.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(dst)
+}
+
+// ===== impl InvalidStructuredField =====
+
+impl InvalidStructuredField {
+    fn new() -> InvalidStructuredField {
+        InvalidStructuredField { _priv: () }
+    }
+}
+
+impl fmt::Debug for InvalidStructuredField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidStructuredField")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidStructuredField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid structured field value")
+    }
+}
+
+impl Error for InvalidStructuredField {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(s: &str) -> Option<Item> {
+        Item::parse(&HeaderValue::from_str(s).unwrap()).ok()
+    }
+
+    fn bare(s: &str) -> Option<BareItem> {
+        item(s).map(|i| i.bare_item)
+    }
+
+    fn round_trip_list(s: &str) -> String {
+        let list = List::parse(&HeaderValue::from_str(s).unwrap()).unwrap();
+        HeaderValue::try_from(&list)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn round_trip_dict(s: &str) -> String {
+        let dict = Dictionary::parse(&HeaderValue::from_str(s).unwrap()).unwrap();
+        HeaderValue::try_from(&dict)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(bare("42"), Some(BareItem::Integer(42)));
+        assert_eq!(bare("-0"), Some(BareItem::Integer(0)));
+        assert_eq!(
+            bare("999999999999999"),
+            Some(BareItem::Integer(MAX_INTEGER))
+        );
+        assert_eq!(bare("1000000000000000"), None);
+        assert_eq!(
+            bare("-1.5"),
+            Some(BareItem::Decimal(Decimal::from_thousandths(-1500)))
+        );
+        assert_eq!(
+            bare("123456789012.123"),
+            Some(BareItem::Decimal(Decimal::from_thousandths(
+                123_456_789_012_123
+            )))
+        );
+        assert_eq!(bare("1234567890123.0"), None);
+        assert_eq!(bare("1.1234"), None);
+        assert_eq!(bare("1."), None);
+        assert_eq!(bare("-"), None);
+        assert_eq!(bare("- 1"), None);
+    }
+
+    #[test]
+    fn parses_strings_tokens_and_booleans() {
+        assert_eq!(
+            bare(r#""a \"b\" \\ c""#),
+            Some(BareItem::String(r#"a "b" \ c"#.into()))
+        );
+        assert_eq!(bare(r#""\n""#), None);
+        assert_eq!(bare(r#""open"#), None);
+        assert_eq!(bare("text/html"), Some(BareItem::Token("text/html".into())));
+        assert_eq!(bare("*foo:bar"), Some(BareItem::Token("*foo:bar".into())));
+        assert_eq!(bare("?1"), Some(BareItem::Boolean(true)));
+        assert_eq!(bare("?0"), Some(BareItem::Boolean(false)));
+        assert_eq!(bare("?2"), None);
+    }
+
+    #[test]
+    fn parses_byte_sequences() {
+        assert_eq!(
+            bare(":cHJldGVuZCB0aGlzIGlzIGJpbmFyeSBjb250ZW50Lg==:"),
+            Some(BareItem::ByteSequence(
+                b"pretend this is binary content.".to_vec()
+            ))
+        );
+        assert_eq!(bare("::"), Some(BareItem::ByteSequence(Vec::new())));
+        assert_eq!(bare(":YQ:"), Some(BareItem::ByteSequence(b"a".to_vec())));
+        assert_eq!(bare(":Y:"), None);
+        assert_eq!(bare(":YQ=a:"), None);
+        assert_eq!(bare(":YQ=="), None);
+
+        let mut dst = String::new();
+        base64_encode(b"ab", &mut dst);
+        assert_eq!(dst, "YWI=");
+    }
+
+    #[test]
+    fn parses_parameters() {
+        let i = item("abc;a=1;b;a=2").unwrap();
+        assert_eq!(
+            i.params.0,
+            [
+                ("a".to_owned(), BareItem::Integer(2)),
+                ("b".to_owned(), BareItem::Boolean(true)),
+            ]
+        );
+        assert!(item("abc;A=1").is_none());
+        assert!(item("abc; a=1").is_some());
+        assert!(item("abc ;a=1").is_none());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(round_trip_list(""), "");
+        assert_eq!(round_trip_list("a,b,\t c"), "a, b, c");
+        assert_eq!(
+            round_trip_list("(\"foo\"  \"bar\");lvl=5, ( );x=?0"),
+            "(\"foo\" \"bar\");lvl=5, ();x=?0"
+        );
+        assert!(List::parse(&HeaderValue::from_static("a,")).is_err());
+        assert!(List::parse(&HeaderValue::from_static("(a b")).is_err());
+        assert!(List::parse(&HeaderValue::from_static("(a,b)")).is_err());
+    }
+
+    #[test]
+    fn parses_dictionaries() {
+        assert_eq!(
+            round_trip_dict("a=?0, b, c;foo=bar, d=(1 2), a=3"),
+            "a=3, b, c;foo=bar, d=(1 2)"
+        );
+        assert!(Dictionary::parse(&HeaderValue::from_static("A=1")).is_err());
+        assert!(Dictionary::parse(&HeaderValue::from_static("a=1,")).is_err());
+    }
+
+    #[test]
+    fn serializes_items() {
+        let serialize = |bare_item| HeaderValue::try_from(&Item::new(bare_item)).ok();
+
+        assert_eq!(
+            serialize(BareItem::Decimal(Decimal::from_thousandths(2000))).unwrap(),
+            "2.0"
+        );
+        assert_eq!(
+            serialize(BareItem::Decimal(Decimal::from_thousandths(-50))).unwrap(),
+            "-0.05"
+        );
+        assert!(serialize(BareItem::Integer(MAX_INTEGER + 1)).is_none());
+        assert!(serialize(BareItem::String("caf\u{e9}".into())).is_none());
+        assert!(serialize(BareItem::Token("1abc".into())).is_none());
+        assert!(serialize(BareItem::Token(String::new())).is_none());
+
+        let mut i = Item::new(BareItem::Token("abc".into()));
+        i.params.0.push(("Bad".into(), BareItem::Boolean(true)));
+        assert!(HeaderValue::try_from(&i).is_err());
+    }
+}
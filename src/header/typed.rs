@@ -0,0 +1,1335 @@
+//! Typed representations of common HTTP headers.
+//!
+//! The [`Header`] trait ties a Rust type to a header name and describes how
+//! it is decoded from, and encoded into, the raw `HeaderValue`s stored in a
+//! `HeaderMap`.
+//!
+//! # Examples
+//!
+//! ```
+//! use http::header::typed::{ContentLength, Header};
+//! use http::header::{HeaderMap, HeaderValue};
+//!
+//! let mut map = HeaderMap::new();
+//! map.insert(ContentLength::name(), HeaderValue::from_static("42"));
+//!
+//! let len = ContentLength::decode(&mut map.get_all(ContentLength::name()).iter()).unwrap();
+//! assert_eq!(len, ContentLength(42));
+//! ```
+
+use std::convert::TryFrom;
+use std::error;
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use super::name::{self, is_token, HeaderName};
+use super::value::HeaderValue;
+use crate::method::Method;
+use crate::uri::Authority;
+
+/// A typed HTTP header.
+pub trait Header {
+    /// The name of this header.
+    fn name() -> HeaderName;
+
+    /// Decode this header from the values of every field with its name.
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>;
+
+    /// Encode this header into one or more field values.
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>;
+}
+
+/// A possible error when decoding a typed header.
+pub struct InvalidHeader {
+    _priv: (),
+}
+
+impl InvalidHeader {
+    fn new() -> InvalidHeader {
+        InvalidHeader { _priv: () }
+    }
+}
+
+impl fmt::Debug for InvalidHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidHeader")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid typed header")
+    }
+}
+
+impl error::Error for InvalidHeader {}
+
+/// Returns the only value in `values`, failing if there are none or several.
+fn one<'i, I>(values: &mut I) -> Result<&'i HeaderValue, InvalidHeader>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    match (values.next(), values.next()) {
+        (Some(value), None) => Ok(value),
+        _ => Err(InvalidHeader::new()),
+    }
+}
+
+// ===== ContentLength =====
+
+/// `Content-Length` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-8.6).
+///
+/// Repeated fields are accepted as long as they all carry the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentLength(pub u64);
+
+impl Header for ContentLength {
+    fn name() -> HeaderName {
+        name::CONTENT_LENGTH
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut len = None;
+        for value in values {
+            for part in value.as_bytes().split(|&b| b == b',') {
+                let n = parse_u64(trim(part)).ok_or_else(InvalidHeader::new)?;
+                match len {
+                    Some(prev) if prev != n => return Err(InvalidHeader::new()),
+                    _ => len = Some(n),
+                }
+            }
+        }
+        len.map(ContentLength).ok_or_else(InvalidHeader::new)
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        values.extend(std::iter::once(HeaderValue::from(self.0)));
+    }
+}
+
+fn parse_u64(src: &[u8]) -> Option<u64> {
+    if src.is_empty() {
+        return None;
+    }
+    let mut n: u64 = 0;
+    for &b in src {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        n = n.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+    }
+    Some(n)
+}
+
+fn trim(src: &[u8]) -> &[u8] {
+    let is_ws = |b: &u8| *b == b' ' || *b == b'\t';
+    let start = src.iter().position(|b| !is_ws(b)).unwrap_or(src.len());
+    let end = src.iter().rposition(|b| !is_ws(b)).map_or(start, |i| i + 1);
+    &src[start..end]
+}
+
+// ===== ContentType =====
+
+/// `Content-Type` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-8.3).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentType(HeaderValue);
+
+impl ContentType {
+    /// Creates a `ContentType` from a static media type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument is not a valid media type.
+    pub fn from_static(src: &'static str) -> ContentType {
+        match src.parse() {
+            Ok(ct) => ct,
+            Err(_) => panic!("invalid media type"),
+        }
+    }
+
+    /// Returns the media type without any parameters, such as `text/html`.
+    pub fn essence(&self) -> &str {
+        let s = self.as_str();
+        s.split(';').next().unwrap_or(s).trim()
+    }
+
+    /// Returns the full value of this header, including any parameters.
+    pub fn as_str(&self) -> &str {
+        // Only values that are valid strings are ever stored.
+        self.0.to_str().unwrap_or("")
+    }
+}
+
+impl FromStr for ContentType {
+    type Err = InvalidHeader;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = HeaderValue::from_str(s).map_err(|_| InvalidHeader::new())?;
+        ContentType::decode(&mut std::iter::once(&value))
+    }
+}
+
+impl Header for ContentType {
+    fn name() -> HeaderName {
+        name::CONTENT_TYPE
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?;
+        let s = value.to_str().map_err(|_| InvalidHeader::new())?;
+        let essence = s.split(';').next().unwrap_or(s).trim();
+        match essence.find('/') {
+            Some(idx) if idx > 0 && idx + 1 < essence.len() => Ok(ContentType(value.clone())),
+            _ => Err(InvalidHeader::new()),
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        values.extend(std::iter::once(self.0.clone()));
+    }
+}
+
+// ===== Host =====
+
+/// `Host` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-7.2).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Host(pub Authority);
+
+impl Header for Host {
+    fn name() -> HeaderName {
+        name::HOST
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?;
+        let authority = Authority::try_from(value.as_bytes()).map_err(|_| InvalidHeader::new())?;
+        // Userinfo is not allowed in the Host header.
+        if authority.as_str().contains('@') {
+            return Err(InvalidHeader::new());
+        }
+        Ok(Host(authority))
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let value = HeaderValue::from_str(self.0.as_str())
+            .expect("authority is always a valid header value");
+        values.extend(std::iter::once(value));
+    }
+}
+
+// ===== Location =====
+
+/// `Location` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-10.2.2).
+///
+/// The value is a URI reference and may be relative, so it is kept as a
+/// string rather than parsed into a `Uri`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location(HeaderValue);
+
+impl Location {
+    /// Creates a `Location` from a static URI reference.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument contains invalid header value
+    /// characters.
+    pub fn from_static(src: &'static str) -> Location {
+        Location(HeaderValue::from_static(src))
+    }
+
+    /// Returns the URI reference of this header.
+    pub fn as_str(&self) -> &str {
+        // Only values that are valid strings are ever stored.
+        self.0.to_str().unwrap_or("")
+    }
+}
+
+impl FromStr for Location {
+    type Err = InvalidHeader;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = HeaderValue::from_str(s).map_err(|_| InvalidHeader::new())?;
+        Location::decode(&mut std::iter::once(&value))
+    }
+}
+
+impl Header for Location {
+    fn name() -> HeaderName {
+        name::LOCATION
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?;
+        match value.to_str() {
+            Ok(s) if !s.is_empty() && !s.contains(' ') => Ok(Location(value.clone())),
+            _ => Err(InvalidHeader::new()),
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        values.extend(std::iter::once(self.0.clone()));
+    }
+}
+
+// ===== Allow =====
+
+/// `Allow` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-10.2.1).
+///
+/// An empty list is valid and means the resource allows no methods.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Allow(pub Vec<Method>);
+
+impl Allow {
+    /// Returns `true` if the list contains `method`.
+    pub fn allows(&self, method: &Method) -> bool {
+        self.0.contains(method)
+    }
+}
+
+impl Header for Allow {
+    fn name() -> HeaderName {
+        name::ALLOW
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut methods = Vec::new();
+        for value in values {
+            for part in value.as_bytes().split(|&b| b == b',') {
+                let part = trim(part);
+                if part.is_empty() {
+                    continue;
+                }
+                methods.push(Method::from_bytes(part).map_err(|_| InvalidHeader::new())?);
+            }
+        }
+        Ok(Allow(methods))
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let joined = self
+            .0
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&joined).expect("methods are valid header values");
+        values.extend(std::iter::once(value));
+    }
+}
+
+// ===== Expect =====
+
+/// `Expect` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-10.1.1).
+///
+/// The only expectation defined is `100-continue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Expect(());
+
+impl Expect {
+    /// `Expect: 100-continue`
+    pub const CONTINUE: Expect = Expect(());
+}
+
+impl Header for Expect {
+    fn name() -> HeaderName {
+        name::EXPECT
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?;
+        if value.as_bytes().eq_ignore_ascii_case(b"100-continue") {
+            Ok(Expect::CONTINUE)
+        } else {
+            Err(InvalidHeader::new())
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        values.extend(std::iter::once(HeaderValue::from_static("100-continue")));
+    }
+}
+
+// ===== ETag =====
+
+/// `ETag` header, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-8.8.3).
+///
+/// # Examples
+///
+/// ```
+/// use http::header::typed::ETag;
+///
+/// let a: ETag = "W/\"xyzzy\"".parse().unwrap();
+/// let b: ETag = "\"xyzzy\"".parse().unwrap();
+///
+/// assert!(a.is_weak());
+/// assert_eq!(a.tag(), "xyzzy");
+/// assert!(a.weak_eq(&b));
+/// assert!(!a.strong_eq(&b));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag(HeaderValue);
+
+impl ETag {
+    /// Returns `true` if this is a weak validator.
+    pub fn is_weak(&self) -> bool {
+        self.0.as_bytes().starts_with(b"W/")
+    }
+
+    /// Returns the opaque tag, without the quotes or weakness indicator.
+    pub fn tag(&self) -> &str {
+        // Only values that are valid strings are ever stored.
+        let s = self.0.to_str().unwrap_or("\"\"");
+        let start = if self.is_weak() { 3 } else { 1 };
+        &s[start..s.len() - 1]
+    }
+
+    /// Strong comparison: both must be strong and have the same tag.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.is_weak() && !other.is_weak() && self.tag() == other.tag()
+    }
+
+    /// Weak comparison: the tags must match, regardless of weakness.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag() == other.tag()
+    }
+}
+
+impl FromStr for ETag {
+    type Err = InvalidHeader;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = HeaderValue::from_str(s).map_err(|_| InvalidHeader::new())?;
+        ETag::decode(&mut std::iter::once(&value))
+    }
+}
+
+impl Header for ETag {
+    fn name() -> HeaderName {
+        name::ETAG
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?;
+        let bytes = value.as_bytes();
+        let opaque = if bytes.starts_with(b"W/") {
+            &bytes[2..]
+        } else {
+            bytes
+        };
+        let valid = opaque.len() >= 2
+            && opaque[0] == b'"'
+            && opaque[opaque.len() - 1] == b'"'
+            && opaque[1..opaque.len() - 1]
+                .iter()
+                .all(|&b| b == 0x21 || (0x23..0x7f).contains(&b));
+        if valid {
+            Ok(ETag(value.clone()))
+        } else {
+            Err(InvalidHeader::new())
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        values.extend(std::iter::once(self.0.clone()));
+    }
+}
+
+// ===== Range =====
+
+/// `Range` header with byte ranges, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-14.2).
+///
+/// Ranges in other units are rejected when decoding.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::typed::{Header, Range};
+/// use http::header::HeaderValue;
+///
+/// let value = HeaderValue::from_static("bytes=0-499, 1000-, -200");
+/// let range = Range::decode(&mut std::iter::once(&value)).unwrap();
+///
+/// assert_eq!(range.satisfiable(1200), [0..=499, 1000..=1199, 1000..=1199]);
+/// assert_eq!(range.satisfiable(300), [0..=299, 100..=299]);
+/// assert!(range.satisfiable(0).is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Range(Vec<ByteRangeSpec>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ByteRangeSpec {
+    // `first-last`
+    Bounded(u64, u64),
+    // `first-`
+    From(u64),
+    // `-length`
+    Suffix(u64),
+}
+
+impl Range {
+    /// A range of the bytes from `first` to `last`, inclusive.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `first` is greater than `last`.
+    pub fn bytes(first: u64, last: u64) -> Range {
+        assert!(first <= last, "range must not be empty");
+        Range(vec![ByteRangeSpec::Bounded(first, last)])
+    }
+
+    /// A range of the bytes from `first` to the end of the representation.
+    pub fn bytes_from(first: u64) -> Range {
+        Range(vec![ByteRangeSpec::From(first)])
+    }
+
+    /// A range of the last `length` bytes of the representation.
+    pub fn suffix(length: u64) -> Range {
+        Range(vec![ByteRangeSpec::Suffix(length)])
+    }
+
+    /// Resolves the ranges against a representation of `len` bytes.
+    ///
+    /// Each range is clamped to the end of the representation, and ranges
+    /// that start beyond it are dropped. The ranges are returned in the order
+    /// they were requested, without merging overlaps.
+    ///
+    /// An empty result means the request is unsatisfiable, and should be
+    /// answered with `416 Range Not Satisfiable` and a
+    /// [`ContentRange::unsatisfied`] header.
+    pub fn satisfiable(&self, len: u64) -> Vec<RangeInclusive<u64>> {
+        let last = match len.checked_sub(1) {
+            Some(last) => last,
+            None => return Vec::new(),
+        };
+        self.0
+            .iter()
+            .filter_map(|spec| match *spec {
+                ByteRangeSpec::Bounded(first, end) if first < len => Some(first..=end.min(last)),
+                ByteRangeSpec::From(first) if first < len => Some(first..=last),
+                ByteRangeSpec::Suffix(length) if length > 0 => Some(len - length.min(len)..=last),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Header for Range {
+    fn name() -> HeaderName {
+        name::RANGE
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?.as_bytes();
+        if value.len() < 6 || !value[..6].eq_ignore_ascii_case(b"bytes=") {
+            return Err(InvalidHeader::new());
+        }
+
+        let mut specs = Vec::new();
+        for part in value[6..].split(|&b| b == b',') {
+            let part = trim(part);
+            if part.is_empty() {
+                continue;
+            }
+            let dash = part
+                .iter()
+                .position(|&b| b == b'-')
+                .ok_or_else(InvalidHeader::new)?;
+            let (first, last) = (&part[..dash], &part[dash + 1..]);
+            let spec = match (parse_u64(first), parse_u64(last)) {
+                (Some(first), Some(last)) if first <= last => ByteRangeSpec::Bounded(first, last),
+                (Some(first), None) if last.is_empty() => ByteRangeSpec::From(first),
+                (None, Some(length)) if first.is_empty() => ByteRangeSpec::Suffix(length),
+                _ => return Err(InvalidHeader::new()),
+            };
+            specs.push(spec);
+        }
+
+        if specs.is_empty() {
+            return Err(InvalidHeader::new());
+        }
+        Ok(Range(specs))
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let mut s = String::from("bytes=");
+        for (i, spec) in self.0.iter().enumerate() {
+            if i > 0 {
+                s.push_str(", ");
+            }
+            let _ = match *spec {
+                ByteRangeSpec::Bounded(first, last) => write!(s, "{}-{}", first, last),
+                ByteRangeSpec::From(first) => write!(s, "{}-", first),
+                ByteRangeSpec::Suffix(length) => write!(s, "-{}", length),
+            };
+        }
+        let value = HeaderValue::from_str(&s).expect("ranges are valid header values");
+        values.extend(std::iter::once(value));
+    }
+}
+
+// ===== ContentRange =====
+
+/// `Content-Range` header with a byte range, defined in
+/// [RFC 9110](https://datatracker.ietf.org/doc/html/rfc9110#section-14.4).
+///
+/// # Examples
+///
+/// ```
+/// use http::header::typed::{ContentRange, Header};
+///
+/// let mut values = Vec::new();
+/// ContentRange::bytes(0..=499, Some(1234)).encode(&mut values);
+/// ContentRange::unsatisfied(1234).encode(&mut values);
+///
+/// assert_eq!(values, ["bytes 0-499/1234", "bytes */1234"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentRange {
+    range: Option<(u64, u64)>,
+    complete_length: Option<u64>,
+}
+
+impl ContentRange {
+    /// The bytes in `range` of a representation of `complete_length` bytes,
+    /// or of an unknown length.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the range is empty, or does not end before
+    /// `complete_length`.
+    pub fn bytes(range: RangeInclusive<u64>, complete_length: Option<u64>) -> ContentRange {
+        let (first, last) = range.into_inner();
+        assert!(first <= last, "range must not be empty");
+        assert!(
+            complete_length.map_or(true, |len| last < len),
+            "range must be within the complete length"
+        );
+        ContentRange {
+            range: Some((first, last)),
+            complete_length,
+        }
+    }
+
+    /// The header for a `416 Range Not Satisfiable` response, giving the
+    /// length of the representation.
+    pub fn unsatisfied(complete_length: u64) -> ContentRange {
+        ContentRange {
+            range: None,
+            complete_length: Some(complete_length),
+        }
+    }
+
+    /// Returns the range of bytes enclosed, or `None` for an unsatisfied
+    /// range.
+    pub fn range(&self) -> Option<RangeInclusive<u64>> {
+        self.range.map(|(first, last)| first..=last)
+    }
+
+    /// Returns the length of the whole representation, if known.
+    pub fn complete_length(&self) -> Option<u64> {
+        self.complete_length
+    }
+}
+
+impl Header for ContentRange {
+    fn name() -> HeaderName {
+        name::CONTENT_RANGE
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = one(values)?.as_bytes();
+        if value.len() < 6 || !value[..6].eq_ignore_ascii_case(b"bytes ") {
+            return Err(InvalidHeader::new());
+        }
+        let rest = &value[6..];
+        let slash = rest
+            .iter()
+            .position(|&b| b == b'/')
+            .ok_or_else(InvalidHeader::new)?;
+        let (range, len) = (&rest[..slash], &rest[slash + 1..]);
+
+        let complete_length = match len {
+            b"*" => None,
+            len => Some(parse_u64(len).ok_or_else(InvalidHeader::new)?),
+        };
+        let range = match range {
+            b"*" => None,
+            range => {
+                let dash = range
+                    .iter()
+                    .position(|&b| b == b'-')
+                    .ok_or_else(InvalidHeader::new)?;
+                let first = parse_u64(&range[..dash]).ok_or_else(InvalidHeader::new)?;
+                let last = parse_u64(&range[dash + 1..]).ok_or_else(InvalidHeader::new)?;
+                Some((first, last))
+            }
+        };
+
+        let valid = match (range, complete_length) {
+            (Some((first, last)), len) => first <= last && len.map_or(true, |len| last < len),
+            (None, len) => len.is_some(),
+        };
+        if valid {
+            Ok(ContentRange {
+                range,
+                complete_length,
+            })
+        } else {
+            Err(InvalidHeader::new())
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let mut s = String::from("bytes ");
+        let _ = match self.range {
+            Some((first, last)) => write!(s, "{}-{}/", first, last),
+            None => write!(s, "*/"),
+        };
+        let _ = match self.complete_length {
+            Some(len) => write!(s, "{}", len),
+            None => write!(s, "*"),
+        };
+        let value = HeaderValue::from_str(&s).expect("ranges are valid header values");
+        values.extend(std::iter::once(value));
+    }
+}
+
+// ===== CacheControl =====
+
+/// `Cache-Control` header, defined in
+/// [RFC 9111](https://datatracker.ietf.org/doc/html/rfc9111#section-5.2).
+///
+/// Directives are kept in the order they were listed, across every field
+/// line. Directive names are matched case-insensitively, and arguments may
+/// use either the token or the quoted-string form.
+///
+/// Decoding fails if any directive is malformed, such as `max-age` without
+/// a number or `no-store` with an argument, rather than skipping it. A cache
+/// that silently dropped a broken directive could end up storing or reusing
+/// a response it was told not to.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::typed::{CacheControl, CacheDirective, Header};
+/// use http::header::HeaderValue;
+///
+/// let value = HeaderValue::from_static("max-age=60, stale-while-revalidate=30, x-edge=\"a b\"");
+/// let cc = CacheControl::decode(&mut std::iter::once(&value)).unwrap();
+///
+/// assert_eq!(cc.max_age(), Some(60));
+/// assert_eq!(cc.stale_while_revalidate(), Some(30));
+/// assert!(!cc.no_store());
+///
+/// let cc = CacheControl(vec![CacheDirective::Private(vec![]), CacheDirective::MaxAge(0)]);
+/// let mut values = Vec::new();
+/// cc.encode(&mut values);
+/// assert_eq!(values, ["private, max-age=0"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CacheControl(pub Vec<CacheDirective>);
+
+/// A single `Cache-Control` directive.
+///
+/// Directives without a variant of their own are kept as
+/// [`Extension`](CacheDirective::Extension). More variants may be added in
+/// the future.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CacheDirective {
+    /// `max-age`, in seconds.
+    MaxAge(u64),
+    /// `s-maxage`, in seconds.
+    SMaxAge(u64),
+    /// `no-cache`, optionally limited to the listed fields.
+    NoCache(Vec<HeaderName>),
+    /// `no-store`
+    NoStore,
+    /// `private`, optionally limited to the listed fields.
+    Private(Vec<HeaderName>),
+    /// `public`
+    Public,
+    /// `must-revalidate`
+    MustRevalidate,
+    /// `proxy-revalidate`
+    ProxyRevalidate,
+    /// `must-understand`
+    MustUnderstand,
+    /// `no-transform`
+    NoTransform,
+    /// `max-stale`, in seconds, or any staleness if no limit is given.
+    MaxStale(Option<u64>),
+    /// `min-fresh`, in seconds.
+    MinFresh(u64),
+    /// `only-if-cached`
+    OnlyIfCached,
+    /// `immutable`, defined in
+    /// [RFC 8246](https://datatracker.ietf.org/doc/html/rfc8246#section-2).
+    Immutable,
+    /// `stale-while-revalidate`, in seconds, defined in
+    /// [RFC 5861](https://datatracker.ietf.org/doc/html/rfc5861#section-3).
+    StaleWhileRevalidate(u64),
+    /// `stale-if-error`, in seconds, defined in
+    /// [RFC 5861](https://datatracker.ietf.org/doc/html/rfc5861#section-4).
+    StaleIfError(u64),
+    /// Any other directive.
+    Extension(CacheExtension),
+}
+
+/// A `Cache-Control` directive not otherwise understood, with its optional
+/// argument.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheExtension {
+    name: String,
+    value: Option<String>,
+}
+
+impl CacheControl {
+    /// Returns the `max-age` directive, in seconds.
+    pub fn max_age(&self) -> Option<u64> {
+        self.0.iter().find_map(|d| match *d {
+            CacheDirective::MaxAge(secs) => Some(secs),
+            _ => None,
+        })
+    }
+
+    /// Returns the `s-maxage` directive, in seconds.
+    pub fn s_maxage(&self) -> Option<u64> {
+        self.0.iter().find_map(|d| match *d {
+            CacheDirective::SMaxAge(secs) => Some(secs),
+            _ => None,
+        })
+    }
+
+    /// Returns the `stale-while-revalidate` directive, in seconds.
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.0.iter().find_map(|d| match *d {
+            CacheDirective::StaleWhileRevalidate(secs) => Some(secs),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if the `no-store` directive is present.
+    pub fn no_store(&self) -> bool {
+        self.0.contains(&CacheDirective::NoStore)
+    }
+
+    /// Returns `true` if a `private` directive is present, with or without
+    /// a list of fields.
+    pub fn is_private(&self) -> bool {
+        self.0
+            .iter()
+            .any(|d| matches!(d, CacheDirective::Private(_)))
+    }
+
+    /// Returns the extension directive with the given name, if present.
+    pub fn extension(&self, name: &str) -> Option<&CacheExtension> {
+        self.0.iter().find_map(|d| match d {
+            CacheDirective::Extension(ext) if ext.name.eq_ignore_ascii_case(name) => Some(ext),
+            _ => None,
+        })
+    }
+}
+
+impl CacheExtension {
+    /// Creates an extension directive.
+    ///
+    /// Fails if `name` is not a token, or `value` contains characters that
+    /// cannot appear in a header value.
+    pub fn new(name: &str, value: Option<&str>) -> Result<CacheExtension, InvalidHeader> {
+        if !is_token(name) {
+            return Err(InvalidHeader::new());
+        }
+        if let Some(value) = value {
+            if !value
+                .bytes()
+                .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+            {
+                return Err(InvalidHeader::new());
+            }
+        }
+        Ok(CacheExtension {
+            name: name.to_ascii_lowercase(),
+            value: value.map(str::to_owned),
+        })
+    }
+
+    /// Returns the lowercased name of this directive.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the argument of this directive, unquoted.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
+
+impl Header for CacheControl {
+    fn name() -> HeaderName {
+        name::CACHE_CONTROL
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, InvalidHeader>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut directives = Vec::new();
+        for value in values {
+            for element in value.split_list().map_err(|_| InvalidHeader::new())? {
+                directives.push(CacheDirective::parse(element)?);
+            }
+        }
+        Ok(CacheControl(directives))
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let mut s = String::new();
+        for (i, directive) in self.0.iter().enumerate() {
+            if i > 0 {
+                s.push_str(", ");
+            }
+            directive.write(&mut s);
+        }
+        let value = HeaderValue::from_str(&s).expect("directives are valid header values");
+        values.extend(std::iter::once(value));
+    }
+}
+
+impl CacheDirective {
+    fn parse(element: &str) -> Result<CacheDirective, InvalidHeader> {
+        let (name, value) = match element.find('=') {
+            Some(eq) => {
+                let value = unquote(element[eq + 1..].trim()).ok_or_else(InvalidHeader::new)?;
+                (element[..eq].trim(), Some(value))
+            }
+            None => (element, None),
+        };
+        if !is_token(name) {
+            return Err(InvalidHeader::new());
+        }
+
+        let seconds = || {
+            value
+                .as_deref()
+                .and_then(delta_seconds)
+                .ok_or_else(InvalidHeader::new)
+        };
+        let fields = || match value.as_deref() {
+            Some(list) => list
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| HeaderName::from_bytes(s.as_bytes()).map_err(|_| InvalidHeader::new()))
+                .collect(),
+            None => Ok(Vec::new()),
+        };
+        let flag = |directive| match value {
+            Some(_) => Err(InvalidHeader::new()),
+            None => Ok(directive),
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "max-age" => Ok(CacheDirective::MaxAge(seconds()?)),
+            "s-maxage" => Ok(CacheDirective::SMaxAge(seconds()?)),
+            "stale-while-revalidate" => Ok(CacheDirective::StaleWhileRevalidate(seconds()?)),
+            "stale-if-error" => Ok(CacheDirective::StaleIfError(seconds()?)),
+            "min-fresh" => Ok(CacheDirective::MinFresh(seconds()?)),
+            "max-stale" => match value {
+                Some(_) => Ok(CacheDirective::MaxStale(Some(seconds()?))),
+                None => Ok(CacheDirective::MaxStale(None)),
+            },
+            "no-cache" => Ok(CacheDirective::NoCache(fields()?)),
+            "private" => Ok(CacheDirective::Private(fields()?)),
+            "no-store" => flag(CacheDirective::NoStore),
+            "public" => flag(CacheDirective::Public),
+            "must-revalidate" => flag(CacheDirective::MustRevalidate),
+            "proxy-revalidate" => flag(CacheDirective::ProxyRevalidate),
+            "must-understand" => flag(CacheDirective::MustUnderstand),
+            "no-transform" => flag(CacheDirective::NoTransform),
+            "only-if-cached" => flag(CacheDirective::OnlyIfCached),
+            "immutable" => flag(CacheDirective::Immutable),
+            _ => CacheExtension::new(name, value.as_deref()).map(CacheDirective::Extension),
+        }
+    }
+
+    fn write(&self, dst: &mut String) {
+        let _ = match self {
+            CacheDirective::MaxAge(secs) => write!(dst, "max-age={}", secs),
+            CacheDirective::SMaxAge(secs) => write!(dst, "s-maxage={}", secs),
+            CacheDirective::NoCache(fields) => write_fields(dst, "no-cache", fields),
+            CacheDirective::NoStore => write!(dst, "no-store"),
+            CacheDirective::Private(fields) => write_fields(dst, "private", fields),
+            CacheDirective::Public => write!(dst, "public"),
+            CacheDirective::MustRevalidate => write!(dst, "must-revalidate"),
+            CacheDirective::ProxyRevalidate => write!(dst, "proxy-revalidate"),
+            CacheDirective::MustUnderstand => write!(dst, "must-understand"),
+            CacheDirective::NoTransform => write!(dst, "no-transform"),
+            CacheDirective::MaxStale(Some(secs)) => write!(dst, "max-stale={}", secs),
+            CacheDirective::MaxStale(None) => write!(dst, "max-stale"),
+            CacheDirective::MinFresh(secs) => write!(dst, "min-fresh={}", secs),
+            CacheDirective::OnlyIfCached => write!(dst, "only-if-cached"),
+            CacheDirective::Immutable => write!(dst, "immutable"),
+            CacheDirective::StaleWhileRevalidate(secs) => {
+                write!(dst, "stale-while-revalidate={}", secs)
+            }
+            CacheDirective::StaleIfError(secs) => write!(dst, "stale-if-error={}", secs),
+            CacheDirective::Extension(ext) => {
+                dst.push_str(&ext.name);
+                if let Some(ref value) = ext.value {
+                    dst.push('=');
+                    write_quoted(dst, value);
+                }
+                Ok(())
+            }
+        };
+    }
+}
+
+fn write_fields(dst: &mut String, name: &str, fields: &[HeaderName]) -> fmt::Result {
+    dst.push_str(name);
+    if !fields.is_empty() {
+        let list: Vec<&str> = fields.iter().map(HeaderName::as_str).collect();
+        write!(dst, "=\"{}\"", list.join(", "))?;
+    }
+    Ok(())
+}
+
+/// Writes `value` as a token if it is one, or as a quoted-string.
+fn write_quoted(dst: &mut String, value: &str) {
+    if is_token(value) {
+        dst.push_str(value);
+        return;
+    }
+    dst.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            dst.push('\\');
+        }
+        dst.push(c);
+    }
+    dst.push('"');
+}
+
+/// Returns the contents of a quoted-string with escapes removed, or a token
+/// as it is.
+fn unquote(src: &str) -> Option<String> {
+    if !src.starts_with('"') {
+        return if is_token(src) {
+            Some(src.to_owned())
+        } else {
+            None
+        };
+    }
+
+    let mut out = String::new();
+    let mut chars = src[1..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                return if chars.next().is_none() {
+                    Some(out)
+                } else {
+                    None
+                }
+            }
+            '\\' => out.push(chars.next()?),
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Parses delta-seconds, saturating rather than failing on overflow.
+fn delta_seconds(src: &str) -> Option<u64> {
+    if src.is_empty() || !src.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(src.bytes().fold(0u64, |secs, b| {
+        secs.saturating_mul(10).saturating_add(u64::from(b - b'0'))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode<H: Header>(values: &[&'static str]) -> Result<H, InvalidHeader> {
+        let values: Vec<_> = values.iter().map(|v| HeaderValue::from_static(v)).collect();
+        H::decode(&mut values.iter())
+    }
+
+    fn encode<H: Header>(header: &H) -> Vec<HeaderValue> {
+        let mut values = Vec::new();
+        header.encode(&mut values);
+        values
+    }
+
+    #[test]
+    fn content_length() {
+        assert_eq!(decode::<ContentLength>(&["10"]).unwrap(), ContentLength(10));
+        assert_eq!(
+            decode::<ContentLength>(&["10", "10, 10"]).unwrap(),
+            ContentLength(10)
+        );
+        assert!(decode::<ContentLength>(&["10", "11"]).is_err());
+        assert!(decode::<ContentLength>(&["-1"]).is_err());
+        assert!(decode::<ContentLength>(&[]).is_err());
+        assert!(decode::<ContentLength>(&["99999999999999999999999"]).is_err());
+        assert_eq!(encode(&ContentLength(7)), vec!["7"]);
+    }
+
+    #[test]
+    fn content_type() {
+        let ct = decode::<ContentType>(&["text/html; charset=utf-8"]).unwrap();
+        assert_eq!(ct.essence(), "text/html");
+        assert_eq!(ct.as_str(), "text/html; charset=utf-8");
+        assert!(decode::<ContentType>(&["text"]).is_err());
+        assert!(decode::<ContentType>(&["text/html", "text/plain"]).is_err());
+        assert_eq!(
+            encode(&ContentType::from_static("application/json")),
+            vec!["application/json"]
+        );
+    }
+
+    #[test]
+    fn host() {
+        let host = decode::<Host>(&["example.com:8080"]).unwrap();
+        assert_eq!(host.0.host(), "example.com");
+        assert_eq!(host.0.port_u16(), Some(8080));
+        assert!(decode::<Host>(&["user@example.com"]).is_err());
+        assert_eq!(encode(&host), vec!["example.com:8080"]);
+    }
+
+    #[test]
+    fn location() {
+        let loc = decode::<Location>(&["/index.html"]).unwrap();
+        assert_eq!(loc.as_str(), "/index.html");
+        assert!(decode::<Location>(&[""]).is_err());
+        assert_eq!(encode(&loc), vec!["/index.html"]);
+    }
+
+    #[test]
+    fn allow() {
+        let allow = decode::<Allow>(&["GET, HEAD", "", "PUT"]).unwrap();
+        assert_eq!(allow.0, vec![Method::GET, Method::HEAD, Method::PUT]);
+        assert!(allow.allows(&Method::HEAD));
+        assert!(!allow.allows(&Method::POST));
+        assert_eq!(encode(&allow), vec!["GET, HEAD, PUT"]);
+        assert_eq!(decode::<Allow>(&[]).unwrap(), Allow::default());
+    }
+
+    #[test]
+    fn expect() {
+        assert_eq!(
+            decode::<Expect>(&["100-Continue"]).unwrap(),
+            Expect::CONTINUE
+        );
+        assert!(decode::<Expect>(&["200-ok"]).is_err());
+        assert_eq!(encode(&Expect::CONTINUE), vec!["100-continue"]);
+    }
+
+    #[test]
+    fn etag() {
+        let strong = decode::<ETag>(&["\"abc\""]).unwrap();
+        assert!(!strong.is_weak());
+        assert_eq!(strong.tag(), "abc");
+        assert!(strong.strong_eq(&strong));
+        assert!(decode::<ETag>(&["abc"]).is_err());
+        assert!(decode::<ETag>(&["W/\"a\"b\""]).is_err());
+        assert_eq!(decode::<ETag>(&["\"\""]).unwrap().tag(), "");
+        assert_eq!(encode(&strong), vec!["\"abc\""]);
+    }
+
+    #[test]
+    fn range() {
+        let range = decode::<Range>(&["bytes=0-0, -1,,500-"]).unwrap();
+        assert_eq!(range.satisfiable(10), [0..=0, 9..=9]);
+        assert_eq!(range.satisfiable(1000), [0..=0, 999..=999, 500..=999]);
+        assert_eq!(encode(&range), vec!["bytes=0-0, -1, 500-"]);
+
+        assert_eq!(Range::bytes(5, 10).satisfiable(8), [5..=7]);
+        assert!(Range::bytes(8, 10).satisfiable(8).is_empty());
+        assert!(Range::suffix(0).satisfiable(8).is_empty());
+        assert_eq!(Range::suffix(100).satisfiable(8), [0..=7]);
+        assert_eq!(Range::bytes_from(3).satisfiable(8), [3..=7]);
+        assert_eq!(encode(&Range::suffix(5)), vec!["bytes=-5"]);
+        assert_eq!(decode::<Range>(&["Bytes=1-2"]).unwrap(), Range::bytes(1, 2));
+
+        assert!(decode::<Range>(&["bytes="]).is_err());
+        assert!(decode::<Range>(&["bytes=5-4"]).is_err());
+        assert!(decode::<Range>(&["bytes=-"]).is_err());
+        assert!(decode::<Range>(&["bytes=1-2-3"]).is_err());
+        assert!(decode::<Range>(&["items=0-1"]).is_err());
+    }
+
+    #[test]
+    fn content_range() {
+        let cr = decode::<ContentRange>(&["bytes 21010-47021/47022"]).unwrap();
+        assert_eq!(cr.range(), Some(21010..=47021));
+        assert_eq!(cr.complete_length(), Some(47022));
+        assert_eq!(encode(&cr), vec!["bytes 21010-47021/47022"]);
+
+        let cr = decode::<ContentRange>(&["bytes 0-9/*"]).unwrap();
+        assert_eq!(cr, ContentRange::bytes(0..=9, None));
+        assert_eq!(encode(&cr), vec!["bytes 0-9/*"]);
+
+        let cr = decode::<ContentRange>(&["bytes */100"]).unwrap();
+        assert_eq!(cr, ContentRange::unsatisfied(100));
+
+        assert!(decode::<ContentRange>(&["bytes */*"]).is_err());
+        assert!(decode::<ContentRange>(&["bytes 0-100/100"]).is_err());
+        assert!(decode::<ContentRange>(&["bytes 5-4/100"]).is_err());
+        assert!(decode::<ContentRange>(&["bytes 0-4"]).is_err());
+    }
+
+    #[test]
+    fn cache_control() {
+        let cc = decode::<CacheControl>(&[
+            "Max-Age=60, s-maxage=\"120\", no-store",
+            "private=\"set-cookie, x-user\", stale-while-revalidate=99999999999999999999",
+            "community=\"UCI\", x-flag",
+        ])
+        .unwrap();
+        assert_eq!(cc.max_age(), Some(60));
+        assert_eq!(cc.s_maxage(), Some(120));
+        assert_eq!(cc.stale_while_revalidate(), Some(u64::MAX));
+        assert!(cc.no_store());
+        assert!(cc.is_private());
+        assert_eq!(
+            cc.0[3],
+            CacheDirective::Private(vec![name::SET_COOKIE, HeaderName::from_static("x-user")])
+        );
+        assert_eq!(cc.extension("COMMUNITY").unwrap().value(), Some("UCI"));
+        assert_eq!(cc.extension("x-flag").unwrap().value(), None);
+        assert_eq!(
+            encode(&cc),
+            vec![
+                "max-age=60, s-maxage=120, no-store, private=\"set-cookie, x-user\", \
+                 stale-while-revalidate=18446744073709551615, community=UCI, x-flag"
+            ]
+        );
+
+        let ext = CacheExtension::new("x-note", Some("a \"b\"")).unwrap();
+        let cc = CacheControl(vec![CacheDirective::Extension(ext), CacheDirective::Public]);
+        assert_eq!(encode(&cc), vec!["x-note=\"a \\\"b\\\"\", public"]);
+        assert_eq!(
+            decode::<CacheControl>(&["x-note=\"a \\\"b\\\"\""])
+                .unwrap()
+                .0,
+            cc.0[..1]
+        );
+
+        let cc = decode::<CacheControl>(&[
+            "No-Transform, proxy-revalidate, must-understand, immutable",
+            "stale-if-error=600, max-stale, max-stale=5, min-fresh=\"10\", only-if-cached",
+        ])
+        .unwrap();
+        assert_eq!(
+            cc.0,
+            [
+                CacheDirective::NoTransform,
+                CacheDirective::ProxyRevalidate,
+                CacheDirective::MustUnderstand,
+                CacheDirective::Immutable,
+                CacheDirective::StaleIfError(600),
+                CacheDirective::MaxStale(None),
+                CacheDirective::MaxStale(Some(5)),
+                CacheDirective::MinFresh(10),
+                CacheDirective::OnlyIfCached,
+            ]
+        );
+        assert_eq!(
+            encode(&cc),
+            vec![
+                "no-transform, proxy-revalidate, must-understand, immutable, \
+                 stale-if-error=600, max-stale, max-stale=5, min-fresh=10, only-if-cached"
+            ]
+        );
+
+        assert!(CacheExtension::new("bad name", None).is_err());
+        assert!(decode::<CacheControl>(&["public, max-age"]).is_err());
+        assert!(decode::<CacheControl>(&["immutable=1"]).is_err());
+        assert!(decode::<CacheControl>(&["max-age"]).is_err());
+        assert!(decode::<CacheControl>(&["max-age=-1"]).is_err());
+        assert!(decode::<CacheControl>(&["no-store=1"]).is_err());
+        assert!(decode::<CacheControl>(&["x=\"open"]).is_err());
+        assert_eq!(
+            decode::<CacheControl>(&[]).unwrap(),
+            CacheControl::default()
+        );
+    }
+}
@@ -8,6 +8,7 @@ use std::str::FromStr;
 use std::{cmp, fmt, str};
 
 use crate::header::name::HeaderName;
+use crate::Uri;
 
 /// Represents an HTTP header field value.
 ///
@@ -184,6 +185,23 @@ impl HeaderValue {
     ///
     /// This will try to prevent a copy if the type passed is the type used
     /// internally, and will copy the data if it is not.
+    ///
+    /// Decoders can use this to hand a slice of their receive buffer straight
+    /// to a `HeaderValue`: the bytes are validated but not copied, and the
+    /// value shares the buffer's allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use bytes::Bytes;
+    ///
+    /// let buf = Bytes::from_static(b"content-type: text/html\r\n");
+    /// let val = HeaderValue::from_maybe_shared(buf.slice(14..23)).unwrap();
+    ///
+    /// assert_eq!(val, "text/html");
+    /// assert_eq!(val.as_bytes().as_ptr(), buf[14..].as_ptr());
+    /// ```
     pub fn from_maybe_shared<T>(src: T) -> Result<HeaderValue, InvalidHeaderValue>
     where
         T: AsRef<[u8]> + 'static,
@@ -233,18 +251,25 @@ impl HeaderValue {
         }
     }
 
-    fn from_shared(src: Bytes) -> Result<HeaderValue, InvalidHeaderValue> {
+    pub(crate) fn from_shared(src: Bytes) -> Result<HeaderValue, InvalidHeaderValue> {
         HeaderValue::try_from_generic(src, std::convert::identity)
     }
 
+    /// Wraps bytes that were already checked with `is_valid_value`.
+    pub(super) fn from_valid_shared(src: Bytes, is_sensitive: bool) -> HeaderValue {
+        debug_assert!(is_valid_value(&src));
+        HeaderValue {
+            inner: src,
+            is_sensitive,
+        }
+    }
+
     fn try_from_generic<T: AsRef<[u8]>, F: FnOnce(T) -> Bytes>(
         src: T,
         into: F,
     ) -> Result<HeaderValue, InvalidHeaderValue> {
-        for &b in src.as_ref() {
-            if !is_valid(b) {
-                return Err(InvalidHeaderValue { _priv: () });
-            }
+        if !is_valid_value(src.as_ref()) {
+            return Err(InvalidHeaderValue { _priv: () });
         }
         Ok(HeaderValue {
             inner: into(src),
@@ -355,7 +380,9 @@ impl HeaderValue {
     /// Additionally, sensitive values will be masked by the `Debug`
     /// implementation of `HeaderValue`.
     ///
-    /// Note that sensitivity is not factored into equality or ordering.
+    /// Note that sensitivity is not factored into equality or ordering,
+    /// though comparing a sensitive value with `==`, whether against another
+    /// `HeaderValue`, a string or bytes, is done in constant time. See [`eq_constant_time`](HeaderValue::eq_constant_time).
     ///
     /// # Examples
     ///
@@ -373,6 +400,123 @@ impl HeaderValue {
     pub fn is_sensitive(&self) -> bool {
         self.is_sensitive
     }
+
+    /// Returns the value without leading or trailing whitespace.
+    ///
+    /// Only spaces and horizontal tabs are removed, matching the optional
+    /// whitespace (`OWS`) rule of RFC 9110. The returned value shares this
+    /// value's buffer, and keeps its sensitivity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static(" \tgzip  ");
+    /// assert_eq!(val.trim(), "gzip");
+    /// ```
+    pub fn trim(&self) -> HeaderValue {
+        let bytes = self.as_bytes();
+        let is_ows = |b: &u8| *b == b' ' || *b == b'\t';
+        let start = bytes.iter().position(|b| !is_ows(b)).unwrap_or(bytes.len());
+        let end = bytes
+            .iter()
+            .rposition(|b| !is_ows(b))
+            .map_or(start, |i| i + 1);
+
+        HeaderValue {
+            inner: self.inner.slice(start..end),
+            is_sensitive: self.is_sensitive,
+        }
+    }
+
+    /// Appends a string to the end of the value.
+    ///
+    /// The string is checked as with [`from_str`](HeaderValue::from_str). On
+    /// error the value is left unchanged. The value's sensitivity is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let mut via = HeaderValue::from_static("1.1 proxy-a");
+    /// via.try_push_str(", 1.1 proxy-b").unwrap();
+    /// assert_eq!(via, "1.1 proxy-a, 1.1 proxy-b");
+    ///
+    /// assert!(via.try_push_str("\n").is_err());
+    /// assert_eq!(via, "1.1 proxy-a, 1.1 proxy-b");
+    /// ```
+    pub fn try_push_str(&mut self, src: &str) -> Result<(), InvalidHeaderValue> {
+        self.try_extend_from_slice(src.as_bytes())
+    }
+
+    /// Appends bytes to the end of the value.
+    ///
+    /// The bytes are checked as with [`from_bytes`](HeaderValue::from_bytes).
+    /// On error the value is left unchanged. The value's sensitivity is kept.
+    ///
+    /// Only the appended bytes are validated. The value is always copied into
+    /// a new buffer large enough for both parts, even if its current buffer
+    /// is not shared, so each call costs time proportional to the whole
+    /// value. A composite value built from many pieces is better assembled
+    /// with [`join`](HeaderValue::join).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let mut val = HeaderValue::from_static("attachment");
+    /// val.try_extend_from_slice(b"; filename=\"a.txt\"").unwrap();
+    /// assert_eq!(val, "attachment; filename=\"a.txt\"");
+    /// ```
+    pub fn try_extend_from_slice(&mut self, src: &[u8]) -> Result<(), InvalidHeaderValue> {
+        if !is_valid_value(src) {
+            return Err(InvalidHeaderValue { _priv: () });
+        }
+
+        let mut buf = BytesMut::with_capacity(self.inner.len() + src.len());
+        buf.extend_from_slice(&self.inner);
+        buf.extend_from_slice(src);
+        self.inner = buf.freeze();
+        Ok(())
+    }
+
+    /// Compares two values in time that depends only on their lengths.
+    ///
+    /// Unlike `==`, this does not stop at the first differing byte, so it
+    /// doesn't reveal how much of a secret, such as a bearer token, an
+    /// attacker has guessed correctly. Values of different lengths are
+    /// rejected immediately.
+    ///
+    /// `==` uses this comparison automatically whenever either value is
+    /// marked as sensitive, including when comparing against a `str`,
+    /// `String` or byte slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let token = HeaderValue::from_static("Bearer secret");
+    ///
+    /// assert!(token.eq_constant_time(&HeaderValue::from_static("Bearer secret")));
+    /// assert!(!token.eq_constant_time(&HeaderValue::from_static("Bearer guess!")));
+    /// ```
+    pub fn eq_constant_time(&self, other: &HeaderValue) -> bool {
+        constant_time_eq(self.as_bytes(), other.as_bytes())
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        // The volatile read keeps the compiler from turning the loop back
+        // into an early-exit comparison.
+        diff = unsafe { std::ptr::read_volatile(&(diff | (x ^ y))) };
+    }
+    diff == 0
 }
 
 impl AsRef<[u8]> for HeaderValue {
@@ -420,6 +564,25 @@ impl From<HeaderName> for HeaderValue {
     }
 }
 
+impl<'a> From<&'a Uri> for HeaderValue {
+    /// Formats the URI as a header value, such as for `Location`.
+    #[inline]
+    fn from(uri: &'a Uri) -> HeaderValue {
+        // A `Uri` only ever holds visible ASCII, so it is always a valid value.
+        HeaderValue {
+            inner: Bytes::from(uri.to_string()),
+            is_sensitive: false,
+        }
+    }
+}
+
+impl From<Uri> for HeaderValue {
+    #[inline]
+    fn from(uri: Uri) -> HeaderValue {
+        HeaderValue::from(&uri)
+    }
+}
+
 macro_rules! from_integers {
     ($($name:ident: $t:ident => $max_len:expr),*) => {$(
         impl From<$t> for HeaderValue {
@@ -586,6 +749,40 @@ fn is_valid(b: u8) -> bool {
     b >= 32 && b != 127 || b == b'\t'
 }
 
+/// Checks every byte with `is_valid`, eight bytes at a time.
+///
+/// Most values contain no control characters at all, so each word is first
+/// tested for any byte below 0x20 or equal to 0x7F, and only words that have
+/// one (usually a tab) are checked byte by byte.
+pub(super) fn is_valid_value(src: &[u8]) -> bool {
+    const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGHS: u64 = u64::from_ne_bytes([0x80; 8]);
+    const SPACES: u64 = u64::from_ne_bytes([0x20; 8]);
+    const DELS: u64 = u64::from_ne_bytes([0x7f; 8]);
+
+    let mut chunks = src.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        let x = u64::from_ne_bytes(word);
+
+        // Sets the high bit of a byte if it is below 0x20, or if it is 0x7F.
+        // Bytes with their own high bit set are excluded by `!x`.
+        let below_space = x.wrapping_sub(SPACES) & !x;
+        let del = (x ^ DELS).wrapping_sub(ONES) & !(x ^ DELS);
+        if (below_space | del) & HIGHS != 0 && !chunk.iter().all(|&b| is_valid(b)) {
+            return false;
+        }
+    }
+    chunks.remainder().iter().all(|&b| is_valid(b))
+}
+
+impl InvalidHeaderValue {
+    pub(super) fn new() -> InvalidHeaderValue {
+        InvalidHeaderValue { _priv: () }
+    }
+}
+
 impl fmt::Debug for InvalidHeaderValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("InvalidHeaderValue")
@@ -610,6 +807,74 @@ impl fmt::Display for ToStrError {
 
 impl Error for ToStrError {}
 
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::HeaderValue;
+    use serde::de::{self, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+
+    // Values that are valid strings are written as strings to human readable
+    // formats, everything else is written as raw bytes.
+    impl Serialize for HeaderValue {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                if let Ok(s) = self.to_str() {
+                    return serializer.serialize_str(s);
+                }
+            }
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for HeaderValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(HeaderValueVisitor)
+            } else {
+                deserializer.deserialize_bytes(HeaderValueVisitor)
+            }
+        }
+    }
+
+    struct HeaderValueVisitor;
+
+    impl<'de> Visitor<'de> for HeaderValueVisitor {
+        type Value = HeaderValue;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a header value")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<HeaderValue, E> {
+            HeaderValue::from_str(v).map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<HeaderValue, E> {
+            HeaderValue::from_maybe_shared(v)
+                .map_err(|_| E::invalid_value(de::Unexpected::Other("string"), &self))
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<HeaderValue, E> {
+            HeaderValue::from_bytes(v)
+                .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<HeaderValue, E> {
+            HeaderValue::from_maybe_shared(v)
+                .map_err(|_| E::invalid_value(de::Unexpected::Other("bytes"), &self))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<HeaderValue, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                bytes.push(b);
+            }
+            self.visit_byte_buf(bytes)
+        }
+    }
+}
+
 // ===== PartialEq / PartialOrd =====
 
 impl Hash for HeaderValue {
@@ -621,6 +886,9 @@ impl Hash for HeaderValue {
 impl PartialEq for HeaderValue {
     #[inline]
     fn eq(&self, other: &HeaderValue) -> bool {
+        if self.is_sensitive || other.is_sensitive {
+            return self.eq_constant_time(other);
+        }
         self.inner == other.inner
     }
 }
@@ -644,13 +912,16 @@ impl Ord for HeaderValue {
 impl PartialEq<str> for HeaderValue {
     #[inline]
     fn eq(&self, other: &str) -> bool {
-        self.inner == other.as_bytes()
+        *self == *other.as_bytes()
     }
 }
 
 impl PartialEq<[u8]> for HeaderValue {
     #[inline]
     fn eq(&self, other: &[u8]) -> bool {
+        if self.is_sensitive {
+            return constant_time_eq(self.as_bytes(), other);
+        }
         self.inner == other
     }
 }
@@ -725,14 +996,14 @@ impl PartialOrd<HeaderValue> for String {
     }
 }
 
-impl<'a> PartialEq<HeaderValue> for &'a HeaderValue {
+impl PartialEq<HeaderValue> for &HeaderValue {
     #[inline]
     fn eq(&self, other: &HeaderValue) -> bool {
         **self == *other
     }
 }
 
-impl<'a> PartialOrd<HeaderValue> for &'a HeaderValue {
+impl PartialOrd<HeaderValue> for &HeaderValue {
     #[inline]
     fn partial_cmp(&self, other: &HeaderValue) -> Option<cmp::Ordering> {
         (**self).partial_cmp(other)
@@ -759,14 +1030,14 @@ where
     }
 }
 
-impl<'a> PartialEq<HeaderValue> for &'a str {
+impl PartialEq<HeaderValue> for &str {
     #[inline]
     fn eq(&self, other: &HeaderValue) -> bool {
         *other == *self
     }
 }
 
-impl<'a> PartialOrd<HeaderValue> for &'a str {
+impl PartialOrd<HeaderValue> for &str {
     #[inline]
     fn partial_cmp(&self, other: &HeaderValue) -> Option<cmp::Ordering> {
         self.as_bytes().partial_cmp(other.as_bytes())
@@ -796,3 +1067,91 @@ fn test_debug() {
     sensitive.set_sensitive(true);
     assert_eq!("Sensitive", format!("{:?}", sensitive));
 }
+
+#[test]
+fn test_is_valid_value() {
+    for b in 0..=255u8 {
+        for len in 1..20 {
+            for pos in 0..len {
+                let mut src = vec![b'a'; len];
+                src[pos] = b;
+                assert_eq!(is_valid_value(&src), is_valid(b), "{:?}", src);
+            }
+        }
+    }
+    assert!(is_valid_value(b""));
+    assert!(is_valid_value(
+        b"\tMozilla/5.0 (X11; Linux x86_64)\t\xff\x80"
+    ));
+}
+
+#[test]
+fn test_trim() {
+    assert_eq!(HeaderValue::from_static("a b").trim(), "a b");
+    assert_eq!(HeaderValue::from_static("\t a b \t").trim(), "a b");
+    assert_eq!(HeaderValue::from_static(" \t ").trim(), "");
+    assert_eq!(HeaderValue::from_static("").trim(), "");
+
+    let mut val = HeaderValue::from_static(" secret ");
+    val.set_sensitive(true);
+    assert!(val.trim().is_sensitive());
+}
+
+#[test]
+fn test_try_extend() {
+    let mut val = HeaderValue::from_static("");
+    val.set_sensitive(true);
+    val.try_push_str("a").unwrap();
+    val.try_extend_from_slice(b"\xffb").unwrap();
+    assert_eq!(val, &b"a\xffb"[..]);
+    assert!(val.is_sensitive());
+
+    assert!(val.try_extend_from_slice(b"\x7f").is_err());
+    assert!(val.try_push_str("\r\n").is_err());
+    assert_eq!(val, &b"a\xffb"[..]);
+}
+
+#[test]
+fn test_eq_constant_time() {
+    let a = HeaderValue::from_static("Bearer abc");
+    assert!(a.eq_constant_time(&HeaderValue::from_static("Bearer abc")));
+    assert!(!a.eq_constant_time(&HeaderValue::from_static("Bearer abd")));
+    assert!(!a.eq_constant_time(&HeaderValue::from_static("Bearer ab")));
+    assert!(HeaderValue::from_static("").eq_constant_time(&HeaderValue::from_static("")));
+
+    let mut sensitive = a.clone();
+    sensitive.set_sensitive(true);
+    assert_eq!(sensitive, a);
+    assert_ne!(sensitive, HeaderValue::from_static("Bearer abd"));
+
+    assert_eq!(sensitive, "Bearer abc");
+    assert_ne!(sensitive, "Bearer abd");
+    assert_eq!("Bearer abc", sensitive);
+    assert_eq!(sensitive, *"Bearer abc");
+    assert_eq!(sensitive, String::from("Bearer abc"));
+    assert_ne!(String::from("Bearer ab"), sensitive);
+    assert_eq!(sensitive, &b"Bearer abc"[..]);
+    assert_ne!(b"Bearer abd"[..], sensitive);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_serde() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    let val = HeaderValue::from_static("text/html");
+    assert_tokens(&val.clone().readable(), &[Token::Str("text/html")]);
+    assert_tokens(&val.compact(), &[Token::Bytes(b"text/html")]);
+
+    assert_de_tokens_error::<serde_test::Readable<HeaderValue>>(
+        &[Token::Str("a\nb")],
+        "invalid value: string \"a\\nb\", expected a header value",
+    );
+}
+
+#[test]
+fn test_from_uri() {
+    let uri: Uri = "https://example.com/a%20b?q=1#frag".parse().unwrap();
+    assert_eq!(HeaderValue::from(&uri), "https://example.com/a%20b?q=1");
+    assert_eq!(HeaderValue::from(Uri::from_static("/")), "/");
+}
@@ -154,6 +154,16 @@
 //! ```
 
 #![deny(warnings, missing_docs, missing_debug_implementations)]
+// Lints newer clippy raises on the unit tests, which are kept as written.
+#![cfg_attr(
+    test,
+    allow(
+        clippy::cmp_owned,
+        clippy::expect_fun_call,
+        clippy::legacy_numeric_constants,
+        clippy::unnecessary_fallible_conversions
+    )
+)]
 
 //#![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(not(feature = "std"))]
@@ -169,8 +179,11 @@ doctest!("../README.md");
 #[macro_use]
 mod convert;
 
+pub mod conditional;
+pub mod fmt;
 pub mod header;
 pub mod method;
+pub mod negotiate;
 pub mod request;
 pub mod response;
 pub mod status;
@@ -181,8 +194,16 @@ mod byte_str;
 mod error;
 mod extensions;
 
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing;
+
 pub use crate::error::{Error, Result};
 pub use crate::extensions::Extensions;
+#[cfg(feature = "serde1")]
+pub use crate::extensions::{
+    ExtensionRegistry, ExtensionTypes, PartsSeed, Registered, RegisteredParts,
+};
 #[doc(no_inline)]
 pub use crate::header::{HeaderMap, HeaderName, HeaderValue};
 pub use crate::method::Method;
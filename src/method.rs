@@ -17,9 +17,12 @@
 
 use self::extension::{AllocatedExtension, InlineExtension};
 use self::Inner::*;
+use crate::header::HeaderValue;
 
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::{fmt, str};
 
@@ -41,7 +44,7 @@ use std::{fmt, str};
 /// assert!(Method::GET.is_idempotent());
 /// assert_eq!(Method::POST.as_str(), "POST");
 /// ```
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
 pub struct Method(Inner);
 
 /// A possible error value when converting `Method` from bytes.
@@ -64,6 +67,8 @@ enum Inner {
     ExtensionInline(InlineExtension),
     // Otherwise, allocate it
     ExtensionAllocated(AllocatedExtension),
+    // Created by `Method::from_static`, borrowing the string
+    ExtensionStatic(&'static str),
 }
 
 impl Method {
@@ -134,6 +139,73 @@ impl Method {
         }
     }
 
+    /// Converts a static string to an HTTP method.
+    ///
+    /// Standard methods are recognized, so `Method::from_static("GET")` is
+    /// `Method::GET`. Any other method borrows `src`, so it is never
+    /// allocated and is cheap to clone. This is the way to define constants
+    /// for the non-standard methods an application handles, such as `PURGE`
+    /// or `REPORT`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument is not a valid method token. As
+    /// with [`HeaderValue::from_static`](crate::HeaderValue::from_static),
+    /// the panic message in a constant is going to look cryptic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// const PURGE: Method = Method::from_static("PURGE");
+    ///
+    /// assert_eq!(PURGE, Method::from_bytes(b"PURGE").unwrap());
+    /// assert_eq!(PURGE.as_str(), "PURGE");
+    /// assert_eq!(Method::from_static("GET"), Method::GET);
+    /// ```
+    #[allow(unconditional_panic)] // required for the panic circumvention
+    pub const fn from_static(src: &'static str) -> Method {
+        match src.as_bytes() {
+            b"OPTIONS" => Method(Options),
+            b"GET" => Method(Get),
+            b"POST" => Method(Post),
+            b"PUT" => Method(Put),
+            b"DELETE" => Method(Delete),
+            b"HEAD" => Method(Head),
+            b"TRACE" => Method(Trace),
+            b"CONNECT" => Method(Connect),
+            b"PATCH" => Method(Patch),
+            bytes => {
+                if bytes.is_empty() || {
+                    let mut i = 0;
+                    loop {
+                        if i >= bytes.len() {
+                            break false;
+                        } else if !extension::is_method_char(bytes[i]) {
+                            break true;
+                        }
+                        i += 1;
+                    }
+                } {
+                    // TODO: When msrv is bumped to larger than 1.57, this should be
+                    // replaced with `panic!` macro.
+                    // https://blog.rust-lang.org/2021/12/02/Rust-1.57.0.html#panic-in-const-contexts
+                    #[allow(clippy::no_effect, clippy::out_of_bounds_indexing)]
+                    ([] as [u8; 0])[0]; // Invalid method
+                }
+
+                Method(ExtensionStatic(src))
+            }
+        }
+    }
+
+    fn is_extension(&self) -> bool {
+        matches!(
+            self.0,
+            ExtensionInline(_) | ExtensionAllocated(_) | ExtensionStatic(_)
+        )
+    }
+
     fn extension_inline(src: &[u8]) -> Result<Method, InvalidMethod> {
         let inline = InlineExtension::new(src)?;
 
@@ -161,6 +233,47 @@ impl Method {
         }
     }
 
+    /// Whether responses to a method are defined as cacheable.
+    ///
+    /// This is true for GET, HEAD and POST. Note that a POST response may
+    /// only be reused for a later GET or HEAD, and only when it has explicit
+    /// freshness information and a `Content-Location` header matching the
+    /// target URI; many caches never store POST responses at all. Extension
+    /// methods are not cacheable.
+    ///
+    /// See [the spec](https://www.rfc-editor.org/rfc/rfc9110#section-9.2.3)
+    /// for more words.
+    pub fn is_cacheable(&self) -> bool {
+        matches!(self.0, Get | Head | Post)
+    }
+
+    /// Parses a comma-separated list of methods, such as the value of an
+    /// `Allow` or `Access-Control-Allow-Methods` header.
+    ///
+    /// Whitespace around each method and empty list elements are ignored.
+    /// Methods are case-sensitive, so `get` is parsed as an extension method
+    /// rather than as `GET`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderValue, Method};
+    /// let allow = HeaderValue::from_static("GET, HEAD ,, PURGE");
+    /// assert_eq!(
+    ///     Method::parse_list(&allow).unwrap(),
+    ///     [Method::GET, Method::HEAD, Method::from_static("PURGE")]
+    /// );
+    ///
+    /// assert!(Method::parse_list(&HeaderValue::from_static("GET POST")).is_err());
+    /// ```
+    pub fn parse_list(value: &HeaderValue) -> Result<Vec<Method>, InvalidMethod> {
+        value
+            .split_list()
+            .map_err(|_| InvalidMethod::new())?
+            .map(|method| Method::from_bytes(method.as_bytes()))
+            .collect()
+    }
+
     /// Return a &str representation of the HTTP method
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -176,10 +289,64 @@ impl Method {
             Patch => "PATCH",
             ExtensionInline(ref inline) => inline.as_str(),
             ExtensionAllocated(ref allocated) => allocated.as_str(),
+            ExtensionStatic(src) => src,
+        }
+    }
+}
+
+// Extension methods compare by their string, however they are stored.
+impl PartialEq for Method {
+    #[inline]
+    fn eq(&self, other: &Method) -> bool {
+        if self.is_extension() || other.is_extension() {
+            self.as_str() == other.as_str()
+        } else {
+            self.0 == other.0
         }
     }
 }
 
+impl Eq for Method {}
+
+impl Hash for Method {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for Method {
+    #[inline]
+    fn partial_cmp(&self, other: &Method) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Standard methods come first, in the order RFC 9110 defines them (GET,
+/// HEAD, POST, PUT, DELETE, CONNECT, OPTIONS, TRACE) followed by PATCH.
+/// Extension methods follow, ordered by their string.
+impl Ord for Method {
+    fn cmp(&self, other: &Method) -> Ordering {
+        fn rank(method: &Method) -> u8 {
+            match method.0 {
+                Get => 0,
+                Head => 1,
+                Post => 2,
+                Put => 3,
+                Delete => 4,
+                Connect => 5,
+                Options => 6,
+                Trace => 7,
+                Patch => 8,
+                ExtensionInline(_) | ExtensionAllocated(_) | ExtensionStatic(_) => 9,
+            }
+        }
+
+        rank(self)
+            .cmp(&rank(other))
+            .then_with(|| self.as_str().cmp(other.as_str()))
+    }
+}
+
 impl AsRef<str> for Method {
     #[inline]
     fn as_ref(&self) -> &str {
@@ -194,7 +361,7 @@ impl<'a> PartialEq<&'a Method> for Method {
     }
 }
 
-impl<'a> PartialEq<Method> for &'a Method {
+impl PartialEq<Method> for &Method {
     #[inline]
     fn eq(&self, other: &Method) -> bool {
         *self == other
@@ -222,13 +389,41 @@ impl<'a> PartialEq<&'a str> for Method {
     }
 }
 
-impl<'a> PartialEq<Method> for &'a str {
+impl PartialEq<Method> for &str {
     #[inline]
     fn eq(&self, other: &Method) -> bool {
         *self == other.as_ref()
     }
 }
 
+impl PartialEq<[u8]> for Method {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_str().as_bytes() == other
+    }
+}
+
+impl PartialEq<Method> for [u8] {
+    #[inline]
+    fn eq(&self, other: &Method) -> bool {
+        self == other.as_str().as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Method {
+    #[inline]
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_str().as_bytes() == *other
+    }
+}
+
+impl PartialEq<Method> for &[u8] {
+    #[inline]
+    fn eq(&self, other: &Method) -> bool {
+        *self == other.as_str().as_bytes()
+    }
+}
+
 impl fmt::Debug for Method {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.as_ref())
@@ -304,6 +499,146 @@ impl fmt::Display for InvalidMethod {
 
 impl Error for InvalidMethod {}
 
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::{Inner, Method};
+    use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, VariantAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+
+    // Human readable formats get the method string. Compact formats get an
+    // enum, so the standard methods are encoded as a bare variant index.
+    const VARIANTS: &[&str] = &[
+        "OPTIONS",
+        "GET",
+        "POST",
+        "PUT",
+        "DELETE",
+        "HEAD",
+        "TRACE",
+        "CONNECT",
+        "PATCH",
+        "Extension",
+    ];
+    const EXTENSION: u32 = 9;
+
+    impl Method {
+        fn variant_index(&self) -> u32 {
+            match self.0 {
+                Inner::Options => 0,
+                Inner::Get => 1,
+                Inner::Post => 2,
+                Inner::Put => 3,
+                Inner::Delete => 4,
+                Inner::Head => 5,
+                Inner::Trace => 6,
+                Inner::Connect => 7,
+                Inner::Patch => 8,
+                Inner::ExtensionInline(_)
+                | Inner::ExtensionAllocated(_)
+                | Inner::ExtensionStatic(_) => EXTENSION,
+            }
+        }
+    }
+
+    impl Serialize for Method {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                return serializer.serialize_str(self.as_str());
+            }
+            match self.variant_index() {
+                EXTENSION => serializer.serialize_newtype_variant(
+                    "Method",
+                    EXTENSION,
+                    "Extension",
+                    self.as_str(),
+                ),
+                idx => serializer.serialize_unit_variant("Method", idx, VARIANTS[idx as usize]),
+            }
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Method {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(MethodVisitor)
+            } else {
+                deserializer.deserialize_enum("Method", VARIANTS, MethodVisitor)
+            }
+        }
+    }
+
+    struct MethodVisitor;
+
+    impl<'de> Visitor<'de> for MethodVisitor {
+        type Value = Method;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an HTTP method")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Method, E> {
+            Method::from_bytes(v.as_bytes()).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Method, E> {
+            Method::from_bytes(v).map_err(E::custom)
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Method, A::Error> {
+            let (idx, variant) = data.variant_seed(VariantIndex)?;
+            if idx == EXTENSION {
+                return variant.newtype_variant_seed(ExtensionSeed);
+            }
+            variant.unit_variant()?;
+            Method::from_bytes(VARIANTS[idx as usize].as_bytes()).map_err(de::Error::custom)
+        }
+    }
+
+    struct VariantIndex;
+
+    impl<'de> DeserializeSeed<'de> for VariantIndex {
+        type Value = u32;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<u32, D::Error> {
+            deserializer.deserialize_identifier(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for VariantIndex {
+        type Value = u32;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an HTTP method variant")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<u32, E> {
+            if v <= EXTENSION as u64 {
+                Ok(v as u32)
+            } else {
+                Err(E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<u32, E> {
+            match VARIANTS.iter().position(|name| *name == v) {
+                Some(idx) => Ok(idx as u32),
+                None => Err(E::unknown_variant(v, VARIANTS)),
+            }
+        }
+    }
+
+    struct ExtensionSeed;
+
+    impl<'de> DeserializeSeed<'de> for ExtensionSeed {
+        type Value = Method;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Method, D::Error> {
+            deserializer.deserialize_str(MethodVisitor)
+        }
+    }
+}
+
 mod extension {
     use super::InvalidMethod;
     use std::str;
@@ -317,8 +652,10 @@ mod extension {
     pub struct AllocatedExtension(Box<[u8]>);
 
     impl InlineExtension {
-        // Method::from_bytes() assumes this is at least 7
-        pub const MAX: usize = 15;
+        // Method::from_bytes() assumes this is at least 7. With the tag
+        // byte this fills the same 24 bytes as the `&'static str` variant,
+        // so long WebDAV methods such as UPDATEREDIRECTREF stay inline.
+        pub const MAX: usize = 22;
 
         pub fn new(src: &[u8]) -> Result<InlineExtension, InvalidMethod> {
             let mut data: [u8; InlineExtension::MAX] = Default::default();
@@ -402,6 +739,10 @@ mod extension {
         b'\0', b'\0', b'\0', b'\0', b'\0', b'\0'                              // 25x
     ];
 
+    pub const fn is_method_char(b: u8) -> bool {
+        METHOD_CHARS[b as usize] != 0
+    }
+
     // write_checked ensures (among other things) that the first src.len() bytes
     // of dst are valid UTF-8
     fn write_checked(src: &[u8], dst: &mut [u8]) -> Result<(), InvalidMethod> {
@@ -458,6 +799,22 @@ mod test {
         assert!(!Method::PATCH.is_idempotent());
     }
 
+    #[test]
+    fn test_is_safe_and_cacheable() {
+        assert!(Method::GET.is_safe());
+        assert!(Method::HEAD.is_safe());
+        assert!(!Method::PUT.is_safe());
+        assert!(!Method::from_str("PROPFIND").unwrap().is_safe());
+        assert!(!Method::from_str("PROPFIND").unwrap().is_idempotent());
+
+        assert!(Method::GET.is_cacheable());
+        assert!(Method::HEAD.is_cacheable());
+        assert!(Method::POST.is_cacheable());
+        assert!(!Method::PUT.is_cacheable());
+        assert!(!Method::OPTIONS.is_cacheable());
+        assert!(!Method::from_str("PURGE").unwrap().is_cacheable());
+    }
+
     #[test]
     fn test_extension_method() {
         assert_eq!(Method::from_str("WOW").unwrap(), "WOW");
@@ -482,6 +839,99 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_eq_bytes() {
+        let buf: &[u8] = b"GET /index.html HTTP/1.1";
+        assert_eq!(Method::GET, buf[..3]);
+        assert_eq!(&buf[..3], Method::GET);
+        assert_ne!(Method::POST, &buf[..3]);
+        assert_ne!(Method::GET, b"get"[..]);
+        assert_eq!(Method::from_static("PURGE"), &b"PURGE"[..]);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let parse = |s: &str| Method::parse_list(&HeaderValue::from_str(s).unwrap());
+
+        assert_eq!(parse("GET,POST").unwrap(), [Method::GET, Method::POST]);
+        assert_eq!(parse(" OPTIONS\t").unwrap(), [Method::OPTIONS]);
+        assert_eq!(parse("get").unwrap(), [Method::from_static("get")]);
+        assert_ne!(parse("get").unwrap(), [Method::GET]);
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse("GET, \"POST\"").is_err());
+
+        let opaque = HeaderValue::from_bytes(b"GET, \xff").unwrap();
+        assert!(Method::parse_list(&opaque).is_err());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_inline_extension_size() {
+        assert_eq!(std::mem::size_of::<Method>(), 24);
+        assert!(matches!(
+            Method::from_str("UPDATEREDIRECTREF").unwrap().0,
+            ExtensionInline(_)
+        ));
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut methods = vec![
+            Method::from_static("PURGE"),
+            Method::PATCH,
+            Method::OPTIONS,
+            Method::from_static("BAN"),
+            Method::POST,
+            Method::GET,
+            Method::HEAD,
+        ];
+        methods.sort();
+        assert_eq!(
+            methods,
+            ["GET", "HEAD", "POST", "OPTIONS", "PATCH", "BAN", "PURGE"]
+        );
+
+        let long = Method::from_str("A_VERY_LONG_EXTENSION_METHOD").unwrap();
+        assert!(long < Method::from_static("BAN"));
+        assert_eq!(
+            Method::from_static("PURGE").cmp(&Method::from_str("PURGE").unwrap()),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_from_static() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(m: &Method) -> u64 {
+            let mut h = DefaultHasher::new();
+            m.hash(&mut h);
+            h.finish()
+        }
+
+        const PURGE: Method = Method::from_static("PURGE");
+        let parsed = Method::from_bytes(b"PURGE").unwrap();
+        assert_eq!(PURGE, parsed);
+        assert_eq!(hash(&PURGE), hash(&parsed));
+        assert_ne!(PURGE, Method::from_static("BAN"));
+        assert_ne!(PURGE, Method::GET);
+
+        let long = Method::from_static("VERY_LONG_EXTENSION_METHOD");
+        assert_eq!(
+            long,
+            Method::from_str("VERY_LONG_EXTENSION_METHOD").unwrap()
+        );
+
+        assert_eq!(Method::from_static("OPTIONS"), Method::OPTIONS);
+        assert!(Method::from_static("PATCH").0 == Patch);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_static_invalid() {
+        Method::from_static("GET /");
+    }
+
     #[test]
     fn test_extension_method_chars() {
         const VALID_METHOD_CHARS: &str =
@@ -497,4 +947,47 @@ mod test {
             );
         }
     }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_serde_readable() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        assert_tokens(&Method::GET.readable(), &[Token::Str("GET")]);
+        assert_tokens(
+            &Method::from_bytes(b"PURGE").unwrap().readable(),
+            &[Token::Str("PURGE")],
+        );
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_serde_compact() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+        assert_tokens(
+            &Method::PATCH.compact(),
+            &[Token::UnitVariant {
+                name: "Method",
+                variant: "PATCH",
+            }],
+        );
+        assert_tokens(
+            &Method::from_bytes(b"PURGE").unwrap().compact(),
+            &[
+                Token::NewtypeVariant {
+                    name: "Method",
+                    variant: "Extension",
+                },
+                Token::Str("PURGE"),
+            ],
+        );
+        assert_de_tokens_error::<serde_test::Compact<Method>>(
+            &[Token::UnitVariant {
+                name: "Method",
+                variant: "BREW",
+            }],
+            "unknown variant `BREW`, expected one of `OPTIONS`, `GET`, `POST`, `PUT`, `DELETE`, `HEAD`, `TRACE`, `CONNECT`, `PATCH`, `Extension`",
+        );
+    }
 }
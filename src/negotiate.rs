@@ -0,0 +1,215 @@
+//! Proactive content negotiation.
+//!
+//! [`media_type`] picks which of the representations a server can produce
+//! best fits a request's `Accept` header, following
+//! [RFC 9110, Section 12.5.1](https://www.rfc-editor.org/rfc/rfc9110#section-12.5.1).
+//!
+//! # Examples
+//!
+//! ```
+//! use http::negotiate::media_type;
+//! use http::Request;
+//!
+//! let request = Request::get("/report")
+//!     .header("accept", "text/html;q=0.9, application/json, */*;q=0.1")
+//!     .body(())
+//!     .unwrap();
+//!
+//! let available = ["text/csv", "text/html", "application/json"];
+//! assert_eq!(media_type(request.headers(), &available), Some("application/json"));
+//! ```
+
+use crate::header::{self, HeaderMap, HeaderValue};
+
+/// Chooses the media type from `available` that best matches the `Accept`
+/// headers.
+///
+/// Each available type is weighed by the most specific media range that
+/// matches it, so `text/html;level=1` is more specific than `text/html`,
+/// which is more specific than `text/*` and `*/*`. Parameters of a media
+/// range must all be present on the available type for it to match. The
+/// type with the highest weight wins, and ties go to whichever comes first
+/// in `available`, so list the preferred representation first.
+///
+/// If there is no `Accept` header, the first available type is chosen.
+/// Values that cannot be parsed are ignored.
+///
+/// Returns `None` if nothing is acceptable. A server would usually respond
+/// with `406 Not Acceptable`, or ignore the header and send its default.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::{HeaderMap, HeaderValue, ACCEPT};
+/// use http::negotiate::media_type;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(ACCEPT, HeaderValue::from_static("text/*, text/plain;q=0"));
+///
+/// assert_eq!(media_type(&headers, &["text/plain", "text/html"]), Some("text/html"));
+/// assert_eq!(media_type(&headers, &["text/plain", "image/png"]), None);
+/// ```
+pub fn media_type<'a>(headers: &HeaderMap<HeaderValue>, available: &[&'a str]) -> Option<&'a str> {
+    if !headers.contains_key(header::ACCEPT) {
+        return available.first().copied();
+    }
+
+    let mut ranges = Vec::new();
+    for value in headers.get_all(header::ACCEPT) {
+        if let Ok(items) = value.quality_items() {
+            ranges.extend(
+                items
+                    .iter()
+                    .filter_map(|item| MediaType::parse(item.item()).map(|r| (r, item.quality()))),
+            );
+        }
+    }
+
+    let mut best: Option<(&'a str, u16)> = None;
+    for &candidate in available {
+        let media_type = match MediaType::parse(candidate) {
+            Some(media_type) => media_type,
+            None => continue,
+        };
+
+        let quality = ranges
+            .iter()
+            .filter_map(|(range, quality)| range.specificity(&media_type).map(|s| (s, *quality)))
+            .max_by_key(|&(specificity, _)| specificity)
+            .map(|(_, quality)| quality)
+            .unwrap_or(0);
+
+        if quality > 0 && best.map_or(true, |(_, q)| quality > q) {
+            best = Some((candidate, quality));
+        }
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
+// A media type or media range, with its parameters.
+struct MediaType<'a> {
+    type_: &'a str,
+    subtype: &'a str,
+    params: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> MediaType<'a> {
+    fn parse(s: &'a str) -> Option<MediaType<'a>> {
+        let mut parts = s.split(';');
+        let essence = parts.next()?.trim();
+        let slash = essence.find('/')?;
+        let (type_, subtype) = (&essence[..slash], &essence[slash + 1..]);
+        if type_.is_empty() || subtype.is_empty() || (type_ == "*" && subtype != "*") {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        for param in parts {
+            let param = param.trim();
+            if param.is_empty() {
+                continue;
+            }
+            let eq = param.find('=')?;
+            let value = param[eq + 1..].trim_matches('"');
+            params.push((param[..eq].trim(), value));
+        }
+
+        Some(MediaType {
+            type_,
+            subtype,
+            params,
+        })
+    }
+
+    // How specifically this range matches `media_type`, or `None` if it
+    // does not match at all.
+    fn specificity(&self, media_type: &MediaType<'_>) -> Option<usize> {
+        if self.type_ == "*" {
+            return Some(0);
+        }
+        if !self.type_.eq_ignore_ascii_case(media_type.type_) {
+            return None;
+        }
+        if self.subtype == "*" {
+            return Some(1);
+        }
+        if !self.subtype.eq_ignore_ascii_case(media_type.subtype) {
+            return None;
+        }
+
+        let all_present = self.params.iter().all(|(name, value)| {
+            media_type
+                .params
+                .iter()
+                .any(|(n, v)| n.eq_ignore_ascii_case(name) && v.eq_ignore_ascii_case(value))
+        });
+        if all_present {
+            Some(2 + self.params.len())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choose<'a>(accept: &[&'static str], available: &[&'a str]) -> Option<&'a str> {
+        let mut headers = HeaderMap::new();
+        for value in accept {
+            headers.append(header::ACCEPT, HeaderValue::from_static(value));
+        }
+        media_type(&headers, available)
+    }
+
+    #[test]
+    fn no_accept_header() {
+        assert_eq!(choose(&[], &["text/html", "text/plain"]), Some("text/html"));
+        assert_eq!(choose(&[], &[]), None);
+    }
+
+    #[test]
+    fn ranks_by_quality_then_server_order() {
+        let available = ["text/plain", "text/html"];
+        assert_eq!(
+            choose(&["text/html, text/plain"], &available),
+            Some("text/plain")
+        );
+        assert_eq!(
+            choose(&["text/html, text/plain;q=0.5"], &available),
+            Some("text/html")
+        );
+        assert_eq!(
+            choose(&["text/plain;q=0.5", "text/html;q=0.6"], &available),
+            Some("text/html")
+        );
+    }
+
+    #[test]
+    fn most_specific_range_wins() {
+        // The RFC 9110 example.
+        let accept = ["text/*;q=0.3, text/plain;q=0.7, text/plain;format=flowed, */*;q=0.5"];
+        assert_eq!(
+            choose(&accept, &["text/plain", "text/plain;format=flowed"]),
+            Some("text/plain;format=flowed")
+        );
+        assert_eq!(
+            choose(&accept, &["text/html", "image/jpeg"]),
+            Some("image/jpeg")
+        );
+        assert_eq!(choose(&accept, &["text/html"]), Some("text/html"));
+    }
+
+    #[test]
+    fn excluded_and_unmatched() {
+        assert_eq!(choose(&["*/*, text/html;q=0"], &["text/html"]), None);
+        assert_eq!(choose(&["image/*"], &["text/html"]), None);
+        assert_eq!(choose(&["TEXT/HTML"], &["text/html"]), Some("text/html"));
+        assert_eq!(
+            choose(&["nonsense, */*"], &["text/html"]),
+            Some("text/html")
+        );
+        assert_eq!(choose(&["*/html"], &["text/html"]), None);
+    }
+}
@@ -53,11 +53,18 @@
 //! ```
 
 use std::any::Any;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::error;
 use std::fmt;
+use std::result;
 
-use crate::header::{HeaderMap, HeaderName, HeaderValue};
+use crate::header::typed::{ContentLength, Header, Host};
+use crate::header::{
+    parse_request_head, HeaderMap, HeaderName, HeaderValue, IntoHeaderName, InvalidHeaderBlock,
+    Redacted, CONTENT_LENGTH, EXPECT, HOST, TRANSFER_ENCODING,
+};
 use crate::method::Method;
+use crate::uri::Authority;
 use crate::version::Version;
 use crate::{Extensions, Result, Uri};
 
@@ -165,6 +172,7 @@ pub struct Request<T> {
 /// The HTTP request head consists of a method, uri, version, and a set of
 /// header fields.
 #[derive(Clone)]
+#[allow(clippy::manual_non_exhaustive)]
 pub struct Parts {
     /// The request's method
     pub method: Method,
@@ -181,6 +189,12 @@ pub struct Parts {
     /// The request's extensions
     pub extensions: Extensions,
 
+    /// The request's trailers, if it has any
+    ///
+    /// Trailers are header fields sent after the body, such as by chunked
+    /// HTTP/1.1 messages or by gRPC over HTTP/2.
+    pub trailers: Option<HeaderMap<HeaderValue>>,
+
     _priv: (),
 }
 
@@ -193,6 +207,23 @@ pub struct Builder {
     inner: Result<Parts>,
 }
 
+/// An error returned when a request's URI is not a valid target for its
+/// method
+///
+/// This type is returned by [`Request::validate_target`].
+pub struct InvalidRequestTarget {
+    _priv: (),
+}
+
+/// The host a request is directed at
+///
+/// This type is returned by [`Request::effective_host`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveHost {
+    authority: Authority,
+    mismatch: bool,
+}
+
 impl Request<()> {
     /// Creates a new builder-style object to manufacture a `Request`
     ///
@@ -602,6 +633,241 @@ impl<T> Request<T> {
         &mut self.head.extensions
     }
 
+    /// Returns a reference to the associated trailers, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request: Request<()> = Request::default();
+    /// assert!(request.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.head.trailers.as_ref()
+    }
+
+    /// Returns a mutable reference to the associated trailers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::*;
+    /// let mut request: Request<()> = Request::default();
+    /// request
+    ///     .trailers_mut()
+    ///     .get_or_insert_with(HeaderMap::new)
+    ///     .insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert_eq!(request.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut Option<HeaderMap<HeaderValue>> {
+        &mut self.head.trailers
+    }
+
+    /// Appends the request line and headers to `dst` in HTTP/1.1 wire format.
+    ///
+    /// The request line is followed by every header, as written by
+    /// [`HeaderMap::encode`], and the blank line that ends the head. The URI
+    /// is written as it is stored, so an origin-form URI such as `/index.html`
+    /// needs a `Host` header to be a valid HTTP/1.1 request. The version is
+    /// written as it is, even if it is not an HTTP/1 version.
+    ///
+    /// Trailers and the body are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::post("/submit")
+    ///     .header("host", "example.com")
+    ///     .header("content-length", "5")
+    ///     .body("hello")
+    ///     .unwrap();
+    ///
+    /// let mut buf = Vec::new();
+    /// request.encode_head(&mut buf);
+    /// assert_eq!(
+    ///     buf,
+    ///     &b"POST /submit HTTP/1.1\r\nhost: example.com\r\ncontent-length: 5\r\n\r\n"[..]
+    /// );
+    /// ```
+    pub fn encode_head(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(self.method().as_str().as_bytes());
+        dst.push(b' ');
+        dst.extend_from_slice(self.uri().to_string().as_bytes());
+        dst.push(b' ');
+        dst.extend_from_slice(format!("{:?}", self.version()).as_bytes());
+        dst.extend_from_slice(b"\r\n");
+        self.headers().encode(dst);
+        dst.extend_from_slice(b"\r\n");
+    }
+
+    /// Returns `true` if the client is waiting for a `100 Continue` response
+    /// before sending the body.
+    ///
+    /// Following [RFC 9110, Section 10.1.1](https://datatracker.ietf.org/doc/html/rfc9110#section-10.1.1),
+    /// this checks for a `100-continue` element in the `Expect` headers,
+    /// matched case-insensitively. The expectation is ignored for HTTP/1.0
+    /// and earlier, and for requests that announce no content, with neither
+    /// a `Transfer-Encoding` header nor a valid, non-zero `Content-Length`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::put("/upload")
+    ///     .header("expect", "100-Continue")
+    ///     .header("content-length", "1048576")
+    ///     .body(())
+    ///     .unwrap();
+    /// assert!(request.expects_continue());
+    ///
+    /// let request = Request::get("/")
+    ///     .header("expect", "100-continue")
+    ///     .body(())
+    ///     .unwrap();
+    /// assert!(!request.expects_continue());
+    /// ```
+    pub fn expects_continue(&self) -> bool {
+        if self.version() < Version::HTTP_11 {
+            return false;
+        }
+
+        let headers = self.headers();
+        let has_content = headers.contains_key(TRANSFER_ENCODING)
+            || ContentLength::decode(&mut headers.get_all(CONTENT_LENGTH).iter())
+                .map_or(false, |len| len.0 > 0);
+        has_content
+            && headers.get_all(EXPECT).iter().any(|value| {
+                value.split_list().map_or(false, |mut elements| {
+                    elements.any(|e| e.eq_ignore_ascii_case("100-continue"))
+                })
+            })
+    }
+
+    /// Checks that the URI is a valid request target for the method.
+    ///
+    /// [RFC 9112, Section 3.2](https://datatracker.ietf.org/doc/html/rfc9112#section-3.2)
+    /// allows each method only some forms of request target:
+    ///
+    /// - `CONNECT` must use the authority-form, such as `example.com:443`,
+    ///   with a port and without userinfo.
+    /// - `OPTIONS` may use the asterisk-form, `*`.
+    /// - Every method other than `CONNECT` may use the origin-form, such as
+    ///   `/where?q=now`, or the absolute-form, such as
+    ///   `http://example.com/where`.
+    ///
+    /// Servers can use this to reject a malformed request line with
+    /// `400 Bad Request` before routing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::connect("example.com:443").body(()).unwrap();
+    /// assert!(request.validate_target().is_ok());
+    ///
+    /// let request = Request::options("*").body(()).unwrap();
+    /// assert!(request.validate_target().is_ok());
+    ///
+    /// let request = Request::get("*").body(()).unwrap();
+    /// assert!(request.validate_target().is_err());
+    ///
+    /// let request = Request::connect("/").body(()).unwrap();
+    /// assert!(request.validate_target().is_err());
+    /// ```
+    pub fn validate_target(&self) -> result::Result<(), InvalidRequestTarget> {
+        let uri = self.uri();
+        let valid = if self.method() == Method::CONNECT {
+            uri.scheme().is_none()
+                && uri.path_and_query().is_none()
+                && uri
+                    .authority()
+                    .map_or(false, |a| a.port().is_some() && !a.as_str().contains('@'))
+        } else if uri.path() == "*" {
+            self.method() == Method::OPTIONS
+        } else {
+            // Origin-form has neither a scheme nor an authority, and
+            // absolute-form has both.
+            uri.scheme().is_some() == uri.authority().is_some() && uri.path().starts_with('/')
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(InvalidRequestTarget { _priv: () })
+        }
+    }
+
+    /// Resolves the host this request is directed at.
+    ///
+    /// Following [RFC 9112, Section 3.2](https://datatracker.ietf.org/doc/html/rfc9112#section-3.2),
+    /// the authority of the URI is used when there is one, as with the
+    /// absolute-form and authority-form request targets; any userinfo is
+    /// dropped. Otherwise the request must have exactly one valid `Host`
+    /// header.
+    ///
+    /// When the URI's authority is used, a `Host` header that is missing,
+    /// invalid, or names a different authority is reported by
+    /// [`EffectiveHost::host_mismatch`], which servers may reject with
+    /// `400 Bad Request`.
+    ///
+    /// Returns `None` if there is no authority in the URI and the `Host`
+    /// header is missing, repeated, or invalid. It is also `None` if what
+    /// follows the userinfo in the URI's authority is not a valid authority
+    /// by itself, as in `http://[@host]/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::get("/index.html")
+    ///     .header("host", "example.com:8080")
+    ///     .body(())
+    ///     .unwrap();
+    /// let host = request.effective_host().unwrap();
+    /// assert_eq!(host.authority(), "example.com:8080");
+    /// assert!(!host.host_mismatch());
+    ///
+    /// let request = Request::get("http://example.org/index.html")
+    ///     .header("host", "example.com")
+    ///     .body(())
+    ///     .unwrap();
+    /// let host = request.effective_host().unwrap();
+    /// assert_eq!(host.authority(), "example.org");
+    /// assert!(host.host_mismatch());
+    ///
+    /// let request = Request::get("/index.html").body(()).unwrap();
+    /// assert!(request.effective_host().is_none());
+    /// ```
+    pub fn effective_host(&self) -> Option<EffectiveHost> {
+        let header = Host::decode(&mut self.headers().get_all(HOST).iter())
+            .ok()
+            .map(|Host(authority)| authority);
+
+        let authority = match self.uri().authority() {
+            Some(authority) => authority,
+            None => {
+                return header.map(|authority| EffectiveHost {
+                    authority,
+                    mismatch: false,
+                })
+            }
+        };
+
+        let authority = match authority.as_str().rfind('@') {
+            Some(at) => Authority::try_from(&authority.as_str()[at + 1..]).ok()?,
+            None => authority.clone(),
+        };
+        let mismatch = header.as_ref() != Some(&authority);
+        Some(EffectiveHost {
+            authority,
+            mismatch,
+        })
+    }
+
     /// Returns a reference to the associated HTTP body.
     ///
     /// # Examples
@@ -685,6 +951,138 @@ impl<T> Request<T> {
             head: self.head,
         }
     }
+
+    /// Consumes the request returning a new request with body mapped by the
+    /// passed in fallible function.
+    ///
+    /// If the function fails its error is returned, and the rest of the
+    /// request is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::builder().body("42").unwrap();
+    /// let parsed: Request<u32> = request.try_map(|b| b.parse()).unwrap();
+    /// assert_eq!(*parsed.body(), 42);
+    ///
+    /// let request = Request::builder().body("forty-two").unwrap();
+    /// assert!(request.try_map(|b| b.parse::<u32>()).is_err());
+    /// ```
+    #[inline]
+    pub fn try_map<F, U, E>(self, f: F) -> result::Result<Request<U>, E>
+    where
+        F: FnOnce(T) -> result::Result<U, E>,
+    {
+        Ok(Request {
+            body: f(self.body)?,
+            head: self.head,
+        })
+    }
+
+    /// Consumes the request returning a new request with its head rewritten
+    /// by the passed in function.
+    ///
+    /// The function receives the request's `Parts` and a reference to its
+    /// body, and returns the new `Parts`. The body is kept as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::CONTENT_LENGTH;
+    /// let request = Request::post("/v1/items").body("hello").unwrap();
+    /// let request = request.map_parts(|mut parts, body| {
+    ///     parts.uri = Uri::from_static("/v2/items");
+    ///     parts.headers.insert(CONTENT_LENGTH, body.len().into());
+    ///     parts
+    /// });
+    ///
+    /// assert_eq!(request.uri(), "/v2/items");
+    /// assert_eq!(request.headers()[CONTENT_LENGTH], "5");
+    /// assert_eq!(*request.body(), "hello");
+    /// ```
+    #[inline]
+    pub fn map_parts<F>(self, f: F) -> Request<T>
+    where
+        F: FnOnce(Parts, &T) -> Parts,
+    {
+        Request {
+            head: f(self.head, &self.body),
+            body: self.body,
+        }
+    }
+
+    /// Consumes the request, returning it with the method replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::new(()).with_method(Method::HEAD);
+    /// assert_eq!(*request.method(), Method::HEAD);
+    /// ```
+    #[inline]
+    pub fn with_method(mut self, method: Method) -> Request<T> {
+        self.head.method = method;
+        self
+    }
+
+    /// Consumes the request, returning it with the URI replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::new(()).with_uri(Uri::from_static("/v2/items"));
+    /// assert_eq!(request.uri(), "/v2/items");
+    /// ```
+    #[inline]
+    pub fn with_uri(mut self, uri: Uri) -> Request<T> {
+        self.head.uri = uri;
+        self
+    }
+
+    /// Consumes the request, returning it with the version replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let request = Request::new(()).with_version(Version::HTTP_2);
+    /// assert_eq!(request.version(), Version::HTTP_2);
+    /// ```
+    #[inline]
+    pub fn with_version(mut self, version: Version) -> Request<T> {
+        self.head.version = version;
+        self
+    }
+
+    /// Consumes the request, returning it with a header set.
+    ///
+    /// Any existing values for the header are replaced, as with
+    /// [`HeaderMap::insert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::ACCEPT;
+    /// let request = Request::get("/")
+    ///     .header(ACCEPT, "text/html")
+    ///     .header(ACCEPT, "*/*")
+    ///     .body(())
+    ///     .unwrap()
+    ///     .with_header(ACCEPT, HeaderValue::from_static("application/json"));
+    ///
+    /// let accept: Vec<_> = request.headers().get_all(ACCEPT).iter().collect();
+    /// assert_eq!(accept, ["application/json"]);
+    /// ```
+    #[inline]
+    pub fn with_header<K: IntoHeaderName>(mut self, name: K, value: HeaderValue) -> Request<T> {
+        self.head.headers.insert(name, value);
+        self
+    }
 }
 
 impl<T: Default> Default for Request<T> {
@@ -693,13 +1091,36 @@ impl<T: Default> Default for Request<T> {
     }
 }
 
+/// Formats the request with the values of credential headers hidden.
+///
+/// Values of `Authorization`, `Proxy-Authorization`, `Cookie` and
+/// `Set-Cookie`, and any value marked sensitive, are shown as `<redacted>`,
+/// so that an accidental `dbg!` does not leak them into logs.
+/// Print [`headers().unredacted()`](crate::HeaderMap::unredacted) to see
+/// them.
+///
+/// # Example
+///
+/// ```
+/// # use http::*;
+/// let request = Request::builder()
+///     .header("authorization", "Bearer secret")
+///     .header("accept", "*/*")
+///     .body(())
+///     .unwrap();
+///
+/// let debug = format!("{:?}", request);
+/// assert!(debug.contains(r#""authorization": <redacted>"#));
+/// assert!(debug.contains(r#""accept": "*/*""#));
+/// assert!(!format!("{:?}", request.headers()).contains("Bearer secret"));
+/// ```
 impl<T: fmt::Debug> fmt::Debug for Request<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Request")
             .field("method", self.method())
             .field("uri", self.uri())
             .field("version", &self.version())
-            .field("headers", self.headers())
+            .field("headers", &Redacted(self.headers()))
             // omits Extensions because not useful
             .field("body", self.body())
             .finish()
@@ -715,9 +1136,51 @@ impl Parts {
             version: Version::default(),
             headers: HeaderMap::default(),
             extensions: Extensions::default(),
+            trailers: None,
             _priv: (),
         }
     }
+
+    /// Parses an HTTP/1.1 request head.
+    ///
+    /// `src` holds the request line and the header fields, each ending in
+    /// CRLF or a bare LF, and optionally the blank line that ends the head.
+    /// Nothing may follow the blank line. The version must be HTTP/1.0 or
+    /// HTTP/1.1, and the header fields are parsed as with
+    /// [`HeaderMap::parse`].
+    ///
+    /// This is meant for test fixtures, fuzzing and tooling. It does not
+    /// check that the request target suits the method, nor enforce any
+    /// limits on the size of the head.
+    ///
+    /// # Errors
+    ///
+    /// The [`InvalidHeaderBlock`] error describes what was wrong, and where.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::request::Parts;
+    /// let parts = Parts::parse(b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n").unwrap();
+    /// assert_eq!(parts.method, Method::GET);
+    /// assert_eq!(parts.uri, "/index.html");
+    /// assert_eq!(parts.version, Version::HTTP_11);
+    /// assert_eq!(parts.headers["host"], "example.com");
+    ///
+    /// let err = Parts::parse(b"GET /index.html HTTP/9\r\n\r\n").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid HTTP version at byte 16");
+    /// ```
+    pub fn parse(src: &[u8]) -> result::Result<Parts, InvalidHeaderBlock> {
+        let (method, uri, version, headers) = parse_request_head(src)?;
+        Ok(Parts {
+            method,
+            uri,
+            version,
+            headers,
+            ..Parts::new()
+        })
+    }
 }
 
 impl fmt::Debug for Parts {
@@ -726,13 +1189,48 @@ impl fmt::Debug for Parts {
             .field("method", &self.method)
             .field("uri", &self.uri)
             .field("version", &self.version)
-            .field("headers", &self.headers)
+            .field("headers", &Redacted(&self.headers))
+            .field("trailers", &self.trailers.as_ref().map(Redacted))
             // omits Extensions because not useful
             // omits _priv because not useful
             .finish()
     }
 }
 
+impl fmt::Debug for InvalidRequestTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidRequestTarget")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidRequestTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid request target for method")
+    }
+}
+
+impl error::Error for InvalidRequestTarget {}
+
+impl EffectiveHost {
+    /// Returns the authority the request is directed at.
+    pub fn authority(&self) -> &Authority {
+        &self.authority
+    }
+
+    /// Returns `true` if the authority came from the URI, and the `Host`
+    /// header did not agree with it.
+    pub fn host_mismatch(&self) -> bool {
+        self.mismatch
+    }
+
+    /// Consumes this, returning the authority.
+    pub fn into_authority(self) -> Authority {
+        self.authority
+    }
+}
+
 impl Builder {
     /// Creates a new default instance of `Builder` to construct a `Request`.
     ///
@@ -796,6 +1294,23 @@ impl Builder {
         self.inner.as_ref().ok().map(|h| &h.method)
     }
 
+    /// Get a mutable reference to the HTTP Method for this request.
+    ///
+    /// If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut req = Request::builder();
+    /// *req.method_mut().unwrap() = Method::PUT;
+    /// assert_eq!(req.method_ref(), Some(&Method::PUT));
+    /// ```
+    pub fn method_mut(&mut self) -> Option<&mut Method> {
+        self.inner.as_mut().ok().map(|h| &mut h.method)
+    }
+
     /// Set the URI for this request.
     ///
     /// By default this is `/`.
@@ -840,6 +1355,23 @@ impl Builder {
         self.inner.as_ref().ok().map(|h| &h.uri)
     }
 
+    /// Get a mutable reference to the URI for this request.
+    ///
+    /// If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut req = Request::builder().uri("/index.html");
+    /// *req.uri_mut().unwrap() = Uri::from_static("/v2/index.html");
+    /// assert_eq!(req.uri_ref().unwrap(), "/v2/index.html");
+    /// ```
+    pub fn uri_mut(&mut self) -> Option<&mut Uri> {
+        self.inner.as_mut().ok().map(|h| &mut h.uri)
+    }
+
     /// Set the HTTP version for this request.
     ///
     /// By default this is HTTP/1.1
@@ -880,6 +1412,23 @@ impl Builder {
         self.inner.as_ref().ok().map(|h| &h.version)
     }
 
+    /// Get a mutable reference to the HTTP version for this request.
+    ///
+    /// If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut req = Request::builder();
+    /// *req.version_mut().unwrap() = Version::HTTP_2;
+    /// assert_eq!(req.version_ref(), Some(&Version::HTTP_2));
+    /// ```
+    pub fn version_mut(&mut self) -> Option<&mut Version> {
+        self.inner.as_mut().ok().map(|h| &mut h.version)
+    }
+
     /// Appends a header to this request builder.
     ///
     /// This function will append the provided key/value as a header to the
@@ -913,6 +1462,81 @@ impl Builder {
         })
     }
 
+    /// Appends every header in `headers` to this request builder.
+    ///
+    /// This is equivalent to calling [`header`](Builder::header) for each
+    /// value, in order, so a prepared set of default headers can be added in
+    /// one call. Headers already on the builder are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
+    /// let mut defaults = HeaderMap::new();
+    /// defaults.insert(USER_AGENT, HeaderValue::from_static("my-agent/1.0"));
+    /// defaults.append(ACCEPT, HeaderValue::from_static("text/html"));
+    /// defaults.append(ACCEPT, HeaderValue::from_static("*/*"));
+    ///
+    /// let req = Request::builder()
+    ///     .header(ACCEPT, "application/json")
+    ///     .headers(defaults)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(req.headers()[USER_AGENT], "my-agent/1.0");
+    /// assert_eq!(req.headers().get_all(ACCEPT).iter().count(), 3);
+    /// ```
+    pub fn headers(self, headers: HeaderMap<HeaderValue>) -> Builder {
+        self.and_then(move |mut head| {
+            let mut name = None;
+            for (key, value) in headers {
+                if key.is_some() {
+                    name = key;
+                }
+                // The first value yielded for each name carries the name.
+                let name = name.clone().expect("HeaderMap yields a name first");
+                head.headers.try_append(name, value)?;
+            }
+            Ok(head)
+        })
+    }
+
+    /// Appends a header to this request builder if `value` is `Some`.
+    ///
+    /// When `value` is `None` the builder is returned unchanged, and `key`
+    /// is not checked. This keeps optional headers, such as an auth token,
+    /// within a single builder chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let token: Option<&str> = None;
+    /// let trace_id = Some("4bf92f3577b34da6");
+    ///
+    /// let req = Request::builder()
+    ///     .header_opt("authorization", token)
+    ///     .header_opt("x-trace-id", trace_id)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert!(!req.headers().contains_key("authorization"));
+    /// assert_eq!(req.headers()["x-trace-id"], "4bf92f3577b34da6");
+    /// ```
+    pub fn header_opt<K, V>(self, key: K, value: Option<V>) -> Builder
+    where
+        K: TryInto<HeaderName>,
+        <K as TryInto<HeaderName>>::Error: Into<crate::Error>,
+        V: TryInto<HeaderValue>,
+        <V as TryInto<HeaderValue>>::Error: Into<crate::Error>,
+    {
+        match value {
+            Some(value) => self.header(key, value),
+            None => self,
+        }
+    }
+
     /// Get header on this request builder.
     /// when builder has error returns None
     ///
@@ -1013,6 +1637,37 @@ impl Builder {
         self.inner.as_mut().ok().map(|h| &mut h.extensions)
     }
 
+    /// Sets the trailers for this request, replacing any set before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::{HeaderMap, HeaderValue};
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    ///
+    /// let request = Request::builder()
+    ///     .trailers(trailers)
+    ///     .body(())
+    ///     .unwrap();
+    /// assert_eq!(request.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    pub fn trailers(self, trailers: HeaderMap<HeaderValue>) -> Builder {
+        self.and_then(move |mut head| {
+            head.trailers = Some(trailers);
+            Ok(head)
+        })
+    }
+
+    /// Get the trailers for this request builder.
+    ///
+    /// If the builder has an error or no trailers were set, this returns
+    /// `None`.
+    pub fn trailers_ref(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.inner.as_ref().ok().and_then(|h| h.trailers.as_ref())
+    }
+
     /// "Consumes" this builder, using the provided `body` to return a
     /// constructed `Request`.
     ///
@@ -1043,9 +1698,18 @@ impl Builder {
     where
         F: FnOnce(Parts) -> Result<Parts>,
     {
-        Builder {
-            inner: self.inner.and_then(func),
-        }
+        // After an error, later inputs are still checked against a blank
+        // head so that all of their errors are reported together.
+        let inner = match self.inner {
+            Ok(head) => func(head),
+            Err(mut err) => {
+                if let Err(next) = func(Parts::new()) {
+                    err.push(next);
+                }
+                Err(err)
+            }
+        };
+        Builder { inner }
     }
 }
 
@@ -1058,6 +1722,211 @@ impl Default for Builder {
     }
 }
 
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::Parts;
+    use crate::extensions::{ExtensionRegistry, ExtensionTypes, PartsSeed, RegisteredParts};
+    use crate::header::{HeaderMap, HeaderValue};
+    use crate::{Extensions, Method, Uri, Version};
+    use serde::de::{
+        self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    };
+    use serde::ser::{self, Serialize, SerializeStruct, Serializer};
+    use std::convert::TryFrom;
+    use std::fmt;
+
+    // Parts are written as a struct of their fields. Extensions can hold any
+    // type, so they are only written through an `ExtensionRegistry`, as a
+    // last `extensions` field. Otherwise serializing fails unless there are
+    // none, and deserialized parts have none.
+    const FIELDS: &[&str] = &[
+        "method",
+        "uri",
+        "version",
+        "headers",
+        "trailers",
+        "extensions",
+    ];
+
+    impl Serialize for Parts {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self, None::<&ExtensionRegistry>, serializer)
+        }
+    }
+
+    impl<'a, L: ExtensionTypes> Serialize for RegisteredParts<'a, Parts, L> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self.parts, Some(self.registry), serializer)
+        }
+    }
+
+    fn serialize<S: Serializer, L: ExtensionTypes>(
+        parts: &Parts,
+        registry: Option<&ExtensionRegistry<L>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if registry.is_none() && !parts.extensions.is_empty() {
+            return Err(ser::Error::custom(
+                "request extensions cannot be serialized",
+            ));
+        }
+        let len = FIELDS.len() - registry.is_none() as usize;
+        let mut state = serializer.serialize_struct("Parts", len)?;
+        state.serialize_field("method", &parts.method)?;
+        state.serialize_field("uri", &UriStr(&parts.uri))?;
+        state.serialize_field("version", &parts.version)?;
+        state.serialize_field("headers", &parts.headers)?;
+        state.serialize_field("trailers", &parts.trailers)?;
+        if let Some(registry) = registry {
+            state.serialize_field("extensions", &registry.extensions(&parts.extensions))?;
+        }
+        state.end()
+    }
+
+    struct UriStr<'a>(&'a Uri);
+
+    impl<'a> Serialize for UriStr<'a> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self.0)
+        }
+    }
+
+    fn parse_uri<E: de::Error>(uri: String) -> Result<Uri, E> {
+        Uri::try_from(uri.as_str())
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(&uri), &"a valid URI"))
+    }
+
+    impl<'de> de::Deserialize<'de> for Parts {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let visitor = PartsVisitor {
+                registry: None::<&ExtensionRegistry>,
+            };
+            deserializer.deserialize_struct("Parts", FIELDS, visitor)
+        }
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> DeserializeSeed<'de> for PartsSeed<'a, Parts, L> {
+        type Value = Parts;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Parts, D::Error> {
+            let visitor = PartsVisitor {
+                registry: Some(self.registry),
+            };
+            deserializer.deserialize_struct("Parts", FIELDS, visitor)
+        }
+    }
+
+    struct PartsVisitor<'a, L> {
+        registry: Option<&'a ExtensionRegistry<L>>,
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> Visitor<'de> for PartsVisitor<'a, L> {
+        type Value = Parts;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("request parts")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Parts, A::Error> {
+            let method: Method = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let uri: String = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let version: Version = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+            let headers: HeaderMap<HeaderValue> = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+            // Trailers may be left out, as in a map.
+            let trailers = seq.next_element()?.unwrap_or(None);
+            let extensions = match self.registry {
+                Some(registry) => seq
+                    .next_element_seed(registry.extensions_seed())?
+                    .unwrap_or_default(),
+                None => Extensions::new(),
+            };
+            Ok(Parts {
+                method,
+                uri: parse_uri(uri)?,
+                version,
+                headers,
+                trailers,
+                extensions,
+                ..Parts::new()
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Parts, A::Error> {
+            let mut method = None;
+            let mut uri = None;
+            let mut version = None;
+            let mut headers = None;
+            let mut trailers = None;
+            let mut extensions = None;
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "method" => {
+                        if method.is_some() {
+                            return Err(de::Error::duplicate_field("method"));
+                        }
+                        method = Some(map.next_value::<Method>()?);
+                    }
+                    "uri" => {
+                        if uri.is_some() {
+                            return Err(de::Error::duplicate_field("uri"));
+                        }
+                        uri = Some(map.next_value::<String>()?);
+                    }
+                    "version" => {
+                        if version.is_some() {
+                            return Err(de::Error::duplicate_field("version"));
+                        }
+                        version = Some(map.next_value::<Version>()?);
+                    }
+                    "headers" => {
+                        if headers.is_some() {
+                            return Err(de::Error::duplicate_field("headers"));
+                        }
+                        headers = Some(map.next_value::<HeaderMap<HeaderValue>>()?);
+                    }
+                    "trailers" => {
+                        if trailers.is_some() {
+                            return Err(de::Error::duplicate_field("trailers"));
+                        }
+                        trailers = Some(map.next_value::<Option<HeaderMap<HeaderValue>>>()?);
+                    }
+                    "extensions" => {
+                        if extensions.is_some() {
+                            return Err(de::Error::duplicate_field("extensions"));
+                        }
+                        extensions = Some(match self.registry {
+                            Some(registry) => map.next_value_seed(registry.extensions_seed())?,
+                            None => {
+                                map.next_value_seed(ExtensionRegistry::new().extensions_seed())?
+                            }
+                        });
+                    }
+                    _ => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(Parts {
+                method: method.ok_or_else(|| de::Error::missing_field("method"))?,
+                uri: parse_uri(uri.ok_or_else(|| de::Error::missing_field("uri"))?)?,
+                version: version.ok_or_else(|| de::Error::missing_field("version"))?,
+                headers: headers.ok_or_else(|| de::Error::missing_field("headers"))?,
+                trailers: trailers.unwrap_or(None),
+                extensions: extensions.unwrap_or_default(),
+                ..Parts::new()
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1071,4 +1940,273 @@ mod tests {
         });
         assert_eq!(mapped_request.body(), &123u32);
     }
+
+    #[test]
+    fn debug_redacts_credentials() {
+        let mut token = HeaderValue::from_static("s3cr3t");
+        token.set_sensitive(true);
+        let request = Request::builder()
+            .header("cookie", "session=abc")
+            .header("proxy-authorization", "Basic Zm9vOmJhcg==")
+            .header("x-api-key", token)
+            .header("user-agent", "test")
+            .body(())
+            .unwrap();
+        let (parts, _) = request.into_parts();
+
+        let debug = format!("{:?}", parts);
+        assert!(debug.contains(r#""cookie": <redacted>"#));
+        assert!(debug.contains(r#""proxy-authorization": <redacted>"#));
+        assert!(debug.contains(r#""x-api-key": <redacted>"#));
+        assert!(debug.contains(r#""user-agent": "test""#));
+    }
+
+    #[test]
+    fn effective_host() {
+        fn host(uri: &str, hosts: &[&str]) -> Option<(String, bool)> {
+            let mut builder = Request::get(uri);
+            for host in hosts {
+                builder = builder.header(HOST, *host);
+            }
+            let request = builder.body(()).unwrap();
+            request
+                .effective_host()
+                .map(|h| (h.authority().to_string(), h.host_mismatch()))
+        }
+
+        let found = |authority: &str, mismatch| Some((authority.to_string(), mismatch));
+        assert_eq!(host("/", &["example.com"]), found("example.com", false));
+        assert_eq!(host("*", &["[::1]:80"]), found("[::1]:80", false));
+        assert_eq!(host("/", &[]), None);
+        assert_eq!(host("/", &["a.example", "b.example"]), None);
+        assert_eq!(host("/", &["user@example.com"]), None);
+        assert_eq!(host("/", &["exa mple.com"]), None);
+
+        assert_eq!(
+            host("http://user:pw@Example.com:8080/", &["example.com:8080"]),
+            found("Example.com:8080", false)
+        );
+        assert_eq!(host("http://example.com/", &[]), found("example.com", true));
+        assert_eq!(
+            host("http://example.com/", &["example.com:80"]),
+            found("example.com", true)
+        );
+        assert_eq!(
+            host("example.com:443", &["example.com:443", "other"]),
+            found("example.com:443", true)
+        );
+
+        // The part after the userinfo is not always a valid authority.
+        assert_eq!(host("http://[@host]/", &["host"]), None);
+        assert_eq!(host("http://[:@host]/x", &[]), None);
+        assert_eq!(host("s'4x&.[:s@:Z]Z", &[]), None);
+    }
+
+    #[test]
+    fn validate_target() {
+        fn check(method: Method, uri: &str) -> bool {
+            let request = Request::builder().method(method).uri(uri).body(()).unwrap();
+            request.validate_target().is_ok()
+        }
+
+        assert!(check(Method::GET, "/"));
+        assert!(check(Method::GET, "/a?b"));
+        assert!(check(Method::POST, "https://example.com/a"));
+        assert!(check(Method::DELETE, "http://example.com"));
+        assert!(check(Method::OPTIONS, "*"));
+        assert!(check(Method::OPTIONS, "/"));
+        assert!(check(Method::CONNECT, "example.com:443"));
+        assert!(check(Method::CONNECT, "[::1]:8080"));
+
+        assert!(!check(Method::GET, "*"));
+        assert!(!check(Method::GET, "example.com:443"));
+        assert!(!check(Method::CONNECT, "/"));
+        assert!(!check(Method::CONNECT, "*"));
+        assert!(!check(Method::CONNECT, "example.com"));
+        assert!(!check(Method::CONNECT, "user@example.com:443"));
+        assert!(!check(Method::CONNECT, "http://example.com:443/"));
+    }
+
+    #[test]
+    fn expects_continue() {
+        fn check(version: Version, headers: &[(&str, &str)]) -> bool {
+            let mut builder = Request::post("/").version(version);
+            for &(name, value) in headers {
+                builder = builder.header(name, value);
+            }
+            builder.body(()).unwrap().expects_continue()
+        }
+
+        let expect = ("expect", "100-continue");
+        let length = ("content-length", "10");
+        assert!(check(Version::HTTP_11, &[expect, length]));
+        assert!(check(Version::HTTP_2, &[expect, length]));
+        assert!(check(
+            Version::HTTP_11,
+            &[
+                ("expect", "foo, 100-CONTINUE"),
+                ("transfer-encoding", "chunked")
+            ]
+        ));
+        assert!(check(
+            Version::HTTP_11,
+            &[("expect", "foo"), ("expect", "100-continue"), length]
+        ));
+
+        assert!(!check(Version::HTTP_10, &[expect, length]));
+        assert!(!check(Version::HTTP_11, &[expect]));
+        assert!(!check(
+            Version::HTTP_11,
+            &[expect, ("content-length", "00")]
+        ));
+        assert!(!check(
+            Version::HTTP_11,
+            &[expect, ("content-length", "0 , 0")]
+        ));
+        assert!(!check(
+            Version::HTTP_11,
+            &[expect, ("content-length", "abc")]
+        ));
+        assert!(!check(
+            Version::HTTP_11,
+            &[expect, ("content-length", "5, 6")]
+        ));
+        assert!(check(
+            Version::HTTP_11,
+            &[expect, ("content-length", "5, 5")]
+        ));
+        assert!(!check(
+            Version::HTTP_11,
+            &[("expect", "100-continued"), length]
+        ));
+        assert!(!check(Version::HTTP_11, &[length]));
+    }
+
+    #[test]
+    fn encode_head() {
+        fn encode(request: Request<()>) -> String {
+            let mut buf = Vec::new();
+            request.encode_head(&mut buf);
+            String::from_utf8(buf).unwrap()
+        }
+
+        assert_eq!(encode(Request::default()), "GET / HTTP/1.1\r\n\r\n");
+        assert_eq!(
+            encode(
+                Request::options("*")
+                    .version(Version::HTTP_10)
+                    .body(())
+                    .unwrap()
+            ),
+            "OPTIONS * HTTP/1.0\r\n\r\n"
+        );
+        assert_eq!(
+            encode(
+                Request::connect("example.com:443")
+                    .header("host", "example.com:443")
+                    .body(())
+                    .unwrap()
+            ),
+            "CONNECT example.com:443 HTTP/1.1\r\nhost: example.com:443\r\n\r\n"
+        );
+        assert_eq!(
+            encode(
+                Request::get("http://example.com/a?b=c")
+                    .header("accept", "*/*")
+                    .header("accept", "text/html")
+                    .body(())
+                    .unwrap()
+            ),
+            "GET http://example.com/a?b=c HTTP/1.1\r\naccept: */*\r\naccept: text/html\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn parse_parts() {
+        let parts = Parts::parse(
+            b"POST http://example.com/a?b HTTP/1.0\nContent-Length: 0\nx-a: 1\nX-A: 2\n",
+        )
+        .unwrap();
+        assert_eq!(parts.method, Method::POST);
+        assert_eq!(parts.uri, "http://example.com/a?b");
+        assert_eq!(parts.version, Version::HTTP_10);
+        assert_eq!(parts.headers.len(), 3);
+        assert_eq!(parts.headers.get_all("x-a").iter().count(), 2);
+
+        let parts = Parts::parse(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(parts.uri.authority().unwrap(), "example.com:443");
+        assert!(parts.headers.is_empty());
+
+        let err = |src: &[u8]| Parts::parse(src).unwrap_err().to_string();
+        assert_eq!(err(b""), "malformed start line at byte 0");
+        assert_eq!(err(b"GET /\r\n"), "malformed start line at byte 0");
+        assert_eq!(err(b"G(T / HTTP/1.1\r\n"), "invalid method at byte 0");
+        assert_eq!(
+            err(b"GET  / HTTP/1.1\r\n"),
+            "invalid request target at byte 4"
+        );
+        assert_eq!(
+            err(b"GET / HTTP/1.1 \r\n"),
+            "invalid HTTP version at byte 6"
+        );
+        assert_eq!(err(b"GET / http/1.1\r\n"), "invalid HTTP version at byte 6");
+        assert_eq!(
+            err(b"GET / HTTP/1.1\r"),
+            "carriage return without line feed at byte 14"
+        );
+        assert_eq!(
+            err(b"GET / HTTP/1.1\r\nbad header: 1\r\n"),
+            "invalid header name at byte 16"
+        );
+        assert_eq!(
+            err(b"GET / HTTP/1.1\r\n\r\nbody"),
+            "data after end of header block at byte 18"
+        );
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn serde_parts() {
+        let (mut parts, _) = Request::put("https://example.com/a?b=c")
+            .header("content-type", "text/plain")
+            .header("accept", "a")
+            .header("accept", "b")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.trailers = Some(HeaderMap::new());
+
+        let json = serde_json::to_string(&parts).unwrap();
+        assert_eq!(
+            json,
+            r#"{"method":"PUT","uri":"https://example.com/a?b=c","version":"HTTP/1.1","headers":{"content-type":["text/plain"],"accept":["a","b"]},"trailers":{}}"#
+        );
+        let back: Parts = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.method, parts.method);
+        assert_eq!(back.uri, parts.uri);
+        assert_eq!(back.version, parts.version);
+        assert_eq!(back.headers, parts.headers);
+        assert_eq!(back.trailers, parts.trailers);
+
+        let back: Parts = serde_json::from_str(
+            r#"{"uri":"/","method":"GET","version":"h2","headers":{},"extra":1}"#,
+        )
+        .unwrap();
+        assert_eq!(back.version, Version::HTTP_2);
+        assert!(back.trailers.is_none());
+
+        let err = |json| serde_json::from_str::<Parts>(json).unwrap_err().to_string();
+        assert!(err(r#"{"method":"GET","version":"HTTP/1.1","headers":{}}"#)
+            .contains("missing field `uri`"));
+        assert!(
+            err(r#"{"method":"GET","uri":"a b","version":"HTTP/1.1","headers":{}}"#)
+                .contains("expected a valid URI")
+        );
+
+        parts.extensions.insert(5i32);
+        assert!(serde_json::to_string(&parts)
+            .unwrap_err()
+            .to_string()
+            .contains("request extensions cannot be serialized"));
+    }
 }
@@ -64,11 +64,15 @@
 use std::any::Any;
 use std::convert::TryInto;
 use std::fmt;
+use std::result;
 
-use crate::header::{HeaderMap, HeaderName, HeaderValue};
-use crate::status::StatusCode;
+use crate::header::{
+    parse_response_head, HeaderMap, HeaderName, HeaderValue, IntoHeaderName, InvalidHeaderBlock,
+    Redacted,
+};
+use crate::status::{InvalidStatusCode, ReasonPhrase, StatusCode};
 use crate::version::Version;
-use crate::{Extensions, Result};
+use crate::{Extensions, Result, Uri};
 
 /// Represents an HTTP response
 ///
@@ -187,6 +191,7 @@ pub struct Response<T> {
 /// The HTTP response head consists of a status, version, and a set of
 /// header fields.
 #[derive(Clone)]
+#[allow(clippy::manual_non_exhaustive)]
 pub struct Parts {
     /// The response's status
     pub status: StatusCode,
@@ -200,6 +205,12 @@ pub struct Parts {
     /// The response's extensions
     pub extensions: Extensions,
 
+    /// The response's trailers, if it has any
+    ///
+    /// Trailers are header fields sent after the body, such as by chunked
+    /// HTTP/1.1 messages or by gRPC over HTTP/2.
+    pub trailers: Option<HeaderMap<HeaderValue>>,
+
     _priv: (),
 }
 
@@ -232,6 +243,57 @@ impl Response<()> {
     pub fn builder() -> Builder {
         Builder::new()
     }
+
+    /// Creates a new `Response` with a `204 No Content` status and no body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::no_content();
+    /// assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    /// ```
+    #[inline]
+    pub fn no_content() -> Response<()> {
+        Response::from_status(StatusCode::NO_CONTENT, ())
+    }
+
+    /// Creates a new redirect `Response` to `location`.
+    ///
+    /// The `Location` header is set from the URI, and there is no body.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `status` is not a redirection
+    /// (3xx) status, or is `304 Not Modified`, which does not redirect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::LOCATION;
+    /// let response = Response::redirect(
+    ///     StatusCode::SEE_OTHER,
+    ///     Uri::from_static("https://example.com/orders/17"),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    /// assert_eq!(response.headers()[LOCATION], "https://example.com/orders/17");
+    ///
+    /// assert!(Response::redirect(StatusCode::OK, Uri::from_static("/")).is_err());
+    /// ```
+    pub fn redirect(status: StatusCode, location: Uri) -> Result<Response<()>> {
+        if !status.is_redirection() || status == StatusCode::NOT_MODIFIED {
+            return Err(InvalidStatusCode::new().into());
+        }
+
+        let mut response = Response::from_status(status, ());
+        response
+            .headers_mut()
+            .insert(crate::header::LOCATION, location.into());
+        Ok(response)
+    }
 }
 
 impl<T> Response<T> {
@@ -257,6 +319,84 @@ impl<T> Response<T> {
         }
     }
 
+    /// Creates a new `Response` with a `200 OK` status and the given body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::ok("hello world");
+    /// assert_eq!(response.status(), StatusCode::OK);
+    /// ```
+    #[inline]
+    pub fn ok(body: T) -> Response<T> {
+        Response::from_status(StatusCode::OK, body)
+    }
+
+    /// Creates a new `Response` with a `201 Created` status and the given body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::created("{\"id\":1}");
+    /// assert_eq!(response.status(), StatusCode::CREATED);
+    /// ```
+    #[inline]
+    pub fn created(body: T) -> Response<T> {
+        Response::from_status(StatusCode::CREATED, body)
+    }
+
+    /// Creates a new `Response` with a `400 Bad Request` status and the given body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::bad_request("missing field `name`");
+    /// assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    /// ```
+    #[inline]
+    pub fn bad_request(body: T) -> Response<T> {
+        Response::from_status(StatusCode::BAD_REQUEST, body)
+    }
+
+    /// Creates a new `Response` with a `404 Not Found` status and the given body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::not_found("no such item");
+    /// assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    /// ```
+    #[inline]
+    pub fn not_found(body: T) -> Response<T> {
+        Response::from_status(StatusCode::NOT_FOUND, body)
+    }
+
+    /// Creates a new `Response` with a `500 Internal Server Error` status and the given body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::internal_server_error("something went wrong");
+    /// assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    /// ```
+    #[inline]
+    pub fn internal_server_error(body: T) -> Response<T> {
+        Response::from_status(StatusCode::INTERNAL_SERVER_ERROR, body)
+    }
+
+    // Shared by the status-specific constructors.
+    #[inline]
+    fn from_status(status: StatusCode, body: T) -> Response<T> {
+        let mut head = Parts::new();
+        head.status = status;
+        Response { head, body }
+    }
+
     /// Creates a new `Response` with the given head and body
     ///
     /// # Examples
@@ -395,6 +535,80 @@ impl<T> Response<T> {
         &mut self.head.extensions
     }
 
+    /// Returns a reference to the associated trailers, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response: Response<()> = Response::default();
+    /// assert!(response.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.head.trailers.as_ref()
+    }
+
+    /// Returns a mutable reference to the associated trailers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::*;
+    /// let mut response: Response<()> = Response::default();
+    /// response
+    ///     .trailers_mut()
+    ///     .get_or_insert_with(HeaderMap::new)
+    ///     .insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert_eq!(response.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut Option<HeaderMap<HeaderValue>> {
+        &mut self.head.trailers
+    }
+
+    /// Appends the status line and headers to `dst` in HTTP/1.1 wire format.
+    ///
+    /// The status line uses the [`ReasonPhrase`] stored in the extensions
+    /// if there is one, or else the canonical reason for the status code,
+    /// which is left empty for unknown codes. It is followed by every
+    /// header, as written by [`HeaderMap::encode`], and the blank line that
+    /// ends the head. The version is written as it is, even if it is not an
+    /// HTTP/1 version.
+    ///
+    /// Trailers and the body are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .status(StatusCode::NOT_FOUND)
+    ///     .header("content-length", "0")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let mut buf = Vec::new();
+    /// response.encode_head(&mut buf);
+    /// assert_eq!(buf, &b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n"[..]);
+    /// ```
+    pub fn encode_head(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(format!("{:?}", self.version()).as_bytes());
+        dst.push(b' ');
+        dst.extend_from_slice(self.status().as_str().as_bytes());
+        dst.push(b' ');
+        match self.extensions().get::<ReasonPhrase>() {
+            Some(reason) => dst.extend_from_slice(reason.as_bytes()),
+            None => {
+                dst.extend_from_slice(self.status().canonical_reason().unwrap_or("").as_bytes())
+            }
+        }
+        dst.extend_from_slice(b"\r\n");
+        self.headers().encode(dst);
+        dst.extend_from_slice(b"\r\n");
+    }
+
     /// Returns a reference to the associated HTTP body.
     ///
     /// # Examples
@@ -478,6 +692,121 @@ impl<T> Response<T> {
             head: self.head,
         }
     }
+
+    /// Consumes the response returning a new response with body mapped by the
+    /// passed in fallible function.
+    ///
+    /// If the function fails its error is returned, and the rest of the
+    /// response is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder().body("42").unwrap();
+    /// let parsed: Response<u32> = response.try_map(|b| b.parse()).unwrap();
+    /// assert_eq!(*parsed.body(), 42);
+    ///
+    /// let response = Response::builder().body("forty-two").unwrap();
+    /// assert!(response.try_map(|b| b.parse::<u32>()).is_err());
+    /// ```
+    #[inline]
+    pub fn try_map<F, U, E>(self, f: F) -> result::Result<Response<U>, E>
+    where
+        F: FnOnce(T) -> result::Result<U, E>,
+    {
+        Ok(Response {
+            body: f(self.body)?,
+            head: self.head,
+        })
+    }
+
+    /// Consumes the response returning a new response with its head
+    /// rewritten by the passed in function.
+    ///
+    /// The function receives the response's `Parts` and a reference to its
+    /// body, and returns the new `Parts`. The body is kept as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::CONTENT_LENGTH;
+    /// let response = Response::new("not here");
+    /// let response = response.map_parts(|mut parts, body| {
+    ///     parts.status = StatusCode::NOT_FOUND;
+    ///     parts.headers.insert(CONTENT_LENGTH, body.len().into());
+    ///     parts
+    /// });
+    ///
+    /// assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    /// assert_eq!(response.headers()[CONTENT_LENGTH], "8");
+    /// ```
+    #[inline]
+    pub fn map_parts<F>(self, f: F) -> Response<T>
+    where
+        F: FnOnce(Parts, &T) -> Parts,
+    {
+        Response {
+            head: f(self.head, &self.body),
+            body: self.body,
+        }
+    }
+
+    /// Consumes the response, returning it with the status replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::new(()).with_status(StatusCode::ACCEPTED);
+    /// assert_eq!(response.status(), StatusCode::ACCEPTED);
+    /// ```
+    #[inline]
+    pub fn with_status(mut self, status: StatusCode) -> Response<T> {
+        self.head.status = status;
+        self
+    }
+
+    /// Consumes the response, returning it with the version replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::new(()).with_version(Version::HTTP_2);
+    /// assert_eq!(response.version(), Version::HTTP_2);
+    /// ```
+    #[inline]
+    pub fn with_version(mut self, version: Version) -> Response<T> {
+        self.head.version = version;
+        self
+    }
+
+    /// Consumes the response, returning it with a header set.
+    ///
+    /// Any existing values for the header are replaced, as with
+    /// [`HeaderMap::insert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::{CACHE_CONTROL, CONTENT_TYPE};
+    /// let response = Response::new("{}")
+    ///     .with_status(StatusCode::CREATED)
+    ///     .with_header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+    ///     .with_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    ///
+    /// assert_eq!(response.status(), StatusCode::CREATED);
+    /// assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    /// assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+    /// ```
+    #[inline]
+    pub fn with_header<K: IntoHeaderName>(mut self, name: K, value: HeaderValue) -> Response<T> {
+        self.head.headers.insert(name, value);
+        self
+    }
 }
 
 impl<T: Default> Default for Response<T> {
@@ -487,12 +816,35 @@ impl<T: Default> Default for Response<T> {
     }
 }
 
+/// Formats the response with the values of credential headers hidden.
+///
+/// Values of `Authorization`, `Proxy-Authorization`, `Cookie` and
+/// `Set-Cookie`, and any value marked sensitive, are shown as `<redacted>`,
+/// so that an accidental `dbg!` does not leak them into logs.
+/// Print [`headers().unredacted()`](crate::HeaderMap::unredacted) to see
+/// them.
+///
+/// # Example
+///
+/// ```
+/// # use http::*;
+/// let response = Response::builder()
+///     .header("authorization", "Bearer secret")
+///     .header("accept", "*/*")
+///     .body(())
+///     .unwrap();
+///
+/// let debug = format!("{:?}", response);
+/// assert!(debug.contains(r#""authorization": <redacted>"#));
+/// assert!(debug.contains(r#""accept": "*/*""#));
+/// assert!(!format!("{:?}", response.headers()).contains("Bearer secret"));
+/// ```
 impl<T: fmt::Debug> fmt::Debug for Response<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Response")
             .field("status", &self.status())
             .field("version", &self.version())
-            .field("headers", self.headers())
+            .field("headers", &Redacted(self.headers()))
             // omits Extensions because not useful
             .field("body", self.body())
             .finish()
@@ -507,9 +859,58 @@ impl Parts {
             version: Version::default(),
             headers: HeaderMap::default(),
             extensions: Extensions::default(),
+            trailers: None,
             _priv: (),
         }
     }
+
+    /// Parses an HTTP/1.1 response head.
+    ///
+    /// `src` holds the status line and the header fields, each ending in
+    /// CRLF or a bare LF, and optionally the blank line that ends the head.
+    /// Nothing may follow the blank line. The version must be HTTP/1.0 or
+    /// HTTP/1.1, and the header fields are parsed as with
+    /// [`HeaderMap::parse`].
+    ///
+    /// The reason phrase, which may be empty, is stored in the extensions as
+    /// a [`ReasonPhrase`], so [`Response::encode_head`] writes it back out
+    /// unchanged.
+    ///
+    /// This is meant for test fixtures, fuzzing and tooling. It does not
+    /// enforce any limits on the size of the head.
+    ///
+    /// # Errors
+    ///
+    /// The [`InvalidHeaderBlock`] error describes what was wrong, and where.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::response::Parts;
+    /// # use http::status::ReasonPhrase;
+    /// let parts = Parts::parse(b"HTTP/1.1 404 Nothing Here\r\nContent-Length: 0\r\n\r\n").unwrap();
+    /// assert_eq!(parts.status, StatusCode::NOT_FOUND);
+    /// assert_eq!(parts.version, Version::HTTP_11);
+    /// assert_eq!(parts.headers["content-length"], "0");
+    ///
+    /// let reason = parts.extensions.get::<ReasonPhrase>().unwrap();
+    /// assert_eq!(reason.as_bytes(), b"Nothing Here");
+    ///
+    /// let err = Parts::parse(b"HTTP/1.1 2000 OK\r\n\r\n").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid status code at byte 9");
+    /// ```
+    pub fn parse(src: &[u8]) -> result::Result<Parts, InvalidHeaderBlock> {
+        let (version, status, reason, headers) = parse_response_head(src)?;
+        let mut parts = Parts {
+            status,
+            version,
+            headers,
+            ..Parts::new()
+        };
+        parts.extensions.insert(reason);
+        Ok(parts)
+    }
 }
 
 impl fmt::Debug for Parts {
@@ -517,7 +918,8 @@ impl fmt::Debug for Parts {
         f.debug_struct("Parts")
             .field("status", &self.status)
             .field("version", &self.version)
-            .field("headers", &self.headers)
+            .field("headers", &Redacted(&self.headers))
+            .field("trailers", &self.trailers.as_ref().map(Redacted))
             // omits Extensions because not useful
             // omits _priv because not useful
             .finish()
@@ -568,6 +970,42 @@ impl Builder {
         })
     }
 
+    /// Get the HTTP status for this response.
+    ///
+    /// By default this is `200`. If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let res = Response::builder().status(404);
+    /// assert_eq!(res.status_ref(), Some(&StatusCode::NOT_FOUND));
+    /// ```
+    pub fn status_ref(&self) -> Option<&StatusCode> {
+        self.inner.as_ref().ok().map(|h| &h.status)
+    }
+
+    /// Get a mutable reference to the HTTP status for this response.
+    ///
+    /// If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut res = Response::builder();
+    /// *res.status_mut().unwrap() = StatusCode::ACCEPTED;
+    /// assert_eq!(res.status_ref(), Some(&StatusCode::ACCEPTED));
+    ///
+    /// let mut res = Response::builder().status(1000);
+    /// assert!(res.status_mut().is_none());
+    /// ```
+    pub fn status_mut(&mut self) -> Option<&mut StatusCode> {
+        self.inner.as_mut().ok().map(|h| &mut h.status)
+    }
+
     /// Set the HTTP version for this response.
     ///
     /// By default this is HTTP/1.1
@@ -589,6 +1027,39 @@ impl Builder {
         })
     }
 
+    /// Get the HTTP version for this response.
+    ///
+    /// By default this is HTTP/1.1. If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let res = Response::builder().version(Version::HTTP_2);
+    /// assert_eq!(res.version_ref(), Some(&Version::HTTP_2));
+    /// ```
+    pub fn version_ref(&self) -> Option<&Version> {
+        self.inner.as_ref().ok().map(|h| &h.version)
+    }
+
+    /// Get a mutable reference to the HTTP version for this response.
+    ///
+    /// If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut res = Response::builder();
+    /// *res.version_mut().unwrap() = Version::HTTP_10;
+    /// assert_eq!(res.version_ref(), Some(&Version::HTTP_10));
+    /// ```
+    pub fn version_mut(&mut self) -> Option<&mut Version> {
+        self.inner.as_mut().ok().map(|h| &mut h.version)
+    }
+
     /// Appends a header to this response builder.
     ///
     /// This function will append the provided key/value as a header to the
@@ -623,6 +1094,81 @@ impl Builder {
         })
     }
 
+    /// Appends every header in `headers` to this response builder.
+    ///
+    /// This is equivalent to calling [`header`](Builder::header) for each
+    /// value, in order, so a prepared set of default headers can be added in
+    /// one call. Headers already on the builder are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
+    /// let mut defaults = HeaderMap::new();
+    /// defaults.insert(USER_AGENT, HeaderValue::from_static("my-agent/1.0"));
+    /// defaults.append(ACCEPT, HeaderValue::from_static("text/html"));
+    /// defaults.append(ACCEPT, HeaderValue::from_static("*/*"));
+    ///
+    /// let res = Response::builder()
+    ///     .header(ACCEPT, "application/json")
+    ///     .headers(defaults)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(res.headers()[USER_AGENT], "my-agent/1.0");
+    /// assert_eq!(res.headers().get_all(ACCEPT).iter().count(), 3);
+    /// ```
+    pub fn headers(self, headers: HeaderMap<HeaderValue>) -> Builder {
+        self.and_then(move |mut head| {
+            let mut name = None;
+            for (key, value) in headers {
+                if key.is_some() {
+                    name = key;
+                }
+                // The first value yielded for each name carries the name.
+                let name = name.clone().expect("HeaderMap yields a name first");
+                head.headers.try_append(name, value)?;
+            }
+            Ok(head)
+        })
+    }
+
+    /// Appends a header to this response builder if `value` is `Some`.
+    ///
+    /// When `value` is `None` the builder is returned unchanged, and `key`
+    /// is not checked. This keeps optional headers, such as an auth token,
+    /// within a single builder chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let token: Option<&str> = None;
+    /// let trace_id = Some("4bf92f3577b34da6");
+    ///
+    /// let res = Response::builder()
+    ///     .header_opt("authorization", token)
+    ///     .header_opt("x-trace-id", trace_id)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert!(!res.headers().contains_key("authorization"));
+    /// assert_eq!(res.headers()["x-trace-id"], "4bf92f3577b34da6");
+    /// ```
+    pub fn header_opt<K, V>(self, key: K, value: Option<V>) -> Builder
+    where
+        K: TryInto<HeaderName>,
+        <K as TryInto<HeaderName>>::Error: Into<crate::Error>,
+        V: TryInto<HeaderValue>,
+        <V as TryInto<HeaderValue>>::Error: Into<crate::Error>,
+    {
+        match value {
+            Some(value) => self.header(key, value),
+            None => self,
+        }
+    }
+
     /// Get header on this response builder.
     ///
     /// When builder has error returns None.
@@ -726,6 +1272,37 @@ impl Builder {
         self.inner.as_mut().ok().map(|h| &mut h.extensions)
     }
 
+    /// Sets the trailers for this response, replacing any set before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::{HeaderMap, HeaderValue};
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    ///
+    /// let response = Response::builder()
+    ///     .trailers(trailers)
+    ///     .body(())
+    ///     .unwrap();
+    /// assert_eq!(response.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    pub fn trailers(self, trailers: HeaderMap<HeaderValue>) -> Builder {
+        self.and_then(move |mut head| {
+            head.trailers = Some(trailers);
+            Ok(head)
+        })
+    }
+
+    /// Get the trailers for this response builder.
+    ///
+    /// If the builder has an error or no trailers were set, this returns
+    /// `None`.
+    pub fn trailers_ref(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.inner.as_ref().ok().and_then(|h| h.trailers.as_ref())
+    }
+
     /// "Consumes" this builder, using the provided `body` to return a
     /// constructed `Response`.
     ///
@@ -756,9 +1333,18 @@ impl Builder {
     where
         F: FnOnce(Parts) -> Result<Parts>,
     {
-        Builder {
-            inner: self.inner.and_then(func),
-        }
+        // After an error, later inputs are still checked against a blank
+        // head so that all of their errors are reported together.
+        let inner = match self.inner {
+            Ok(head) => func(head),
+            Err(mut err) => {
+                if let Err(next) = func(Parts::new()) {
+                    err.push(next);
+                }
+                Err(err)
+            }
+        };
+        Builder { inner }
     }
 }
 
@@ -771,9 +1357,181 @@ impl Default for Builder {
     }
 }
 
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::Parts;
+    use crate::extensions::{ExtensionRegistry, ExtensionTypes, PartsSeed, RegisteredParts};
+    use crate::header::{HeaderMap, HeaderValue};
+    use crate::{Extensions, StatusCode, Version};
+    use serde::de::{
+        self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    };
+    use serde::ser::{self, Serialize, SerializeStruct, Serializer};
+    use std::fmt;
+
+    // Parts are written as a struct of their fields. Extensions can hold any
+    // type, so they are only written through an `ExtensionRegistry`, as a
+    // last `extensions` field. Otherwise serializing fails unless there are
+    // none, and deserialized parts have none.
+    const FIELDS: &[&str] = &["status", "version", "headers", "trailers", "extensions"];
+
+    impl Serialize for Parts {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self, None::<&ExtensionRegistry>, serializer)
+        }
+    }
+
+    impl<'a, L: ExtensionTypes> Serialize for RegisteredParts<'a, Parts, L> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(self.parts, Some(self.registry), serializer)
+        }
+    }
+
+    fn serialize<S: Serializer, L: ExtensionTypes>(
+        parts: &Parts,
+        registry: Option<&ExtensionRegistry<L>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if registry.is_none() && !parts.extensions.is_empty() {
+            return Err(ser::Error::custom(
+                "response extensions cannot be serialized",
+            ));
+        }
+        let len = FIELDS.len() - registry.is_none() as usize;
+        let mut state = serializer.serialize_struct("Parts", len)?;
+        state.serialize_field("status", &parts.status)?;
+        state.serialize_field("version", &parts.version)?;
+        state.serialize_field("headers", &parts.headers)?;
+        state.serialize_field("trailers", &parts.trailers)?;
+        if let Some(registry) = registry {
+            state.serialize_field("extensions", &registry.extensions(&parts.extensions))?;
+        }
+        state.end()
+    }
+
+    impl<'de> de::Deserialize<'de> for Parts {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let visitor = PartsVisitor {
+                registry: None::<&ExtensionRegistry>,
+            };
+            deserializer.deserialize_struct("Parts", FIELDS, visitor)
+        }
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> DeserializeSeed<'de> for PartsSeed<'a, Parts, L> {
+        type Value = Parts;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Parts, D::Error> {
+            let visitor = PartsVisitor {
+                registry: Some(self.registry),
+            };
+            deserializer.deserialize_struct("Parts", FIELDS, visitor)
+        }
+    }
+
+    struct PartsVisitor<'a, L> {
+        registry: Option<&'a ExtensionRegistry<L>>,
+    }
+
+    impl<'a, 'de, L: ExtensionTypes> Visitor<'de> for PartsVisitor<'a, L> {
+        type Value = Parts;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("response parts")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Parts, A::Error> {
+            let status: StatusCode = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let version: Version = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            let headers: HeaderMap<HeaderValue> = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+            // Trailers may be left out, as in a map.
+            let trailers = seq.next_element()?.unwrap_or(None);
+            let extensions = match self.registry {
+                Some(registry) => seq
+                    .next_element_seed(registry.extensions_seed())?
+                    .unwrap_or_default(),
+                None => Extensions::new(),
+            };
+            Ok(Parts {
+                status,
+                version,
+                headers,
+                trailers,
+                extensions,
+                ..Parts::new()
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Parts, A::Error> {
+            let mut status = None;
+            let mut version = None;
+            let mut headers = None;
+            let mut trailers = None;
+            let mut extensions = None;
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "status" => {
+                        if status.is_some() {
+                            return Err(de::Error::duplicate_field("status"));
+                        }
+                        status = Some(map.next_value::<StatusCode>()?);
+                    }
+                    "version" => {
+                        if version.is_some() {
+                            return Err(de::Error::duplicate_field("version"));
+                        }
+                        version = Some(map.next_value::<Version>()?);
+                    }
+                    "headers" => {
+                        if headers.is_some() {
+                            return Err(de::Error::duplicate_field("headers"));
+                        }
+                        headers = Some(map.next_value::<HeaderMap<HeaderValue>>()?);
+                    }
+                    "trailers" => {
+                        if trailers.is_some() {
+                            return Err(de::Error::duplicate_field("trailers"));
+                        }
+                        trailers = Some(map.next_value::<Option<HeaderMap<HeaderValue>>>()?);
+                    }
+                    "extensions" => {
+                        if extensions.is_some() {
+                            return Err(de::Error::duplicate_field("extensions"));
+                        }
+                        extensions = Some(match self.registry {
+                            Some(registry) => map.next_value_seed(registry.extensions_seed())?,
+                            None => {
+                                map.next_value_seed(ExtensionRegistry::new().extensions_seed())?
+                            }
+                        });
+                    }
+                    _ => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            Ok(Parts {
+                status: status.ok_or_else(|| de::Error::missing_field("status"))?,
+                version: version.ok_or_else(|| de::Error::missing_field("version"))?,
+                headers: headers.ok_or_else(|| de::Error::missing_field("headers"))?,
+                trailers: trailers.unwrap_or(None),
+                extensions: extensions.unwrap_or_default(),
+                ..Parts::new()
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::convert::TryFrom;
 
     #[test]
     fn it_can_map_a_body_from_one_type_to_another() {
@@ -784,4 +1542,93 @@ mod tests {
         });
         assert_eq!(mapped_response.body(), &123u32);
     }
+
+    #[test]
+    fn encode_head() {
+        fn encode(response: Response<()>) -> Vec<u8> {
+            let mut buf = Vec::new();
+            response.encode_head(&mut buf);
+            buf
+        }
+
+        assert_eq!(encode(Response::default()), b"HTTP/1.1 200 OK\r\n\r\n");
+
+        let response = Response::builder()
+            .status(599)
+            .version(Version::HTTP_10)
+            .header("x-a", "1")
+            .body(())
+            .unwrap();
+        assert_eq!(encode(response), b"HTTP/1.0 599 \r\nx-a: 1\r\n\r\n");
+
+        let mut response = Response::new(());
+        *response.status_mut() = StatusCode::NOT_FOUND;
+        response
+            .extensions_mut()
+            .insert(ReasonPhrase::try_from(&b"Gone Fishing \xff"[..]).unwrap());
+        assert_eq!(encode(response), b"HTTP/1.1 404 Gone Fishing \xff\r\n\r\n");
+    }
+
+    #[test]
+    fn parse_parts() {
+        let parts = Parts::parse(b"HTTP/1.0 599\nx-a: 1\n").unwrap();
+        assert_eq!(parts.status.as_u16(), 599);
+        assert_eq!(parts.version, Version::HTTP_10);
+        assert_eq!(parts.headers["x-a"], "1");
+        assert_eq!(
+            parts.extensions.get::<ReasonPhrase>().unwrap().as_bytes(),
+            b""
+        );
+
+        let parts = Parts::parse(b"HTTP/1.1 200 \xffOK \r\n").unwrap();
+        let response = Response::from_parts(parts, ());
+        let mut buf = Vec::new();
+        response.encode_head(&mut buf);
+        assert_eq!(buf, b"HTTP/1.1 200 \xffOK \r\n\r\n");
+
+        let err = |src: &[u8]| Parts::parse(src).unwrap_err().to_string();
+        assert_eq!(err(b"HTTP/1.1\r\n"), "malformed start line at byte 0");
+        assert_eq!(err(b"HTTP/2 200 OK\r\n"), "invalid HTTP version at byte 0");
+        assert_eq!(err(b"HTTP/1.1 20 OK\r\n"), "invalid status code at byte 9");
+        assert_eq!(
+            err(b"HTTP/1.1 200 O\x7fK\r\n"),
+            "invalid reason phrase at byte 13"
+        );
+        assert_eq!(
+            err(b"HTTP/1.1 200 OK\r\n folded\r\n"),
+            "obsolete line folding at byte 17"
+        );
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn serde_parts() {
+        let (parts, _) = Response::builder()
+            .status(StatusCode::CREATED)
+            .version(Version::HTTP_10)
+            .header("location", "/items/1")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let json = serde_json::to_string(&parts).unwrap();
+        assert_eq!(
+            json,
+            r#"{"status":"201","version":"HTTP/1.0","headers":{"location":["/items/1"]},"trailers":null}"#
+        );
+        let back: Parts = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.status, parts.status);
+        assert_eq!(back.version, parts.version);
+        assert_eq!(back.headers, parts.headers);
+        assert!(back.trailers.is_none());
+
+        let err = serde_json::from_str::<Parts>(r#"{"status":"201","status":"202"}"#).unwrap_err();
+        assert!(err.to_string().contains("duplicate field `status`"));
+
+        let parts = Parts::parse(b"HTTP/1.1 200 OK\r\n").unwrap();
+        assert!(serde_json::to_string(&parts)
+            .unwrap_err()
+            .to_string()
+            .contains("response extensions cannot be serialized"));
+    }
 }
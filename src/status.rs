@@ -18,7 +18,10 @@ use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::num::NonZeroU16;
-use std::str::FromStr;
+use std::ops::{Range, RangeInclusive};
+use std::str::{self, FromStr};
+
+use bytes::Bytes;
 
 /// An HTTP status code (`status-code` in RFC 9110 et al.).
 ///
@@ -44,6 +47,89 @@ use std::str::FromStr;
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StatusCode(NonZeroU16);
 
+/// The class of a status code, given by its first digit.
+///
+/// Returned by [`StatusCode::class`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusClass {
+    /// 1xx: the request was received, continuing process.
+    Informational,
+    /// 2xx: the request was successfully received, understood, and accepted.
+    Success,
+    /// 3xx: further action needs to be taken to complete the request.
+    Redirection,
+    /// 4xx: the request contains bad syntax or cannot be fulfilled.
+    ClientError,
+    /// 5xx: the server failed to fulfill an apparently valid request.
+    ServerError,
+}
+
+/// An inclusive range of status codes.
+///
+/// Ranges can be built from `u16` ranges, a single [`StatusCode`] or a
+/// [`StatusClass`], so retry and alerting policies can be written as plain
+/// lists of ranges.
+///
+/// # Example
+///
+/// ```
+/// use http::status::{StatusClass, StatusCodeRange};
+/// use http::StatusCode;
+///
+/// let retry: [StatusCodeRange; 3] = [
+///     StatusCode::TOO_MANY_REQUESTS.into(),
+///     (502..=504).into(),
+///     StatusClass::Informational.into(),
+/// ];
+/// let should_retry = |status| retry.iter().any(|r| r.contains(status));
+///
+/// assert!(should_retry(StatusCode::BAD_GATEWAY));
+/// assert!(should_retry(StatusCode::TOO_MANY_REQUESTS));
+/// assert!(!should_retry(StatusCode::INTERNAL_SERVER_ERROR));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StatusCodeRange {
+    start: u16,
+    end: u16,
+}
+
+/// The reason phrase of an HTTP/1.1 response, as sent by the server.
+///
+/// `StatusCode` only knows the canonical reason for each code. A proxy that
+/// wants to forward the exact phrase it received can store a `ReasonPhrase`
+/// in the response [`Extensions`](crate::Extensions) and write it back out
+/// instead of the canonical one.
+///
+/// A reason phrase may contain tabs, spaces, visible ASCII and `obs-text`
+/// bytes (0x80 to 0xFF). It can be empty.
+///
+/// # Example
+///
+/// ```
+/// use http::status::ReasonPhrase;
+/// use http::{Response, StatusCode};
+/// use std::convert::TryFrom;
+///
+/// let reason = ReasonPhrase::try_from("Not Here").unwrap();
+/// let mut response = Response::new(());
+/// *response.status_mut() = StatusCode::NOT_FOUND;
+/// response.extensions_mut().insert(reason);
+///
+/// let reason = response.extensions().get::<ReasonPhrase>().unwrap();
+/// assert_eq!(reason.as_bytes(), b"Not Here");
+/// assert!(ReasonPhrase::try_from("Not\r\nHere").is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReasonPhrase(Bytes);
+
+/// A possible error when converting to a `ReasonPhrase`.
+///
+/// This error indicates that the input contained a control character other
+/// than horizontal tab.
+pub struct InvalidReasonPhrase {
+    _priv: (),
+}
+
 /// A possible error value when converting a `StatusCode` from a `u16` or `&str`
 ///
 /// This error indicates that the supplied input was not a valid number, was less
@@ -69,15 +155,55 @@ impl StatusCode {
     /// let err = StatusCode::from_u16(99);
     /// assert!(err.is_err());
     /// ```
+    ///
+    /// This is a `const fn`, but `Result::unwrap` is not, so use
+    /// [`from_static`](StatusCode::from_static) to define constants.
     #[inline]
-    pub fn from_u16(src: u16) -> Result<StatusCode, InvalidStatusCode> {
-        if !(100..1000).contains(&src) {
+    pub const fn from_u16(src: u16) -> Result<StatusCode, InvalidStatusCode> {
+        if src < 100 || src >= 1000 {
             return Err(InvalidStatusCode::new());
         }
 
-        NonZeroU16::new(src)
-            .map(StatusCode)
-            .ok_or_else(InvalidStatusCode::new)
+        // Safety: `src` is at least 100.
+        Ok(StatusCode(unsafe { NonZeroU16::new_unchecked(src) }))
+    }
+
+    /// Converts a u16 to a status code, in a constant context.
+    ///
+    /// This allows tables of status codes to be `static` arrays of
+    /// `StatusCode` rather than of raw `u16`s.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument is less than 100 or greater than
+    /// 999. When used to define a constant, this is a compile error, though
+    /// its message is going to look cryptic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::StatusCode;
+    ///
+    /// static RETRYABLE: [StatusCode; 3] = [
+    ///     StatusCode::from_static(429),
+    ///     StatusCode::from_static(503),
+    ///     StatusCode::from_static(504),
+    /// ];
+    /// assert_eq!(RETRYABLE[1], StatusCode::SERVICE_UNAVAILABLE);
+    /// ```
+    #[allow(unconditional_panic)] // required for the panic circumvention
+    pub const fn from_static(src: u16) -> StatusCode {
+        match StatusCode::from_u16(src) {
+            Ok(status) => status,
+            Err(_) => {
+                // TODO: When msrv is bumped to larger than 1.57, this should be
+                // replaced with `panic!` macro.
+                // https://blog.rust-lang.org/2021/12/02/Rust-1.57.0.html#panic-in-const-contexts
+                #[allow(clippy::no_effect, clippy::out_of_bounds_indexing)]
+                ([] as [u8; 0])[0]; // Invalid status code
+                StatusCode::OK
+            }
+        }
     }
 
     /// Converts a &[u8] to a status code
@@ -117,7 +243,7 @@ impl StatusCode {
     /// ```
     #[inline]
     pub const fn as_u16(&self) -> u16 {
-        (*self).0.get()
+        self.0.get()
     }
 
     /// Returns a &str representation of the `StatusCode`
@@ -172,6 +298,36 @@ impl StatusCode {
         canonical_reason(self.0.get())
     }
 
+    /// Returns the class of the status code.
+    ///
+    /// Returns `None` for codes above 599, which have no defined class.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::status::{StatusClass, StatusCode};
+    ///
+    /// let outcome = match StatusCode::NOT_FOUND.class() {
+    ///     Some(StatusClass::Success) => "ok",
+    ///     Some(StatusClass::ClientError) => "client error",
+    ///     Some(_) => "other",
+    ///     None => "unknown",
+    /// };
+    /// assert_eq!(outcome, "client error");
+    /// assert_eq!(StatusCode::from_u16(600).unwrap().class(), None);
+    /// ```
+    #[inline]
+    pub fn class(&self) -> Option<StatusClass> {
+        match self.0.get() / 100 {
+            1 => Some(StatusClass::Informational),
+            2 => Some(StatusClass::Success),
+            3 => Some(StatusClass::Redirection),
+            4 => Some(StatusClass::ClientError),
+            5 => Some(StatusClass::ServerError),
+            _ => None,
+        }
+    }
+
     /// Check if status is within 100-199.
     #[inline]
     pub fn is_informational(&self) -> bool {
@@ -201,6 +357,55 @@ impl StatusCode {
     pub fn is_server_error(&self) -> bool {
         600 > self.0.get() && self.0.get() >= 500
     }
+
+    /// Whether a response with this status may be stored by a cache that
+    /// computes its own freshness lifetime.
+    ///
+    /// These are the "heuristically cacheable" codes: 200, 203, 204, 206,
+    /// 300, 301, 308, 404, 405, 410, 414 and 501. Responses with other
+    /// codes need explicit freshness information, such as
+    /// `Cache-Control: max-age`, before a cache can reuse them.
+    ///
+    /// See [the spec](https://www.rfc-editor.org/rfc/rfc9110#section-15.1)
+    /// for more words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert!(StatusCode::NOT_FOUND.is_cacheable_by_default());
+    /// assert!(!StatusCode::FOUND.is_cacheable_by_default());
+    /// ```
+    pub fn is_cacheable_by_default(&self) -> bool {
+        matches!(
+            self.0.get(),
+            200 | 203 | 204 | 206 | 300 | 301 | 308 | 404 | 405 | 410 | 414 | 501
+        )
+    }
+
+    /// Whether a response with this status never carries a message body.
+    ///
+    /// This is true for informational (1xx) responses, 204 No Content and
+    /// 304 Not Modified. Such responses end after their header section
+    /// regardless of any `Content-Length` or `Transfer-Encoding` they carry.
+    ///
+    /// Responses to HEAD requests, and 2xx responses to CONNECT, also have no
+    /// body; that depends on the request and is not considered here.
+    ///
+    /// See [the spec](https://www.rfc-editor.org/rfc/rfc9110#section-6.4.1)
+    /// for more words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert!(StatusCode::NO_CONTENT.expects_empty_body());
+    /// assert!(StatusCode::CONTINUE.expects_empty_body());
+    /// assert!(!StatusCode::OK.expects_empty_body());
+    /// ```
+    pub fn expects_empty_body(&self) -> bool {
+        self.is_informational() || matches!(self.0.get(), 204 | 304)
+    }
 }
 
 impl fmt::Debug for StatusCode {
@@ -298,6 +503,196 @@ impl TryFrom<u16> for StatusCode {
     }
 }
 
+impl StatusCodeRange {
+    /// Creates a range containing the codes from `start` to `end`, inclusive.
+    ///
+    /// The range is empty if `start` is greater than `end`.
+    #[inline]
+    pub const fn new(start: u16, end: u16) -> StatusCodeRange {
+        StatusCodeRange { start, end }
+    }
+
+    /// Returns the lowest code in the range.
+    #[inline]
+    pub fn start(&self) -> u16 {
+        self.start
+    }
+
+    /// Returns the highest code in the range.
+    #[inline]
+    pub fn end(&self) -> u16 {
+        self.end
+    }
+
+    /// Returns `true` if the range contains no codes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Returns `true` if `status` is within the range.
+    #[inline]
+    pub fn contains(&self, status: StatusCode) -> bool {
+        self.start <= status.as_u16() && status.as_u16() <= self.end
+    }
+}
+
+impl From<RangeInclusive<u16>> for StatusCodeRange {
+    #[inline]
+    fn from(range: RangeInclusive<u16>) -> StatusCodeRange {
+        StatusCodeRange::new(*range.start(), *range.end())
+    }
+}
+
+impl From<Range<u16>> for StatusCodeRange {
+    #[inline]
+    fn from(range: Range<u16>) -> StatusCodeRange {
+        match range.end.checked_sub(1) {
+            Some(end) => StatusCodeRange::new(range.start, end),
+            None => StatusCodeRange::new(1, 0),
+        }
+    }
+}
+
+impl From<u16> for StatusCodeRange {
+    #[inline]
+    fn from(code: u16) -> StatusCodeRange {
+        StatusCodeRange::new(code, code)
+    }
+}
+
+impl From<StatusCode> for StatusCodeRange {
+    #[inline]
+    fn from(status: StatusCode) -> StatusCodeRange {
+        StatusCodeRange::from(status.as_u16())
+    }
+}
+
+impl ReasonPhrase {
+    /// Converts a static string to a reason phrase.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument contains invalid characters. As
+    /// with [`HeaderValue::from_static`](crate::HeaderValue::from_static),
+    /// the panic message in a constant is going to look cryptic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::status::ReasonPhrase;
+    /// const GONE_FISHING: ReasonPhrase = ReasonPhrase::from_static("Gone Fishing");
+    /// assert_eq!(GONE_FISHING.as_bytes(), b"Gone Fishing");
+    /// ```
+    #[allow(unconditional_panic)] // required for the panic circumvention
+    pub const fn from_static(src: &'static str) -> ReasonPhrase {
+        let bytes = src.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !is_reason_phrase_char(bytes[i]) {
+                // TODO: When msrv is bumped to larger than 1.57, this should be
+                // replaced with `panic!` macro.
+                // https://blog.rust-lang.org/2021/12/02/Rust-1.57.0.html#panic-in-const-contexts
+                #[allow(clippy::no_effect, clippy::out_of_bounds_indexing)]
+                ([] as [u8; 0])[0]; // Invalid reason phrase
+            }
+            i += 1;
+        }
+        ReasonPhrase(Bytes::from_static(bytes))
+    }
+
+    /// Returns the reason phrase as a byte slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the reason phrase as a string slice, if it is valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        str::from_utf8(&self.0).ok()
+    }
+
+    fn from_shared(src: Bytes) -> Result<ReasonPhrase, InvalidReasonPhrase> {
+        if src.iter().all(|&b| is_reason_phrase_char(b)) {
+            Ok(ReasonPhrase(src))
+        } else {
+            Err(InvalidReasonPhrase { _priv: () })
+        }
+    }
+}
+
+// reason-phrase = 1*( HTAB / SP / VCHAR / obs-text )
+const fn is_reason_phrase_char(b: u8) -> bool {
+    b == b'\t' || (b >= b' ' && b != 0x7f)
+}
+
+impl fmt::Debug for ReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_str() {
+            Some(s) => fmt::Debug::fmt(s, f),
+            None => fmt::Debug::fmt(&self.0, f),
+        }
+    }
+}
+
+impl AsRef<[u8]> for ReasonPhrase {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(t: &'a [u8]) -> Result<Self, Self::Error> {
+        ReasonPhrase::from_shared(Bytes::copy_from_slice(t))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(t: &'a str) -> Result<Self, Self::Error> {
+        ReasonPhrase::try_from(t.as_bytes())
+    }
+}
+
+impl TryFrom<Vec<u8>> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(t: Vec<u8>) -> Result<Self, Self::Error> {
+        ReasonPhrase::from_shared(Bytes::from(t))
+    }
+}
+
+impl TryFrom<String> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(t: String) -> Result<Self, Self::Error> {
+        ReasonPhrase::try_from(t.into_bytes())
+    }
+}
+
+impl From<StatusClass> for StatusCodeRange {
+    #[inline]
+    fn from(class: StatusClass) -> StatusCodeRange {
+        let start = match class {
+            StatusClass::Informational => 100,
+            StatusClass::Success => 200,
+            StatusClass::Redirection => 300,
+            StatusClass::ClientError => 400,
+            StatusClass::ServerError => 500,
+        };
+        StatusCodeRange::new(start, start + 99)
+    }
+}
+
 macro_rules! status_codes {
     (
         $(
@@ -311,6 +706,33 @@ macro_rules! status_codes {
             pub const $konst: StatusCode = StatusCode(unsafe { NonZeroU16::new_unchecked($num) });
         )+
 
+            /// Every status code with a constant, in ascending order.
+            ///
+            /// These are the codes with a
+            /// [`canonical_reason`](StatusCode::canonical_reason), which makes
+            /// this useful for generating documentation and exhaustive tests.
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use http::StatusCode;
+            ///
+            /// let redirects: Vec<u16> = StatusCode::ALL
+            ///     .iter()
+            ///     .filter(|s| s.is_redirection())
+            ///     .map(StatusCode::as_u16)
+            ///     .collect();
+            /// assert_eq!(redirects, [300, 301, 302, 303, 304, 305, 307, 308]);
+            ///
+            /// for status in StatusCode::ALL {
+            ///     assert!(status.canonical_reason().is_some());
+            /// }
+            /// ```
+            pub const ALL: &'static [StatusCode] = &[
+            $(
+                StatusCode::$konst,
+            )+
+            ];
         }
 
         fn canonical_reason(num: u16) -> Option<&'static str> {
@@ -334,6 +756,9 @@ status_codes! {
     /// 102 Processing
     /// [[RFC2518, Section 10.1](https://datatracker.ietf.org/doc/html/rfc2518#section-10.1)]
     (102, PROCESSING, "Processing");
+    /// 103 Early Hints
+    /// [[RFC8297, Section 2](https://datatracker.ietf.org/doc/html/rfc8297#section-2)]
+    (103, EARLY_HINTS, "Early Hints");
 
     /// 200 OK
     /// [[RFC9110, Section 15.3.1](https://datatracker.ietf.org/doc/html/rfc9110#section-15.3.1)]
@@ -523,7 +948,7 @@ status_codes! {
 }
 
 impl InvalidStatusCode {
-    fn new() -> InvalidStatusCode {
+    pub(crate) const fn new() -> InvalidStatusCode {
         InvalidStatusCode { _priv: () }
     }
 }
@@ -544,6 +969,22 @@ impl fmt::Display for InvalidStatusCode {
 
 impl Error for InvalidStatusCode {}
 
+impl fmt::Debug for InvalidReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidReasonPhrase")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid reason phrase")
+    }
+}
+
+impl Error for InvalidReasonPhrase {}
+
 // A string of packed 3-ASCII-digit status code values for the supported range
 // of [100, 999] (900 codes, 2700 bytes).
 const CODE_DIGITS: &str = "\
@@ -592,3 +1033,136 @@ const CODE_DIGITS: &str = "\
 940941942943944945946947948949950951952953954955956957958959\
 960961962963964965966967968969970971972973974975976977978979\
 980981982983984985986987988989990991992993994995996997998999";
+
+#[cfg(feature = "serde1")]
+pub use self::serde1::with_reason;
+
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::StatusCode;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::convert::TryFrom;
+    use std::fmt;
+
+    // Human readable formats get the three digit string. Compact formats get
+    // the plain `u16`.
+    impl Serialize for StatusCode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.serialize_str(self.as_str())
+            } else {
+                serializer.serialize_u16(self.as_u16())
+            }
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for StatusCode {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let visitor = StatusCodeVisitor { reason: false };
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(visitor)
+            } else {
+                deserializer.deserialize_u16(visitor)
+            }
+        }
+    }
+
+    // `reason` allows strings to carry a reason phrase after the code.
+    struct StatusCodeVisitor {
+        reason: bool,
+    }
+
+    /// (De)serializes a `StatusCode` together with its reason phrase.
+    ///
+    /// For use with `#[serde(with = "http::status::with_reason")]`. Human
+    /// readable formats get strings such as `"404 Not Found"`, or just the
+    /// code when it has no canonical reason. Deserializing accepts that form,
+    /// a bare code as a string, or a number, so hand-written fixtures can use
+    /// whichever is most convenient. Compact formats get the plain `u16`, as
+    /// with the default implementation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::status::with_reason;
+    /// use http::StatusCode;
+    ///
+    /// let mut json = Vec::new();
+    /// with_reason::serialize(&StatusCode::NOT_FOUND, &mut serde_json::Serializer::new(&mut json))
+    ///     .unwrap();
+    /// assert_eq!(json, br#""404 Not Found""#);
+    ///
+    /// for fixture in &[r#""404 Not Found""#, r#""404 Missing""#, r#""404""#, "404"] {
+    ///     let mut de = serde_json::Deserializer::from_str(fixture);
+    ///     assert_eq!(with_reason::deserialize(&mut de).unwrap(), StatusCode::NOT_FOUND);
+    /// }
+    /// ```
+    pub mod with_reason {
+        use super::StatusCodeVisitor;
+        use crate::StatusCode;
+        use serde::de::Deserializer;
+        use serde::ser::Serializer;
+
+        /// Serializes `status` with its canonical reason phrase.
+        pub fn serialize<S: Serializer>(
+            status: &StatusCode,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            if !serializer.is_human_readable() {
+                return serializer.serialize_u16(status.as_u16());
+            }
+            match status.canonical_reason() {
+                Some(reason) => {
+                    serializer.collect_str(&format_args!("{} {}", status.as_str(), reason))
+                }
+                None => serializer.serialize_str(status.as_str()),
+            }
+        }
+
+        /// Deserializes a status code, with or without a reason phrase.
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<StatusCode, D::Error> {
+            let visitor = StatusCodeVisitor { reason: true };
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(visitor)
+            } else {
+                deserializer.deserialize_u16(visitor)
+            }
+        }
+    }
+
+    impl<'de> Visitor<'de> for StatusCodeVisitor {
+        type Value = StatusCode;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a status code between 100 and 999")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<StatusCode, E> {
+            u16::try_from(v)
+                .ok()
+                .and_then(|v| StatusCode::from_u16(v).ok())
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<StatusCode, E> {
+            u16::try_from(v)
+                .ok()
+                .and_then(|v| StatusCode::from_u16(v).ok())
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(v), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<StatusCode, E> {
+            // The reason phrase is informational only, so any text is accepted.
+            let code = if self.reason && v.len() > 3 && v.as_bytes()[3] == b' ' {
+                &v[..3]
+            } else {
+                v
+            };
+            StatusCode::from_bytes(code.as_bytes())
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
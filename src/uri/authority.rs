@@ -22,7 +22,7 @@ impl Authority {
     }
 
     // Not public while `bytes` is unstable.
-    pub(super) fn from_shared(s: Bytes) -> Result<Self, InvalidUri> {
+    pub(crate) fn from_shared(s: Bytes) -> Result<Self, InvalidUri> {
         // Precondition on create_authority: trivially satisfied by the
         // identity closure
         create_authority(s, |s| s)
@@ -261,6 +261,20 @@ impl Authority {
     pub fn as_str(&self) -> &str {
         &self.data[..]
     }
+
+    /// Returns this authority with any userinfo removed.
+    pub(super) fn without_userinfo(&self) -> Authority {
+        match self.as_str().rfind('@') {
+            Some(at) => {
+                let bytes = Bytes::from(self.data.clone()).slice(at + 1..);
+                // Safety: the data was valid UTF-8, and is split right after
+                // an ASCII character.
+                let data = unsafe { ByteStr::from_utf8_unchecked(bytes) };
+                Authority { data }
+            }
+            None => self.clone(),
+        }
+    }
 }
 
 // Purposefully not public while `bytes` is unstable.
@@ -302,7 +316,7 @@ impl PartialEq<Authority> for str {
     }
 }
 
-impl<'a> PartialEq<Authority> for &'a str {
+impl PartialEq<Authority> for &str {
     fn eq(&self, other: &Authority) -> bool {
         self.eq_ignore_ascii_case(other.as_str())
     }
@@ -360,7 +374,7 @@ impl PartialOrd<Authority> for str {
     }
 }
 
-impl<'a> PartialOrd<Authority> for &'a str {
+impl PartialOrd<Authority> for &str {
     fn partial_cmp(&self, other: &Authority) -> Option<cmp::Ordering> {
         let left = self.as_bytes().iter().map(|b| b.to_ascii_lowercase());
         let right = other.data.as_bytes().iter().map(|b| b.to_ascii_lowercase());
@@ -18,7 +18,7 @@ const NONE: u16 = u16::MAX;
 
 impl PathAndQuery {
     // Not public while `bytes` is unstable.
-    pub(super) fn from_shared(mut src: Bytes) -> Result<Self, InvalidUri> {
+    pub(crate) fn from_shared(mut src: Bytes) -> Result<Self, InvalidUri> {
         let mut query = NONE;
         let mut fragment = None;
 
@@ -276,6 +276,158 @@ impl PathAndQuery {
         }
         ret
     }
+
+    /// Returns `true` if the path contains a `.` or `..` segment.
+    ///
+    /// Percent-encoded dots (`%2e`) are treated the same as literal ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/static/%2e%2e/secret".parse().unwrap();
+    ///
+    /// assert!(path_and_query.contains_dot_segments());
+    /// ```
+    pub fn contains_dot_segments(&self) -> bool {
+        self.path().split('/').any(|seg| dot_segment(seg).is_some())
+    }
+
+    /// Returns `true` if the path is absolute and contains no dot segments or
+    /// empty segments.
+    ///
+    /// A normalized path is returned unchanged by [`sanitize`](#method.sanitize),
+    /// unless it contains an encoded separator, which `sanitize` rejects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let normalized: PathAndQuery = "/static/app.js?v=1".parse().unwrap();
+    /// let unnormalized: PathAndQuery = "/static//app.js".parse().unwrap();
+    ///
+    /// assert!(normalized.is_normalized());
+    /// assert!(!unnormalized.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        let path = self.path();
+        if path == "*" {
+            return true;
+        }
+        if !path.starts_with('/') {
+            return false;
+        }
+        let mut segments = path[1..].split('/').peekable();
+        while let Some(seg) = segments.next() {
+            // A trailing slash leaves one final empty segment, which is fine.
+            let last = segments.peek().is_none();
+            if (seg.is_empty() && !last) || dot_segment(seg).is_some() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Resolves `.` and `..` segments and collapses repeated slashes.
+    ///
+    /// The query is preserved as is. An error is returned if a `..` segment
+    /// would escape above the root, or if a segment contains a backslash or
+    /// an encoded `/`, `\` or NUL (`%2F`, `%5C` or `%00`), which a file
+    /// system could otherwise treat as a separator once decoded.
+    ///
+    /// This makes the result safe to join onto a base directory as long as
+    /// it is not decoded first. Any other percent-encoding is left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/static/./css/../app.js?v=1".parse().unwrap();
+    /// assert_eq!(path_and_query.sanitize().unwrap(), "/static/app.js?v=1");
+    ///
+    /// let escaping: PathAndQuery = "/static/../../etc/passwd".parse().unwrap();
+    /// assert!(escaping.sanitize().is_err());
+    ///
+    /// let encoded: PathAndQuery = "/static/..%2f..%2fetc/passwd".parse().unwrap();
+    /// assert!(encoded.sanitize().is_err());
+    /// ```
+    pub fn sanitize(&self) -> Result<PathAndQuery, InvalidUri> {
+        if self.path().split('/').any(unsafe_segment) {
+            return Err(ErrorKind::UnsafePathSegment.into());
+        }
+        if self.is_normalized() {
+            return Ok(self.clone());
+        }
+
+        let mut segments = Vec::new();
+        let mut trailing_slash = false;
+        for seg in self.path().split('/') {
+            trailing_slash = true;
+            match dot_segment(seg) {
+                Some(1) => {}
+                Some(_) => {
+                    if segments.pop().is_none() {
+                        return Err(ErrorKind::PathEscapesRoot.into());
+                    }
+                }
+                None if seg.is_empty() => {}
+                None => {
+                    segments.push(seg);
+                    trailing_slash = false;
+                }
+            }
+        }
+
+        let mut dst = String::with_capacity(self.data.len());
+        for seg in &segments {
+            dst.push('/');
+            dst.push_str(seg);
+        }
+        if trailing_slash || segments.is_empty() {
+            dst.push('/');
+        }
+        if let Some(query) = self.query() {
+            dst.push('?');
+            dst.push_str(query);
+        }
+
+        PathAndQuery::from_shared(Bytes::from(dst))
+    }
+}
+
+/// Returns `true` if `seg` contains a backslash, or a percent-encoded slash,
+/// backslash or NUL.
+fn unsafe_segment(seg: &str) -> bool {
+    let bytes = seg.as_bytes();
+    bytes.contains(&b'\\')
+        || bytes.windows(3).any(|w| {
+            w[0] == b'%'
+                && matches!(
+                    (w[1], w[2].to_ascii_lowercase()),
+                    (b'2', b'f') | (b'5', b'c') | (b'0', b'0')
+                )
+        })
+}
+
+/// Returns the number of dots in `seg` if it is a `.` or `..` segment,
+/// accounting for percent-encoded dots.
+fn dot_segment(seg: &str) -> Option<usize> {
+    let mut rest = seg.as_bytes();
+    let mut dots = 0;
+    while !rest.is_empty() {
+        if rest[0] == b'.' {
+            rest = &rest[1..];
+        } else if rest.len() >= 3 && rest[..3].eq_ignore_ascii_case(b"%2e") {
+            rest = &rest[3..];
+        } else {
+            return None;
+        }
+        dots += 1;
+    }
+    match dots {
+        1 | 2 => Some(dots),
+        _ => None,
+    }
 }
 
 impl<'a> TryFrom<&'a [u8]> for PathAndQuery {
@@ -369,7 +521,7 @@ impl PartialEq<str> for PathAndQuery {
     }
 }
 
-impl<'a> PartialEq<PathAndQuery> for &'a str {
+impl PartialEq<PathAndQuery> for &str {
     #[inline]
     fn eq(&self, other: &PathAndQuery) -> bool {
         self == &other.as_str()
@@ -432,7 +584,7 @@ impl<'a> PartialOrd<&'a str> for PathAndQuery {
     }
 }
 
-impl<'a> PartialOrd<PathAndQuery> for &'a str {
+impl PartialOrd<PathAndQuery> for &str {
     #[inline]
     fn partial_cmp(&self, other: &PathAndQuery) -> Option<cmp::Ordering> {
         self.partial_cmp(&other.as_str())
@@ -574,6 +726,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dot_segments() {
+        assert!(pq("/a/./b").contains_dot_segments());
+        assert!(pq("/a/..").contains_dot_segments());
+        assert!(pq("/a/%2E%2e/b").contains_dot_segments());
+        assert!(pq("/a/.%2e").contains_dot_segments());
+        assert!(!pq("/a/.../b").contains_dot_segments());
+        assert!(!pq("/a/.b/c.").contains_dot_segments());
+        assert!(!pq("/a/b?../..").contains_dot_segments());
+    }
+
+    #[test]
+    fn normalized_paths() {
+        assert!(pq("/").is_normalized());
+        assert!(pq("*").is_normalized());
+        assert!(pq("/a/b/").is_normalized());
+        assert!(pq("/a?x=/../").is_normalized());
+        assert!(!pq("/a//b").is_normalized());
+        assert!(!pq("/a/./b").is_normalized());
+        assert!(!pq("a/b").is_normalized());
+    }
+
+    #[test]
+    fn sanitize_resolves_dot_segments() {
+        assert_eq!(pq("/a/./b/../c").sanitize().unwrap(), "/a/c");
+        assert_eq!(pq("/a/b/..").sanitize().unwrap(), "/a/");
+        assert_eq!(pq("/a/b/.").sanitize().unwrap(), "/a/b/");
+        assert_eq!(pq("/a/%2e%2E/b?q=1").sanitize().unwrap(), "/b?q=1");
+        assert_eq!(pq("//a///b/").sanitize().unwrap(), "/a/b/");
+        assert_eq!(pq("/a/..").sanitize().unwrap(), "/");
+        assert_eq!(pq("a/b").sanitize().unwrap(), "/a/b");
+        assert_eq!(pq("/a/b").sanitize().unwrap(), "/a/b");
+    }
+
+    #[test]
+    fn sanitize_rejects_escaping_root() {
+        assert!(pq("/..").sanitize().is_err());
+        assert!(pq("/a/../../b").sanitize().is_err());
+        assert!(pq("/%2e%2e/etc/passwd").sanitize().is_err());
+    }
+
+    #[test]
+    fn sanitize_rejects_encoded_separators() {
+        assert!(pq("/a/..%2f..%2fetc/passwd").sanitize().is_err());
+        assert!(pq("/a/%2e%2e%2Fsecret").sanitize().is_err());
+        assert!(pq("/..\\..\\windows").sanitize().is_err());
+        assert!(pq("/a%5Cb").sanitize().is_err());
+        assert!(pq("/a%00/b").sanitize().is_err());
+        assert_eq!(pq("/a%20b/c?x=%2f").sanitize().unwrap(), "/a%20b/c?x=%2f");
+    }
+
     fn pq(s: &str) -> PathAndQuery {
         s.parse().expect(&format!("parsing {}", s))
     }
@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{ErrorKind, InvalidUri, PathAndQuery, MAX_LEN};
+
+/// Builds a `PathAndQuery` by appending query parameters under a length
+/// budget.
+///
+/// Names and values are percent-encoded as they are appended. Once a
+/// maximum length is set with [`max_len`](PathAndQueryBuilder::max_len), an
+/// append that would push the path and query past it fails without changing
+/// the builder, and the error reports which parameter did not fit. This lets
+/// a client split a large batch across several requests deterministically.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::PathAndQueryBuilder;
+/// let ids = ["1001", "1002", "1003", "1004"];
+/// let mut batches = Vec::new();
+/// let mut builder = PathAndQueryBuilder::new("/items").max_len(24);
+///
+/// for id in ids.iter() {
+///     if builder.append_pair("id", id).is_err() {
+///         batches.push(builder.build().unwrap());
+///         builder = PathAndQueryBuilder::new("/items").max_len(24);
+///         builder.append_pair("id", id).unwrap();
+///     }
+/// }
+/// batches.push(builder.build().unwrap());
+///
+/// assert_eq!(batches[0], "/items?id=1001&id=1002");
+/// assert_eq!(batches[1], "/items?id=1003&id=1004");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathAndQueryBuilder {
+    buf: String,
+    has_query: bool,
+    params: usize,
+    max_len: usize,
+}
+
+/// An error returned when a query parameter does not fit in a
+/// `PathAndQueryBuilder`'s length budget.
+pub struct QueryTooLong {
+    index: usize,
+    name: String,
+}
+
+impl PathAndQueryBuilder {
+    /// Creates a new builder starting from the given path.
+    ///
+    /// The path may already contain a query, in which case appended
+    /// parameters are added after it. A fragment is never sent in a request
+    /// target, so anything from the first `#` on is dropped. An empty path is
+    /// treated as `/`. The path is validated when
+    /// [`build`](PathAndQueryBuilder::build) is called.
+    pub fn new(path: &str) -> PathAndQueryBuilder {
+        let path = path.split('#').next().unwrap_or("");
+        let buf = if path.is_empty() { "/" } else { path };
+
+        PathAndQueryBuilder {
+            buf: buf.to_owned(),
+            has_query: buf.contains('?'),
+            params: 0,
+            max_len: MAX_LEN,
+        }
+    }
+
+    /// Sets the maximum length, in bytes, of the built path and query.
+    ///
+    /// Defaults to the largest length a `PathAndQuery` can hold. Gateways
+    /// commonly reject request targets larger than 8 KB.
+    pub fn max_len(self, max: usize) -> PathAndQueryBuilder {
+        PathAndQueryBuilder {
+            max_len: std::cmp::min(max, MAX_LEN),
+            ..self
+        }
+    }
+
+    /// Appends a `name=value` parameter to the query.
+    ///
+    /// Both parts are percent-encoded, leaving only unreserved characters
+    /// as-is. If the encoded parameter would exceed the maximum length, the
+    /// builder is left unchanged and an error naming the parameter is
+    /// returned.
+    pub fn append_pair(&mut self, name: &str, value: &str) -> Result<&mut Self, QueryTooLong> {
+        let added = 1 + encoded_len(name) + 1 + encoded_len(value);
+
+        if self.buf.len() + added > self.max_len {
+            return Err(QueryTooLong {
+                index: self.params,
+                name: name.to_owned(),
+            });
+        }
+
+        self.buf.push(if self.has_query { '&' } else { '?' });
+        encode_into(&mut self.buf, name);
+        self.buf.push('=');
+        encode_into(&mut self.buf, value);
+
+        self.has_query = true;
+        self.params += 1;
+        Ok(self)
+    }
+
+    /// Returns how many more bytes can be appended before reaching the
+    /// maximum length.
+    pub fn remaining(&self) -> usize {
+        self.max_len.saturating_sub(self.buf.len())
+    }
+
+    /// Builds the `PathAndQuery`.
+    ///
+    /// Fails if the starting path is not valid, or is itself longer than the
+    /// maximum length.
+    pub fn build(&self) -> Result<PathAndQuery, InvalidUri> {
+        if self.buf.len() > self.max_len {
+            return Err(ErrorKind::TooLong.into());
+        }
+
+        PathAndQuery::from_maybe_shared(self.buf.clone())
+    }
+}
+
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~'
+}
+
+fn encoded_len(s: &str) -> usize {
+    s.bytes()
+        .map(|b| if is_unreserved(b) { 1 } else { 3 })
+        .sum()
+}
+
+fn encode_into(dst: &mut String, s: &str) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    for b in s.bytes() {
+        if is_unreserved(b) {
+            dst.push(b as char);
+        } else {
+            dst.push('%');
+            dst.push(HEX[(b >> 4) as usize] as char);
+            dst.push(HEX[(b & 0x0f) as usize] as char);
+        }
+    }
+}
+
+impl QueryTooLong {
+    /// Returns the zero-based index of the parameter that did not fit,
+    /// counting only parameters appended to this builder.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the name of the parameter that did not fit.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for QueryTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryTooLong")
+            .field("index", &self.index)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl fmt::Display for QueryTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "query parameter {} ({:?}) exceeds the length limit",
+            self.index, self.name
+        )
+    }
+}
+
+impl Error for QueryTooLong {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_encoded_pairs() {
+        let mut builder = PathAndQueryBuilder::new("/search");
+        builder
+            .append_pair("q", "rust http")
+            .unwrap()
+            .append_pair("tag", "a&b=c")
+            .unwrap();
+
+        let pq = builder.build().unwrap();
+        assert_eq!(pq, "/search?q=rust%20http&tag=a%26b%3Dc");
+        assert_eq!(pq.query(), Some("q=rust%20http&tag=a%26b%3Dc"));
+    }
+
+    #[test]
+    fn extends_existing_query() {
+        let mut builder = PathAndQueryBuilder::new("/a?x=1");
+        builder.append_pair("y", "2").unwrap();
+        assert_eq!(builder.build().unwrap(), "/a?x=1&y=2");
+
+        assert_eq!(PathAndQueryBuilder::new("").build().unwrap(), "/");
+    }
+
+    #[test]
+    fn strips_fragment() {
+        let mut builder = PathAndQueryBuilder::new("/a#frag?z=9");
+        builder.append_pair("x", "1").unwrap();
+        assert_eq!(builder.build().unwrap(), "/a?x=1");
+
+        let mut builder = PathAndQueryBuilder::new("/a?y=2#frag");
+        builder.append_pair("x", "1").unwrap();
+        assert_eq!(builder.build().unwrap(), "/a?y=2&x=1");
+    }
+
+    #[test]
+    fn reports_overflowing_parameter() {
+        let mut builder = PathAndQueryBuilder::new("/p").max_len(12);
+        builder.append_pair("a", "1").unwrap();
+        builder.append_pair("b", "2").unwrap();
+        assert_eq!(builder.remaining(), 2);
+
+        let err = builder.append_pair("long", "3").unwrap_err();
+        assert_eq!(err.index(), 2);
+        assert_eq!(err.name(), "long");
+
+        // the failed append left the builder untouched
+        assert_eq!(builder.build().unwrap(), "/p?a=1&b=2");
+    }
+
+    #[test]
+    fn build_checks_path() {
+        assert!(PathAndQueryBuilder::new("/too-long")
+            .max_len(4)
+            .build()
+            .is_err());
+        assert!(PathAndQueryBuilder::new("/bad\npath").build().is_err());
+    }
+}
@@ -22,6 +22,30 @@
 use std::fmt;
 
 /// Represents a version of the HTTP spec.
+///
+/// Versions are ordered by protocol revision, so comparisons such as
+/// `version >= Version::HTTP_2` can gate features that a version introduced
+/// or removed.
+///
+/// Extension versions, made with [`from_static`](Version::from_static), are
+/// not HTTP revisions. They sort after every standard version only so that
+/// the ordering is total, and should be checked with
+/// [`is_extension`](Version::is_extension) before such a comparison.
+///
+/// # Examples
+///
+/// ```
+/// use http::Version;
+///
+/// assert!(Version::HTTP_10 < Version::HTTP_11);
+/// assert!(Version::HTTP_3 > Version::HTTP_2);
+///
+/// // `Connection` is not allowed from HTTP/2 on.
+/// let allows_connection_header =
+///     |version: Version| !version.is_extension() && version < Version::HTTP_2;
+/// assert!(allows_connection_header(Version::HTTP_11));
+/// assert!(!allows_connection_header(Version::HTTP_2));
+/// ```
 #[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
 pub struct Version(Http);
 
@@ -40,8 +64,91 @@ impl Version {
 
     /// `HTTP/3.0`
     pub const HTTP_3: Version = Version(Http::H3);
+
+    /// Converts a static string to a version.
+    ///
+    /// The standard versions are recognized, so `Version::from_static(&"HTTP/1.1")`
+    /// is `Version::HTTP_11`. Any other string is kept as an extension
+    /// version, which lets gateways for protocols that share HTTP's message
+    /// format, such as ICAP or RTSP, reuse `Request` and `Response`.
+    ///
+    /// The string is taken by a second reference so that a `Version` only
+    /// holds a thin pointer to it, and stays two words in size.
+    ///
+    /// Extension versions sort after every standard version, and compare
+    /// equal only to a version made from the same string. Their `Debug`
+    /// output is the string itself. They cannot be serialized, since they
+    /// could not be deserialized back into a `&'static str`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument is not of the form
+    /// `protocol/version` with only visible ASCII characters. The panic
+    /// message in a constant is going to look cryptic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::Version;
+    ///
+    /// const ICAP_10: Version = Version::from_static(&"ICAP/1.0");
+    ///
+    /// assert_eq!(format!("{:?}", ICAP_10), "ICAP/1.0");
+    /// assert_ne!(ICAP_10, Version::HTTP_10);
+    /// assert_eq!(Version::from_static(&"HTTP/1.1"), Version::HTTP_11);
+    /// ```
+    #[allow(unconditional_panic)] // required for the panic circumvention
+    pub const fn from_static(src: &'static &'static str) -> Version {
+        match src.as_bytes() {
+            b"HTTP/0.9" => Version::HTTP_09,
+            b"HTTP/1.0" => Version::HTTP_10,
+            b"HTTP/1.1" => Version::HTTP_11,
+            b"HTTP/2.0" => Version::HTTP_2,
+            b"HTTP/3.0" => Version::HTTP_3,
+            bytes => {
+                let mut slash = 0;
+                let mut i = 0;
+                while i < bytes.len() {
+                    let b = bytes[i];
+                    if b == b'/' && slash == 0 {
+                        slash = i;
+                    } else if b <= b' ' || b >= 0x7f {
+                        slash = 0;
+                        break;
+                    }
+                    i += 1;
+                }
+
+                if slash == 0 || slash + 1 == bytes.len() {
+                    // TODO: When msrv is bumped to larger than 1.57, this should be
+                    // replaced with `panic!` macro.
+                    // https://blog.rust-lang.org/2021/12/02/Rust-1.57.0.html#panic-in-const-contexts
+                    #[allow(clippy::no_effect, clippy::out_of_bounds_indexing)]
+                    ([] as [u8; 0])[0]; // Invalid version
+                }
+
+                Version(Http::Extension(src))
+            }
+        }
+    }
+
+    /// Returns `true` if this is an extension version made with
+    /// [`from_static`](Version::from_static), rather than an HTTP version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::Version;
+    ///
+    /// assert!(Version::from_static(&"RTSP/1.0").is_extension());
+    /// assert!(!Version::HTTP_3.is_extension());
+    /// ```
+    pub fn is_extension(&self) -> bool {
+        matches!(self.0, Http::Extension(_))
+    }
 }
 
+// The variants are listed in protocol order, which the derived `Ord` relies on.
 #[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
 enum Http {
     Http09,
@@ -49,6 +156,9 @@ enum Http {
     Http11,
     H2,
     H3,
+    // Behind a second reference, so that the whole enum is only a tag and a
+    // thin pointer.
+    Extension(&'static &'static str),
     __NonExhaustive,
 }
 
@@ -64,6 +174,7 @@ impl fmt::Debug for Version {
         use self::Http::*;
 
         f.write_str(match self.0 {
+            Extension(name) => name,
             Http09 => "HTTP/0.9",
             Http10 => "HTTP/1.0",
             Http11 => "HTTP/1.1",
@@ -73,3 +184,153 @@ impl fmt::Debug for Version {
         })
     }
 }
+
+#[cfg(feature = "serde1")]
+mod serde1 {
+    use super::Version;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::ser::{self, Serialize, Serializer};
+    use std::fmt;
+
+    // Versions are always written in their `HTTP/x.y` form. Reading also
+    // accepts the ALPN protocol ids, so values captured from a TLS handshake
+    // can be stored as-is. Extension versions are rejected, since reading
+    // them back would need a `&'static str`.
+    impl Serialize for Version {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if self.is_extension() {
+                return Err(ser::Error::custom(format_args!(
+                    "extension version `{:?}` cannot be serialized",
+                    self
+                )));
+            }
+            serializer.serialize_str(&format!("{:?}", self))
+        }
+    }
+
+    impl<'de> de::Deserialize<'de> for Version {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(VersionVisitor)
+        }
+    }
+
+    struct VersionVisitor;
+
+    impl<'de> Visitor<'de> for VersionVisitor {
+        type Value = Version;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an HTTP version such as \"HTTP/1.1\" or \"h2\"")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Version, E> {
+            const KNOWN: &[(&str, Version)] = &[
+                ("HTTP/0.9", Version::HTTP_09),
+                ("HTTP/1.0", Version::HTTP_10),
+                ("HTTP/1.1", Version::HTTP_11),
+                ("HTTP/2.0", Version::HTTP_2),
+                ("HTTP/2", Version::HTTP_2),
+                ("h2", Version::HTTP_2),
+                ("h2c", Version::HTTP_2),
+                ("HTTP/3.0", Version::HTTP_3),
+                ("HTTP/3", Version::HTTP_3),
+                ("h3", Version::HTTP_3),
+            ];
+
+            KNOWN
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(v))
+                .map(|&(_, version)| version)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_ord() {
+        use super::Version;
+
+        let mut versions = [
+            Version::HTTP_3,
+            Version::HTTP_11,
+            Version::HTTP_09,
+            Version::HTTP_2,
+            Version::HTTP_10,
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            [
+                Version::HTTP_09,
+                Version::HTTP_10,
+                Version::HTTP_11,
+                Version::HTTP_2,
+                Version::HTTP_3,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_static() {
+        use super::Version;
+
+        const RTSP_10: Version = Version::from_static(&"RTSP/1.0");
+
+        assert_eq!(RTSP_10, Version::from_static(&"RTSP/1.0"));
+        assert_ne!(RTSP_10, Version::from_static(&"RTSP/2.0"));
+        assert!(RTSP_10 > Version::HTTP_3);
+        assert_eq!(format!("{:?}", RTSP_10), "RTSP/1.0");
+        assert_eq!(Version::from_static(&"HTTP/2.0"), Version::HTTP_2);
+    }
+
+    #[test]
+    fn test_size() {
+        use super::Version;
+        use std::mem::size_of;
+
+        assert_eq!(size_of::<Version>(), 2 * size_of::<usize>());
+        assert_eq!(size_of::<Option<Version>>(), size_of::<Version>());
+    }
+
+    #[test]
+    fn test_from_static_invalid() {
+        use super::Version;
+
+        for invalid in &["ICAP", "/1.0", "ICAP/", "ICAP 1.0", "ICAP/1.0\r\n", ""] {
+            let result = std::panic::catch_unwind(|| Version::from_static(invalid));
+            assert!(result.is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_serde() {
+        use super::Version;
+        use serde_test::{
+            assert_de_tokens, assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Token,
+        };
+
+        assert_tokens(&Version::HTTP_09, &[Token::Str("HTTP/0.9")]);
+        assert_tokens(&Version::HTTP_10, &[Token::Str("HTTP/1.0")]);
+        assert_tokens(&Version::HTTP_11, &[Token::Str("HTTP/1.1")]);
+        assert_tokens(&Version::HTTP_2, &[Token::Str("HTTP/2.0")]);
+        assert_tokens(&Version::HTTP_3, &[Token::Str("HTTP/3.0")]);
+
+        assert_de_tokens(&Version::HTTP_11, &[Token::Str("http/1.1")]);
+        assert_de_tokens(&Version::HTTP_2, &[Token::Str("h2")]);
+        assert_de_tokens(&Version::HTTP_3, &[Token::Str("h3")]);
+        assert_de_tokens(&Version::HTTP_3, &[Token::Str("HTTP/3")]);
+
+        assert_de_tokens_error::<Version>(
+            &[Token::Str("HTTP/0.0")],
+            "invalid value: string \"HTTP/0.0\", expected an HTTP version such as \"HTTP/1.1\" or \"h2\"",
+        );
+        assert_ser_tokens_error(
+            &Version::from_static(&"ICAP/1.0"),
+            &[],
+            "extension version `ICAP/1.0` cannot be serialized",
+        );
+    }
+}
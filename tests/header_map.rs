@@ -1,3 +1,9 @@
+#![allow(
+    clippy::legacy_numeric_constants,
+    clippy::needless_range_loop,
+    clippy::redundant_static_lifetimes
+)]
+
 use http::header::*;
 use http::*;
 
@@ -87,6 +93,103 @@ fn reserve() {
     assert_eq!(headers.capacity(), reserved_cap, "unexpected reallocation");
 }
 
+#[test]
+fn allocated_bytes() {
+    let mut headers = HeaderMap::<usize>::default();
+    assert_eq!(headers.allocated_bytes(), 0);
+
+    headers.reserve(8);
+    let reserved = headers.allocated_bytes();
+    assert!(reserved > 0);
+
+    headers.insert(HOST, 1);
+    assert_eq!(
+        headers.allocated_bytes(),
+        reserved,
+        "unexpected reallocation"
+    );
+
+    headers.append(HOST, 2);
+    assert!(headers.allocated_bytes() > reserved);
+
+    headers.clear();
+    assert!(
+        headers.allocated_bytes() > reserved,
+        "clear keeps allocations"
+    );
+}
+
+#[test]
+fn small_map_grows() {
+    let mut headers = HeaderMap::<usize>::default();
+    let names: Vec<HeaderName> = (0..50)
+        .map(|i| format!("x-{}", i).parse().unwrap())
+        .collect();
+
+    headers.insert(names[0].clone(), 0);
+    let cap = headers.capacity();
+    let allocated = headers.allocated_bytes();
+    for (i, name) in names.iter().enumerate().take(cap).skip(1) {
+        headers.insert(name.clone(), i);
+    }
+    assert_eq!(headers.allocated_bytes(), allocated);
+
+    let small = headers.clone();
+    for (i, name) in names.iter().enumerate().skip(cap) {
+        headers.insert(name.clone(), i);
+    }
+    assert!(headers.capacity() > cap);
+
+    for (i, name) in names.iter().enumerate() {
+        assert_eq!(headers[name], i);
+        assert_eq!(small.get(name), if i < cap { Some(&i) } else { None });
+    }
+
+    for name in &names[1..] {
+        headers.remove(name);
+    }
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[&names[0]], 0);
+}
+
+#[test]
+fn with_limit() {
+    let mut headers: HeaderMap = HeaderMap::with_limit(3);
+
+    headers.try_insert(HOST, "a".parse().unwrap()).unwrap();
+    headers.try_append(ACCEPT, "b".parse().unwrap()).unwrap();
+    headers.try_append(ACCEPT, "c".parse().unwrap()).unwrap();
+    assert_eq!(headers.len(), 3);
+
+    assert!(headers.try_append(ACCEPT, "d".parse().unwrap()).is_err());
+    assert!(headers.try_insert(VIA, "d".parse().unwrap()).is_err());
+    assert!(headers
+        .try_entry(VIA)
+        .unwrap()
+        .or_try_insert("d".parse().unwrap())
+        .is_err());
+    match headers.entry(HOST) {
+        Entry::Occupied(mut e) => assert!(e.try_append("d".parse().unwrap()).is_err()),
+        Entry::Vacant(_) => panic!(),
+    }
+    assert_eq!(headers.len(), 3);
+
+    // replacing values frees up room again
+    headers.insert(ACCEPT, "e".parse().unwrap());
+    assert_eq!(headers.len(), 2);
+    headers.try_append(VIA, "f".parse().unwrap()).unwrap();
+    assert_eq!(headers.len(), 3);
+    assert_eq!(headers.limit(), 3);
+}
+
+#[test]
+#[should_panic]
+fn append_past_limit() {
+    let mut headers: HeaderMap = HeaderMap::with_limit(1);
+    headers.append(HOST, "a".parse().unwrap());
+    headers.append(HOST, "b".parse().unwrap());
+}
+
 #[test]
 fn drain() {
     let mut headers = HeaderMap::new();
@@ -282,6 +385,12 @@ fn as_header_name() {
     let s = String::from("host");
     assert_eq!(m.get(&s), expected);
     assert_eq!(m.get(s.as_str()), expected);
+
+    assert_eq!(m.get(&b"host"[..]), expected);
+    assert_eq!(m.get(&b"HOST"[..]), expected);
+    assert_eq!(m.get(&b"\xffhost"[..]), None);
+    assert_eq!(m[&b"host"[..]], v);
+    assert!(m.contains_key(&b"Host"[..]));
 }
 
 #[test]
@@ -383,6 +492,9 @@ const STD: &'static [HeaderName] = &[
     CONTENT_SECURITY_POLICY_REPORT_ONLY,
     CONTENT_TYPE,
     COOKIE,
+    CROSS_ORIGIN_EMBEDDER_POLICY,
+    CROSS_ORIGIN_OPENER_POLICY,
+    CROSS_ORIGIN_RESOURCE_POLICY,
     DNT,
     DATE,
     ETAG,
@@ -401,6 +513,7 @@ const STD: &'static [HeaderName] = &[
     LOCATION,
     MAX_FORWARDS,
     ORIGIN,
+    PERMISSIONS_POLICY,
     PRAGMA,
     PROXY_AUTHENTICATE,
     PROXY_AUTHORIZATION,
@@ -410,7 +523,12 @@ const STD: &'static [HeaderName] = &[
     REFERER,
     REFERRER_POLICY,
     RETRY_AFTER,
+    SEC_FETCH_DEST,
+    SEC_FETCH_MODE,
+    SEC_FETCH_SITE,
+    SEC_FETCH_USER,
     SERVER,
+    SERVER_TIMING,
     SET_COOKIE,
     STRICT_TRANSPORT_SECURITY,
     TE,
@@ -425,7 +543,11 @@ const STD: &'static [HeaderName] = &[
     WWW_AUTHENTICATE,
     X_CONTENT_TYPE_OPTIONS,
     X_DNS_PREFETCH_CONTROL,
+    X_FORWARDED_FOR,
+    X_FORWARDED_HOST,
+    X_FORWARDED_PROTO,
     X_FRAME_OPTIONS,
+    X_REAL_IP,
     X_XSS_PROTECTION,
 ];
 
@@ -672,3 +794,556 @@ fn ensure_miri_sharedreadonly_not_violated() {
 
     let _foo = &headers.iter().next();
 }
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serialize_as_map_of_lists() {
+    use serde_test::{assert_ser_tokens, Configure, Token};
+
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("text/html"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+
+    assert_ser_tokens(
+        &(&headers).readable(),
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("accept"),
+            Token::Seq { len: Some(1) },
+            Token::Str("text/html"),
+            Token::SeqEnd,
+            Token::Str("set-cookie"),
+            Token::Seq { len: Some(2) },
+            Token::Str("a=1"),
+            Token::Str("b=2"),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+
+    assert_ser_tokens(
+        &headers.collapsed().readable(),
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("accept"),
+            Token::Str("text/html"),
+            Token::Str("set-cookie"),
+            Token::Seq { len: Some(2) },
+            Token::Str("a=1"),
+            Token::Str("b=2"),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serialize_opaque_values_as_bytes() {
+    use serde_test::{assert_ser_tokens, Configure, Token};
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap());
+
+    assert_ser_tokens(
+        &headers.collapsed().readable(),
+        &[
+            Token::Map { len: Some(1) },
+            Token::Str("x-opaque"),
+            Token::Seq { len: Some(1) },
+            Token::Bytes(b"caf\xc3\xa9"),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+
+    assert_ser_tokens(
+        &headers.collapsed().compact(),
+        &[
+            Token::Map { len: Some(1) },
+            Token::Str("x-opaque"),
+            Token::Seq { len: Some(1) },
+            Token::Bytes(b"caf\xc3\xa9"),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn deserialize_single_and_list_values() {
+    let headers: HeaderMap = serde_json::from_str(
+        r#"{"Accept": "text/html", "set-cookie": ["a=1", "b=2"], "x-empty": []}"#,
+    )
+    .unwrap();
+
+    assert_eq!(headers.len(), 3);
+    assert_eq!(headers[ACCEPT], "text/html");
+    let cookies: Vec<_> = headers.get_all(SET_COOKIE).iter().collect();
+    assert_eq!(cookies, ["a=1", "b=2"]);
+    assert!(!headers.contains_key("x-empty"));
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn deserialize_round_trips() {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("text/html"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"\xff").unwrap());
+
+    let json = serde_json::to_string(&headers).unwrap();
+    assert_eq!(serde_json::from_str::<HeaderMap>(&json).unwrap(), headers);
+
+    let json = serde_json::to_string(&headers.collapsed()).unwrap();
+    assert_eq!(serde_json::from_str::<HeaderMap>(&json).unwrap(), headers);
+
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+    let json = serde_json::to_string(&headers.collapsed()).unwrap();
+    assert!(json.contains(r#""x-opaque":[[99,97,102,233]]"#));
+    assert_eq!(serde_json::from_str::<HeaderMap>(&json).unwrap(), headers);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn deserialize_with_hasher_round_trips_opaque_values() {
+    use serde_test::{assert_tokens, Configure, Token};
+    use std::collections::hash_map::RandomState;
+
+    let mut headers: HeaderMap<HeaderValue, RandomState> =
+        HeaderMap::with_hasher(RandomState::new());
+    headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+
+    let json = serde_json::to_string(&headers).unwrap();
+    let parsed: HeaderMap<HeaderValue, RandomState> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, headers);
+
+    assert_tokens(
+        &headers.compact(),
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("accept"),
+            Token::Seq { len: Some(1) },
+            Token::Bytes(b"*/*"),
+            Token::SeqEnd,
+            Token::Str("x-opaque"),
+            Token::Seq { len: Some(1) },
+            Token::Bytes(b"caf\xe9"),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn deserialize_names_the_failing_header() {
+    let err = serde_json::from_str::<HeaderMap>(r#"{"bad name": "x"}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid header name `bad name`"));
+
+    let err = serde_json::from_str::<HeaderMap>(r#"{"x-a": ["ok", "a\u0000b"]}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid value for header `x-a`"));
+}
+
+#[test]
+fn connection_close_by_version() {
+    let mut headers = HeaderMap::new();
+    assert!(headers.connection_close(Version::HTTP_09));
+    assert!(headers.connection_close(Version::HTTP_10));
+    assert!(!headers.connection_close(Version::HTTP_11));
+    assert!(!headers.connection_close(Version::HTTP_2));
+
+    headers.append(CONNECTION, "Keep-Alive".parse().unwrap());
+    assert!(!headers.connection_close(Version::HTTP_10));
+
+    headers.append(CONNECTION, "te, close".parse().unwrap());
+    assert!(headers.connection_close(Version::HTTP_11));
+}
+
+#[test]
+fn set_connection_close() {
+    let keep_alive = HeaderName::from_static("keep-alive");
+
+    let mut headers = HeaderMap::new();
+    headers.append(CONNECTION, "keep-alive, te".parse().unwrap());
+    headers.insert(keep_alive.clone(), "timeout=5".parse().unwrap());
+
+    headers.set_connection_close(true, Version::HTTP_10);
+    assert_eq!(headers[CONNECTION], "te");
+    assert!(!headers.contains_key(&keep_alive));
+    assert!(headers.connection_close(Version::HTTP_10));
+
+    headers.set_connection_close(true, Version::HTTP_11);
+    assert_eq!(headers[CONNECTION], "te, close");
+    assert!(headers.connection_close(Version::HTTP_11));
+
+    headers.remove(CONNECTION);
+    headers.set_connection_close(false, Version::HTTP_11);
+    assert!(!headers.contains_key(CONNECTION));
+
+    headers.insert(CONNECTION, "close".parse().unwrap());
+    headers.insert(keep_alive.clone(), "timeout=5".parse().unwrap());
+    headers.set_connection_close(false, Version::HTTP_2);
+    assert!(!headers.contains_key(CONNECTION));
+    assert!(!headers.contains_key(&keep_alive));
+}
+
+#[test]
+fn content_encodings_in_decoding_order() {
+    let mut headers = HeaderMap::new();
+    assert!(headers.content_encodings().is_empty());
+
+    headers.append(CONTENT_ENCODING, "x-gzip, , deflate".parse().unwrap());
+    headers.append(CONTENT_ENCODING, "identity,zstd".parse().unwrap());
+    assert_eq!(
+        headers.content_encodings(),
+        [
+            ContentCoding::Zstd,
+            ContentCoding::Identity,
+            ContentCoding::Deflate,
+            ContentCoding::Gzip,
+        ]
+    );
+    assert!(headers.content_encodings().iter().all(|c| c.is_known()));
+
+    headers.append(
+        CONTENT_ENCODING,
+        HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap(),
+    );
+    let codings = headers.content_encodings();
+    assert_eq!(codings[0], ContentCoding::Unknown(""));
+    assert!(!codings[0].is_known());
+}
+
+#[test]
+fn custom_hasher() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    let mut headers: HeaderMap<_, BuildHasherDefault<DefaultHasher>> =
+        HeaderMap::with_capacity_and_hasher(4, Default::default());
+
+    for i in 0..100 {
+        headers.insert(format!("x-{}", i).parse::<HeaderName>().unwrap(), i);
+    }
+    headers.insert(HOST, 100);
+    headers.append(HOST, 101);
+
+    for i in 0..100 {
+        assert_eq!(headers[format!("x-{}", i).as_str()], i);
+    }
+    assert_eq!(headers.get_all(HOST).iter().count(), 2);
+
+    match headers.entry(HOST) {
+        Entry::Occupied(e) => assert_eq!(e.remove_entry_mult().1.count(), 2),
+        Entry::Vacant(_) => panic!(),
+    }
+    assert!(!headers.contains_key(HOST));
+    assert_eq!(headers.len(), 100);
+}
+
+#[test]
+fn colliding_hasher_still_works() {
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    #[derive(Default)]
+    struct Colliding;
+
+    impl Hasher for Colliding {
+        fn write(&mut self, _: &[u8]) {}
+
+        fn finish(&self) -> u64 {
+            0
+        }
+    }
+
+    let mut headers = HeaderMap::with_hasher(BuildHasherDefault::<Colliding>::default());
+
+    for i in 0..2000 {
+        headers.insert(format!("x-{}", i).parse::<HeaderName>().unwrap(), i);
+    }
+
+    for i in 0..2000 {
+        assert_eq!(headers[format!("x-{}", i).as_str()], i);
+    }
+}
+
+#[test]
+fn new_secure() {
+    let mut headers = HeaderMap::new_secure();
+    assert!(headers.is_empty());
+    assert_eq!(headers.capacity(), 0);
+
+    for _ in 0..2 {
+        for i in 0..200 {
+            let name = format!("x-{}", i).parse::<HeaderName>().unwrap();
+            headers.insert(name, HeaderValue::from(i));
+        }
+        headers.append("x-7", HeaderValue::from_static("again"));
+
+        for i in 0..200 {
+            assert_eq!(headers[format!("x-{}", i).as_str()], i.to_string());
+        }
+        assert_eq!(headers.get_all("x-7").iter().count(), 2);
+        assert_eq!(headers.remove("x-0").unwrap(), "0");
+        assert_eq!(headers.len(), 200);
+
+        headers.clear();
+        assert!(headers.is_empty());
+    }
+}
+
+#[test]
+fn reorder_keeps_values_and_lookups() {
+    let mut headers = HeaderMap::new();
+    for i in 0..20 {
+        let name = format!("x-{}", i).parse::<HeaderName>().unwrap();
+        headers.insert(name, HeaderValue::from(i));
+    }
+    headers.append(ACCEPT, "a".parse().unwrap());
+    headers.append(USER_AGENT, "ua".parse().unwrap());
+    headers.append(ACCEPT, "b".parse().unwrap());
+    headers.append(HOST, "example.com".parse().unwrap());
+    headers.append(ACCEPT, "c".parse().unwrap());
+
+    headers.reorder(Profile::CURL);
+
+    let names: Vec<_> = headers.keys().map(|k| k.as_str().to_owned()).collect();
+    assert_eq!(&names[..3], ["host", "user-agent", "accept"]);
+    for (i, name) in names[3..].iter().enumerate() {
+        assert_eq!(*name, format!("x-{}", i));
+    }
+
+    let values: Vec<_> = headers.get_all(ACCEPT).iter().collect();
+    assert_eq!(values, ["a", "b", "c"]);
+    assert_eq!(headers.get_all(ACCEPT).iter().next_back().unwrap(), "c");
+    assert_eq!(headers["x-7"], "7");
+    assert_eq!(headers.len(), 25);
+
+    headers.remove(HOST);
+    headers.append(HOST, "other".parse().unwrap());
+    headers.reorder_by(|a, b| b.as_str().cmp(a.as_str()));
+
+    let values: Vec<_> = headers.iter().map(|(_, v)| v.clone()).collect();
+    assert_eq!(values.first().unwrap(), "9");
+    assert_eq!(values.last().unwrap(), "c");
+    assert_eq!(headers[HOST], "other");
+    assert_eq!(headers.remove(ACCEPT).unwrap(), "a");
+    assert!(!headers.contains_key(ACCEPT));
+}
+
+#[test]
+fn append_all_groups_runs() {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, "text/html".parse().unwrap());
+
+    let via = HeaderName::from_static("via");
+    headers.append_all(vec![
+        ("accept", HeaderValue::from_static("a")),
+        ("accept", HeaderValue::from_static("b")),
+        ("host", HeaderValue::from_static("example.com")),
+        ("accept", HeaderValue::from_static("c")),
+    ]);
+    headers.append_all(vec![
+        (&via, HeaderValue::from_static("1")),
+        (&via, HeaderValue::from_static("2")),
+    ]);
+    headers.append_all(Vec::<(HeaderName, HeaderValue)>::new());
+
+    let accept: Vec<_> = headers.get_all(ACCEPT).iter().collect();
+    assert_eq!(accept, ["text/html", "a", "b", "c"]);
+    assert_eq!(headers.get_all(&via).iter().count(), 2);
+    assert_eq!(headers[HOST], "example.com");
+    assert_eq!(headers.len(), 7);
+    assert_eq!(headers.keys_len(), 3);
+}
+
+#[test]
+fn try_append_all_respects_limit() {
+    let mut headers: HeaderMap = HeaderMap::with_limit(3);
+    let pairs = (0..10).map(|i| (VIA, HeaderValue::from(i)));
+
+    assert!(headers.try_append_all(pairs).is_err());
+    assert_eq!(headers.len(), 3);
+    let via: Vec<_> = headers.get_all(VIA).iter().collect();
+    assert_eq!(via, ["0", "1", "2"]);
+}
+
+#[test]
+fn strict_eq_checks_value_and_name_order() {
+    let mut a = HeaderMap::new();
+    a.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    a.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+    a.insert(HOST, HeaderValue::from_static("example.com"));
+
+    let mut b = a.clone();
+    assert!(a.strict_eq(&b));
+
+    // Same values in a different order are unequal either way.
+    let cookies = remove_all_values(&mut b, SET_COOKIE);
+    b.append(SET_COOKIE, cookies[1].clone());
+    b.append(SET_COOKIE, cookies[0].clone());
+    assert!(a != b);
+    assert!(!a.strict_eq(&b));
+
+    // Same values per name, but names in a different order.
+    let mut c = HeaderMap::new();
+    c.insert(HOST, HeaderValue::from_static("example.com"));
+    c.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    c.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+    assert!(a == c);
+    assert!(!a.strict_eq(&c));
+}
+
+#[test]
+fn encode_wire_format() {
+    let mut map = HeaderMap::new();
+    map.insert(HOST, HeaderValue::from_static("example.com"));
+    map.append(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    map.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    map.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+
+    let mut buf = b"HTTP/1.1 200 OK\r\n".to_vec();
+    map.encode(&mut buf);
+    assert_eq!(
+        &buf[17..],
+        &b"host: example.com\r\nx-content-type-options: nosniff\r\nset-cookie: a=1\r\nset-cookie: b=2\r\n"[..]
+    );
+    assert_eq!(map.encoded_len(), buf.len() - 17);
+
+    let mut buf = Vec::new();
+    map.encode_title_case(&mut buf);
+    assert_eq!(
+        buf,
+        &b"Host: example.com\r\nX-Content-Type-Options: nosniff\r\nSet-Cookie: a=1\r\nSet-Cookie: b=2\r\n"[..]
+    );
+
+    let mut buf = Vec::new();
+    HeaderMap::new().encode(&mut buf);
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn trailer_marks() {
+    let mut map = HeaderMap::new();
+    map.insert(CONTENT_TYPE, "application/grpc".parse().unwrap());
+    map.append("grpc-status", "0".parse().unwrap());
+    map.insert(HOST, "example.com".parse().unwrap());
+    map.append("grpc-message", "a".parse().unwrap());
+    assert_eq!(map.trailers().count(), 0);
+
+    assert!(map.set_trailer("grpc-status", true));
+    assert!(map.set_trailer("grpc-message", true));
+    map.append("grpc-message", "b".parse().unwrap());
+
+    let trailers: Vec<_> = map
+        .trailers()
+        .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+        .collect();
+    assert_eq!(
+        trailers,
+        [
+            ("grpc-status", "0"),
+            ("grpc-message", "a"),
+            ("grpc-message", "b")
+        ]
+    );
+    assert_eq!(map.iter().count(), 5);
+
+    map.remove("grpc-status");
+    map.insert("grpc-status", "1".parse().unwrap());
+    assert!(!map.is_trailer("grpc-status"));
+
+    assert!(map.set_trailer("grpc-message", false));
+    assert_eq!(map.trailers().count(), 0);
+}
+
+#[test]
+fn get_all_len_and_get() {
+    let mut map = HeaderMap::new();
+    for v in &["a", "b", "c"] {
+        map.append(VIA, v.parse().unwrap());
+    }
+    let via = map.get_all(VIA);
+    assert_eq!(via.len(), 3);
+    assert_eq!(via.get(0).unwrap(), "a");
+    assert_eq!(via.get(2).unwrap(), "c");
+    assert_eq!(via.len(), via.iter().count());
+
+    if let Entry::Occupied(mut e) = map.entry(VIA) {
+        e.insert("d".parse().unwrap());
+    }
+    assert_eq!(map.get_all(VIA).len(), 1);
+}
+
+#[test]
+fn split_off_moves_whole_names() {
+    let mut map = HeaderMap::new();
+    map.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    map.append(CONNECTION, "keep-alive".parse().unwrap());
+    map.append(VIA, "1.1 a".parse().unwrap());
+    map.append(CONNECTION, "upgrade".parse().unwrap());
+    map.append(VIA, "1.1 b".parse().unwrap());
+    map.insert("grpc-status", "0".parse().unwrap());
+    map.set_trailer("grpc-status", true);
+
+    let other = map.split_off(|name| name == CONNECTION || name == "grpc-status");
+
+    let kept: Vec<_> = map
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+        .collect();
+    assert_eq!(
+        kept,
+        [
+            ("content-type", "text/html"),
+            ("via", "1.1 a"),
+            ("via", "1.1 b")
+        ]
+    );
+    let moved: Vec<_> = other
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+        .collect();
+    assert_eq!(
+        moved,
+        [
+            ("connection", "keep-alive"),
+            ("connection", "upgrade"),
+            ("grpc-status", "0")
+        ]
+    );
+    assert!(other.is_trailer("grpc-status"));
+
+    let none = map.split_off(|_| false);
+    assert!(none.is_empty());
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn debug_redacts_credentials() {
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+    headers.insert(COOKIE, HeaderValue::from_static("session=abc"));
+    headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+
+    let debug = format!("{:?}", headers);
+    assert_eq!(
+        debug,
+        r#"{"authorization": <redacted>, "cookie": <redacted>, "accept": "*/*"}"#
+    );
+    assert_eq!(headers[AUTHORIZATION], "Bearer secret");
+
+    let mut counts = HeaderMap::<u32>::default();
+    counts.insert(SET_COOKIE, 2);
+    assert_eq!(format!("{:?}", counts), r#"{"set-cookie": <redacted>}"#);
+
+    assert_eq!(
+        format!("{:?}", headers.unredacted()),
+        r#"{"authorization": "Bearer secret", "cookie": "session=abc", "accept": "*/*"}"#
+    );
+}
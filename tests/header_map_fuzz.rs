@@ -1,3 +1,10 @@
+#![allow(
+    clippy::clone_on_copy,
+    clippy::get_first,
+    clippy::map_clone,
+    clippy::redundant_static_lifetimes
+)]
+
 use http::header::*;
 use http::*;
 
@@ -285,6 +292,9 @@ fn gen_header_name(g: &mut StdRng) -> HeaderName {
         header::CONTENT_SECURITY_POLICY_REPORT_ONLY,
         header::CONTENT_TYPE,
         header::COOKIE,
+        header::CROSS_ORIGIN_EMBEDDER_POLICY,
+        header::CROSS_ORIGIN_OPENER_POLICY,
+        header::CROSS_ORIGIN_RESOURCE_POLICY,
         header::DNT,
         header::DATE,
         header::ETAG,
@@ -303,6 +313,7 @@ fn gen_header_name(g: &mut StdRng) -> HeaderName {
         header::LOCATION,
         header::MAX_FORWARDS,
         header::ORIGIN,
+        header::PERMISSIONS_POLICY,
         header::PRAGMA,
         header::PROXY_AUTHENTICATE,
         header::PROXY_AUTHORIZATION,
@@ -313,12 +324,17 @@ fn gen_header_name(g: &mut StdRng) -> HeaderName {
         header::REFERRER_POLICY,
         header::REFRESH,
         header::RETRY_AFTER,
+        header::SEC_FETCH_DEST,
+        header::SEC_FETCH_MODE,
+        header::SEC_FETCH_SITE,
+        header::SEC_FETCH_USER,
         header::SEC_WEBSOCKET_ACCEPT,
         header::SEC_WEBSOCKET_EXTENSIONS,
         header::SEC_WEBSOCKET_KEY,
         header::SEC_WEBSOCKET_PROTOCOL,
         header::SEC_WEBSOCKET_VERSION,
         header::SERVER,
+        header::SERVER_TIMING,
         header::SET_COOKIE,
         header::STRICT_TRANSPORT_SECURITY,
         header::TE,
@@ -333,7 +349,11 @@ fn gen_header_name(g: &mut StdRng) -> HeaderName {
         header::WWW_AUTHENTICATE,
         header::X_CONTENT_TYPE_OPTIONS,
         header::X_DNS_PREFETCH_CONTROL,
+        header::X_FORWARDED_FOR,
+        header::X_FORWARDED_HOST,
+        header::X_FORWARDED_PROTO,
         header::X_FRAME_OPTIONS,
+        header::X_REAL_IP,
         header::X_XSS_PROTECTION,
     ];
 
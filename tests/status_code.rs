@@ -76,7 +76,200 @@ fn is_server_error() {
     assert!(!status_code(600).is_server_error());
 }
 
+#[test]
+fn registered_codes_have_reasons() {
+    // Every code in the IANA HTTP Status Code Registry, except the unused 306.
+    let registered = [
+        100, 101, 102, 103, 200, 201, 202, 203, 204, 205, 206, 207, 208, 226, 300, 301, 302, 303,
+        304, 305, 307, 308, 400, 401, 402, 403, 404, 405, 406, 407, 408, 409, 410, 411, 412, 413,
+        414, 415, 416, 417, 418, 421, 422, 423, 424, 425, 426, 428, 429, 431, 451, 500, 501, 502,
+        503, 504, 505, 506, 507, 508, 510, 511,
+    ];
+    for &code in &registered {
+        assert!(status_code(code).canonical_reason().is_some(), "{}", code);
+    }
+
+    let all: Vec<u16> = StatusCode::ALL.iter().map(StatusCode::as_u16).collect();
+    assert!(all.windows(2).all(|w| w[0] < w[1]));
+    for &code in &registered {
+        assert!(all.contains(&code), "{}", code);
+    }
+    assert_eq!(
+        all.len(),
+        (100..1000)
+            .filter(|&c| status_code(c).canonical_reason().is_some())
+            .count()
+    );
+
+    assert_eq!(StatusCode::EARLY_HINTS, 103);
+    assert_eq!(
+        StatusCode::EARLY_HINTS.canonical_reason(),
+        Some("Early Hints")
+    );
+}
+
+#[test]
+fn class() {
+    use http::status::StatusClass;
+
+    assert_eq!(status_code(100).class(), Some(StatusClass::Informational));
+    assert_eq!(status_code(299).class(), Some(StatusClass::Success));
+    assert_eq!(status_code(308).class(), Some(StatusClass::Redirection));
+    assert_eq!(status_code(499).class(), Some(StatusClass::ClientError));
+    assert_eq!(status_code(500).class(), Some(StatusClass::ServerError));
+    assert_eq!(status_code(600).class(), None);
+    assert_eq!(status_code(999).class(), None);
+}
+
+#[test]
+fn is_cacheable_by_default() {
+    let cacheable = [200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 414, 501];
+    for code in 100..1000 {
+        assert_eq!(
+            status_code(code).is_cacheable_by_default(),
+            cacheable.contains(&code),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn expects_empty_body() {
+    for code in 100..200 {
+        assert!(status_code(code).expects_empty_body());
+    }
+    assert!(StatusCode::NO_CONTENT.expects_empty_body());
+    assert!(StatusCode::NOT_MODIFIED.expects_empty_body());
+
+    assert!(!StatusCode::OK.expects_empty_body());
+    assert!(!StatusCode::RESET_CONTENT.expects_empty_body());
+    assert!(!StatusCode::NOT_FOUND.expects_empty_body());
+}
+
+#[test]
+fn from_static() {
+    const CODES: [StatusCode; 2] = [StatusCode::from_static(100), StatusCode::from_static(999)];
+    const NOT_A_CODE: bool = StatusCode::from_u16(1000).is_err();
+
+    assert_eq!(CODES[0], StatusCode::CONTINUE);
+    assert_eq!(CODES[1], 999);
+    assert!(NOT_A_CODE);
+}
+
+#[test]
+#[should_panic]
+fn from_static_invalid() {
+    StatusCode::from_static(99);
+}
+
+#[test]
+fn status_code_range() {
+    use http::status::{StatusClass, StatusCodeRange};
+
+    let range = StatusCodeRange::from(500..=599);
+    assert!(range.contains(status_code(500)));
+    assert!(range.contains(status_code(599)));
+    assert!(!range.contains(status_code(600)));
+    assert_eq!(range, StatusClass::ServerError.into());
+
+    let range = StatusCodeRange::from(400..500);
+    assert_eq!((range.start(), range.end()), (400, 499));
+    assert!(!range.contains(status_code(500)));
+
+    assert!(StatusCodeRange::from(0..0).is_empty());
+    assert!(StatusCodeRange::from(200..200).is_empty());
+    assert!(!StatusCodeRange::from(200..200).contains(status_code(200)));
+
+    let single = StatusCodeRange::from(StatusCode::NOT_FOUND);
+    assert_eq!(single, 404.into());
+    assert!(single.contains(StatusCode::NOT_FOUND));
+    assert!(!single.contains(StatusCode::GONE));
+}
+
+#[test]
+fn reason_phrase() {
+    use http::status::ReasonPhrase;
+    use std::convert::TryFrom;
+
+    let reason = ReasonPhrase::try_from("Totally\tFine").unwrap();
+    assert_eq!(reason.as_bytes(), b"Totally\tFine");
+    assert_eq!(reason.to_str(), Some("Totally\tFine"));
+    assert_eq!(format!("{:?}", reason), r#""Totally\tFine""#);
+
+    let obs_text = ReasonPhrase::try_from(&b"Caf\xe9"[..]).unwrap();
+    assert_eq!(obs_text.to_str(), None);
+
+    assert!(ReasonPhrase::try_from("").is_ok());
+    assert!(ReasonPhrase::try_from("a\nb").is_err());
+    assert!(ReasonPhrase::try_from(vec![b'a', 0x7f]).is_err());
+    assert!(ReasonPhrase::try_from(String::from("a\0")).is_err());
+    assert_eq!(
+        ReasonPhrase::from_static("OK"),
+        ReasonPhrase::try_from("OK").unwrap()
+    );
+}
+
+#[test]
+#[should_panic]
+fn reason_phrase_from_static_invalid() {
+    http::status::ReasonPhrase::from_static("Bad\r\n");
+}
+
 /// Helper method for readability
 fn status_code(status_code: u16) -> StatusCode {
     StatusCode::from_u16(status_code).unwrap()
 }
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serde_readable_and_compact() {
+    use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
+
+    assert_tokens(&StatusCode::NOT_FOUND.readable(), &[Token::Str("404")]);
+    assert_tokens(&StatusCode::NOT_FOUND.compact(), &[Token::U16(404)]);
+
+    // Human readable formats also accept a plain number.
+    assert_de_tokens(&StatusCode::NOT_FOUND.readable(), &[Token::U64(404)]);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serde_json() {
+    let json = serde_json::to_string(&StatusCode::IM_A_TEAPOT).unwrap();
+    assert_eq!(json, "\"418\"");
+    assert_eq!(
+        serde_json::from_str::<StatusCode>("418").unwrap(),
+        StatusCode::IM_A_TEAPOT
+    );
+    assert!(serde_json::from_str::<StatusCode>("1000").is_err());
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serde_with_reason() {
+    use http::status::with_reason;
+
+    fn to_json(status: StatusCode) -> String {
+        let mut out = Vec::new();
+        with_reason::serialize(&status, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn from_json(json: &str) -> serde_json::Result<StatusCode> {
+        with_reason::deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    assert_eq!(to_json(StatusCode::IM_A_TEAPOT), r#""418 I'm a teapot""#);
+    assert_eq!(to_json(status_code(999)), r#""999""#);
+
+    assert_eq!(from_json(r#""418 I'm a teapot""#).unwrap(), 418);
+    assert_eq!(from_json(r#""999""#).unwrap(), 999);
+    assert_eq!(from_json("200").unwrap(), 200);
+    assert!(from_json(r#""404Not Found""#).is_err());
+    assert!(from_json(r#""40 Not Found""#).is_err());
+    assert!(from_json("1000").is_err());
+
+    // The default impl still requires a bare code.
+    assert!(serde_json::from_str::<StatusCode>(r#""404 Not Found""#).is_err());
+}